@@ -0,0 +1,154 @@
+//! Coordinate reference systems of geometry columns
+//!
+//! The CRS of a geometry column is stored under the `crs` key of its GeoArrow extension metadata
+//! (see [`crate::extension`]), so it travels with the column through Polars operations and is
+//! written to, and read from, Arrow IPC files.
+
+use crate::error::{GeopolarsError, Result};
+use crate::extension::{extension_metadata, geoarrow_type, with_geoarrow_extension};
+use polars::prelude::{DataType, Series};
+use serde_json::{Map, Value};
+
+/// The CRS of a geometry column, if one was set
+///
+/// A CRS stored as a string (e.g. `"EPSG:4326"` or WKT) is returned as is. A CRS stored as a
+/// PROJJSON object is returned serialized to a JSON string, which PROJ also accepts as a CRS
+/// definition.
+pub fn crs(series: &Series) -> Result<Option<String>> {
+    let metadata = match extension_metadata(series) {
+        Some(metadata) => metadata,
+        None => return Ok(None),
+    };
+
+    let crs = match serde_json::from_str::<Value>(&metadata)?.get("crs") {
+        Some(Value::String(crs)) => Some(crs.clone()),
+        Some(Value::Null) | None => None,
+        Some(crs) => Some(crs.to_string()),
+    };
+    Ok(crs)
+}
+
+/// Set or, with `None`, remove the CRS of a geometry column
+///
+/// Only the CRS is replaced, any other extension metadata is kept. This does not transform the
+/// coordinates, use `to_crs` for that.
+pub fn set_crs(series: &Series, crs: Option<&str>) -> Result<Series> {
+    if !matches!(
+        series.dtype(),
+        DataType::Binary | DataType::List(_) | DataType::Struct(_)
+    ) {
+        return Err(GeopolarsError::UnsupportedCrsColumn(
+            series.dtype().to_string(),
+        ));
+    }
+
+    let mut metadata = match extension_metadata(series) {
+        Some(metadata) => match serde_json::from_str::<Value>(&metadata)? {
+            Value::Object(map) => map,
+            _ => Map::new(),
+        },
+        None => Map::new(),
+    };
+    match crs {
+        Some(crs) => metadata.insert("crs".to_string(), Value::String(crs.to_string())),
+        None => metadata.remove("crs"),
+    };
+
+    let metadata = if metadata.is_empty() {
+        None
+    } else {
+        Some(Value::Object(metadata).to_string())
+    };
+    with_geoarrow_extension(series, geoarrow_type(series)?, metadata)
+}
+
+/// Copy the CRS of `input` onto `output`, the result of an operation on `input`
+pub(crate) fn with_crs_of(output: Series, input: &Series) -> Result<Series> {
+    match crs(input)? {
+        Some(crs) => set_crs(&output, Some(&crs)),
+        None => Ok(output),
+    }
+}
+
+/// Check that two geometry columns can be combined in one operation
+///
+/// Columns without a CRS are assumed to match anything. The comparison is on the CRS strings, so
+/// the same CRS spelled differently (e.g. an EPSG code and its WKT) is reported as a mismatch.
+pub fn check_crs_match(left: &Series, right: &Series) -> Result<()> {
+    match (crs(left)?, crs(right)?) {
+        (Some(left), Some(right)) if left != right => {
+            Err(GeopolarsError::CrsMismatch { left, right })
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_crs_match, crs, set_crs};
+    use crate::error::GeopolarsError;
+    use crate::geoseries::GeoSeries;
+    use crate::util::{from_geom_vec, geometries};
+    use geo::{point, polygon, Geometry};
+    use polars::prelude::DataType;
+
+    #[test]
+    fn set_and_get() {
+        let series = from_geom_vec(&[Geometry::Point(point!(x: 0., y: 0.))]).unwrap();
+        assert_eq!(crs(&series).unwrap(), None);
+
+        let series = set_crs(&series, Some("EPSG:4326")).unwrap();
+        assert_eq!(crs(&series).unwrap(), Some("EPSG:4326".to_string()));
+
+        // Geometry operations keep the CRS of their input
+        let hull = series.convex_hull().unwrap();
+        assert_eq!(crs(&hull).unwrap(), Some("EPSG:4326".to_string()));
+
+        let series = set_crs(&series, None).unwrap();
+        assert_eq!(crs(&series).unwrap(), None);
+    }
+
+    #[test]
+    fn native_points() {
+        let polygons = from_geom_vec(&[Geometry::Polygon(polygon![
+            (x: 0., y: 0.),
+            (x: 2., y: 0.),
+            (x: 2., y: 2.),
+            (x: 0., y: 0.),
+        ])])
+        .unwrap();
+        let polygons = set_crs(&polygons, Some("EPSG:3857")).unwrap();
+
+        let centroids = polygons.centroid().unwrap();
+        assert!(matches!(centroids.dtype(), DataType::Struct(_)));
+        assert_eq!(crs(&centroids).unwrap(), Some("EPSG:3857".to_string()));
+        assert_eq!(
+            crs(&polygons.representative_point().unwrap()).unwrap(),
+            Some("EPSG:3857".to_string())
+        );
+
+        // The CRS survives Polars operations that rebuild the struct from its fields
+        assert_eq!(
+            crs(&centroids.slice(0, 1)).unwrap(),
+            Some("EPSG:3857".to_string())
+        );
+        assert_eq!(geometries(&centroids).unwrap().len(), 1);
+
+        let centroids = set_crs(&centroids, Some("EPSG:4326")).unwrap();
+        assert_eq!(crs(&centroids).unwrap(), Some("EPSG:4326".to_string()));
+    }
+
+    #[test]
+    fn mismatch() {
+        let series = from_geom_vec(&[Geometry::Point(point!(x: 0., y: 0.))]).unwrap();
+        let wgs84 = set_crs(&series, Some("EPSG:4326")).unwrap();
+        let web_mercator = set_crs(&series, Some("EPSG:3857")).unwrap();
+
+        assert!(check_crs_match(&wgs84, &series).is_ok());
+        assert!(check_crs_match(&wgs84, &wgs84).is_ok());
+        assert!(matches!(
+            wgs84.distance(&web_mercator),
+            Err(GeopolarsError::CrsMismatch { .. })
+        ));
+    }
+}
@@ -1,4 +1,5 @@
 use core::any::type_name;
+use geozero::error::GeozeroError;
 use polars::error::PolarsError;
 use thiserror::Error;
 
@@ -15,6 +16,36 @@ pub enum GeopolarsError {
         found: &'static str,
     },
 
+    #[error("CRS mismatch between geometry columns: {left} and {right}")]
+    CrsMismatch { left: String, right: String },
+
+    #[error("Geometry column has no CRS, set one with set_crs")]
+    MissingCrs,
+
+    #[error("Cannot store a CRS on a geometry column of type {0}")]
+    UnsupportedCrsColumn(String),
+
+    #[error("Expected longitudes and latitudes in WGS 84 (EPSG:4326 or OGC:CRS84), found CRS {0}")]
+    NotWgs84(String),
+
+    #[error("Unexpected Arrow type for a geometry column: {0}")]
+    UnexpectedArrowType(String),
+
+    #[error(
+        "Unsupported CRS {0}: without the proj feature only EPSG:4326, EPSG:3857, UTM zones \
+        (EPSG:32601-32660, EPSG:32701-32760), UPS (EPSG:32661, EPSG:32761), EPSG:3035 and \
+        EPSG:5070 are supported"
+    )]
+    UnsupportedCrs(String),
+
+    #[cfg(feature = "proj")]
+    #[error("No suitable transformation from {from} to {to}: {reason}")]
+    UnsuitableTransformation {
+        from: String,
+        to: String,
+        reason: String,
+    },
+
     #[cfg(feature = "proj")]
     #[error(transparent)]
     ProjCreateError(Box<ProjCreateError>),
@@ -25,6 +56,15 @@ pub enum GeopolarsError {
 
     #[error(transparent)]
     PolarsError(Box<PolarsError>),
+
+    #[error(transparent)]
+    GeozeroError(Box<GeozeroError>),
+
+    #[error(transparent)]
+    IOError(Box<std::io::Error>),
+
+    #[error(transparent)]
+    JsonError(Box<serde_json::Error>),
 }
 
 pub type Result<T> = std::result::Result<T, GeopolarsError>;
@@ -35,6 +75,24 @@ impl From<PolarsError> for GeopolarsError {
     }
 }
 
+impl From<GeozeroError> for GeopolarsError {
+    fn from(err: GeozeroError) -> Self {
+        Self::GeozeroError(Box::new(err))
+    }
+}
+
+impl From<std::io::Error> for GeopolarsError {
+    fn from(err: std::io::Error) -> Self {
+        Self::IOError(Box::new(err))
+    }
+}
+
+impl From<serde_json::Error> for GeopolarsError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::JsonError(Box::new(err))
+    }
+}
+
 #[cfg(feature = "proj")]
 impl From<ProjCreateError> for GeopolarsError {
     fn from(err: ProjCreateError) -> Self {
@@ -49,8 +107,6 @@ impl From<ProjError> for GeopolarsError {
     }
 }
 
-// TODO: remove?
-#[allow(dead_code)]
 /// Helper to go from geometry object to string name of geometry type
 /// Copied from
 /// https://github.com/georust/geo/blob/a1226940a674c7ac5d1db43d495520e418af8907/geo-types/src/geometry/mod.rs#L253-L269
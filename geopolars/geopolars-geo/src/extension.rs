@@ -0,0 +1,412 @@
+//! GeoArrow extension type information for geometry Series.
+//!
+//! Polars doesn't support Arrow extension types, so the extension name and metadata of a geometry
+//! column can't be stored in the Series' dtype. Instead they are attached to each Arrow chunk of
+//! the Series: WKB chunks are wrapped in an Arrow `Extension` data type, and list chunks store
+//! them in the metadata of the list's child field. Polars carries both along without inspecting
+//! them.
+//!
+//! Point columns are Polars structs, which Polars rebuilds from their fields, dropping any
+//! metadata on the struct itself. Polars takes the data type of each field from its Arrow chunks
+//! though, so point chunks carry the extension information as an `Extension` data type on their
+//! `x` field.
+
+use crate::error::{GeopolarsError, Result};
+use geoarrow::GeometryArray;
+use polars::export::arrow::array::{Array, BinaryArray, ListArray, PrimitiveArray, StructArray};
+use polars::export::arrow::datatypes::{DataType as ArrowDataType, Field as ArrowField};
+use polars::prelude::{
+    BinaryChunked, DataType, Float64Chunked, ListChunked, Series, StructChunked,
+};
+use polars::series::IntoSeries;
+
+/// Key of the child field metadata entry holding the extension name of a list chunk
+const EXTENSION_NAME_KEY: &str = "geopolars:extension:name";
+
+/// Key of the child field metadata entry holding the extension metadata of a list chunk
+const EXTENSION_METADATA_KEY: &str = "geopolars:extension:metadata";
+
+/// The logical geometry type of a geometry column, as defined by the GeoArrow specification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeoArrowType {
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    WKB,
+}
+
+impl GeoArrowType {
+    /// The name of the Arrow extension type for this geometry type
+    pub fn extension_name(&self) -> &'static str {
+        match self {
+            GeoArrowType::Point => "geoarrow.point",
+            GeoArrowType::LineString => "geoarrow.linestring",
+            GeoArrowType::Polygon => "geoarrow.polygon",
+            GeoArrowType::MultiPoint => "geoarrow.multipoint",
+            GeoArrowType::MultiLineString => "geoarrow.multilinestring",
+            GeoArrowType::MultiPolygon => "geoarrow.multipolygon",
+            GeoArrowType::WKB => "geoarrow.wkb",
+        }
+    }
+
+    pub fn from_extension_name(name: &str) -> Option<Self> {
+        let geoarrow_type = match name {
+            "geoarrow.point" => GeoArrowType::Point,
+            "geoarrow.linestring" => GeoArrowType::LineString,
+            "geoarrow.polygon" => GeoArrowType::Polygon,
+            "geoarrow.multipoint" => GeoArrowType::MultiPoint,
+            "geoarrow.multilinestring" => GeoArrowType::MultiLineString,
+            "geoarrow.multipolygon" => GeoArrowType::MultiPolygon,
+            "geoarrow.wkb" => GeoArrowType::WKB,
+            _ => return None,
+        };
+        Some(geoarrow_type)
+    }
+
+    /// Whether this is one of the multi-part geometry types
+    pub fn is_multi(&self) -> bool {
+        matches!(
+            self,
+            GeoArrowType::MultiPoint | GeoArrowType::MultiLineString | GeoArrowType::MultiPolygon
+        )
+    }
+}
+
+impl From<&GeometryArray> for GeoArrowType {
+    fn from(array: &GeometryArray) -> Self {
+        match array {
+            GeometryArray::Point(_) => GeoArrowType::Point,
+            GeometryArray::LineString(_) => GeoArrowType::LineString,
+            GeometryArray::Polygon(_) => GeoArrowType::Polygon,
+            GeometryArray::MultiPoint(_) => GeoArrowType::MultiPoint,
+            GeometryArray::MultiLineString(_) => GeoArrowType::MultiLineString,
+            GeometryArray::MultiPolygon(_) => GeoArrowType::MultiPolygon,
+            GeometryArray::WKB(_) => GeoArrowType::WKB,
+        }
+    }
+}
+
+/// The extension name and metadata attached to an Arrow chunk, if any
+pub fn chunk_extension(chunk: &dyn Array) -> Option<(&str, Option<&str>)> {
+    match chunk.data_type() {
+        ArrowDataType::Extension(name, _, metadata) => Some((name.as_str(), metadata.as_deref())),
+        ArrowDataType::LargeList(field) => field.metadata.get(EXTENSION_NAME_KEY).map(|name| {
+            (
+                name.as_str(),
+                field
+                    .metadata
+                    .get(EXTENSION_METADATA_KEY)
+                    .map(|metadata| metadata.as_str()),
+            )
+        }),
+        ArrowDataType::Struct(fields) => match fields.first().map(|field| field.data_type()) {
+            Some(ArrowDataType::Extension(name, _, metadata)) => {
+                Some((name.as_str(), metadata.as_deref()))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The GeoArrow type of a single Arrow chunk of a geometry column
+///
+/// The extension name attached to the chunk takes precedence. Without one, the type is inferred
+/// from the physical layout of the chunk, where list layouts are assumed to hold single-part
+/// geometries.
+pub fn chunk_geoarrow_type(chunk: &dyn Array) -> Result<GeoArrowType> {
+    if let Some(geoarrow_type) =
+        chunk_extension(chunk).and_then(|(name, _)| GeoArrowType::from_extension_name(name))
+    {
+        return Ok(geoarrow_type);
+    }
+
+    let geoarrow_type = match chunk.data_type().to_logical_type() {
+        ArrowDataType::Binary | ArrowDataType::LargeBinary => GeoArrowType::WKB,
+        ArrowDataType::Struct(_) => GeoArrowType::Point,
+        ArrowDataType::List(field) | ArrowDataType::LargeList(field) => {
+            match field.data_type().to_logical_type() {
+                ArrowDataType::Struct(_) => GeoArrowType::LineString,
+                ArrowDataType::List(_) | ArrowDataType::LargeList(_) => GeoArrowType::Polygon,
+                _ => return Err(unexpected_type(chunk)),
+            }
+        }
+        _ => return Err(unexpected_type(chunk)),
+    };
+    Ok(geoarrow_type)
+}
+
+fn unexpected_type(chunk: &dyn Array) -> GeopolarsError {
+    GeopolarsError::UnexpectedArrowType(format!("{:?}", chunk.data_type()))
+}
+
+/// The GeoArrow type of a geometry Series
+///
+/// See [`chunk_geoarrow_type`] for how the type is determined.
+pub fn geoarrow_type(series: &Series) -> Result<GeoArrowType> {
+    match series.chunks().first() {
+        Some(chunk) => chunk_geoarrow_type(&**chunk),
+        None => Ok(match series.dtype() {
+            DataType::Struct(_) => GeoArrowType::Point,
+            DataType::List(inner) if matches!(**inner, DataType::List(_)) => GeoArrowType::Polygon,
+            DataType::List(_) => GeoArrowType::LineString,
+            _ => GeoArrowType::WKB,
+        }),
+    }
+}
+
+/// The extension metadata of a geometry Series. Per the GeoArrow specification this is a JSON
+/// object serialized to a string.
+pub fn extension_metadata(series: &Series) -> Option<String> {
+    series
+        .chunks()
+        .first()
+        .and_then(|chunk| chunk_extension(&**chunk))
+        .and_then(|(_, metadata)| metadata.map(|metadata| metadata.to_string()))
+}
+
+/// Attach a GeoArrow extension name and metadata to every chunk of a geometry Series
+///
+/// Series that aren't geometry columns are returned unchanged.
+pub fn with_geoarrow_extension(
+    series: &Series,
+    geoarrow_type: GeoArrowType,
+    metadata: Option<String>,
+) -> Result<Series> {
+    match series.dtype() {
+        DataType::Binary => {
+            let chunks = series
+                .chunks()
+                .iter()
+                .map(|chunk| extension_array(&**chunk, geoarrow_type, metadata.clone()))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(BinaryChunked::from_chunks(series.name(), chunks).into_series())
+        }
+        DataType::List(_) => {
+            let chunks = series
+                .chunks()
+                .iter()
+                .map(|chunk| {
+                    let storage = storage_array(&**chunk)?;
+                    let field = match storage.data_type() {
+                        ArrowDataType::LargeList(field) => field,
+                        _ => return Err(unexpected_type(&*storage)),
+                    };
+
+                    let mut field_metadata = field.metadata.clone();
+                    field_metadata.insert(
+                        EXTENSION_NAME_KEY.to_string(),
+                        geoarrow_type.extension_name().to_string(),
+                    );
+                    if let Some(metadata) = &metadata {
+                        field_metadata.insert(EXTENSION_METADATA_KEY.to_string(), metadata.clone());
+                    }
+                    let field = ArrowField::new(&field.name, field.data_type.clone(), true)
+                        .with_metadata(field_metadata);
+
+                    with_data_type(&*storage, ArrowDataType::LargeList(Box::new(field)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(ListChunked::from_chunks(series.name(), chunks).into_series())
+        }
+        DataType::Struct(_) => {
+            let chunks = series
+                .chunks()
+                .iter()
+                .map(|chunk| with_x_extension(&**chunk, Some((geoarrow_type, metadata.clone()))))
+                .collect::<Result<Vec<_>>>()?;
+            point_series(series.name(), chunks)
+        }
+        _ => Ok(series.clone()),
+    }
+}
+
+/// Attach extension information to the `x` field of a point chunk, or with `None` remove it
+fn with_x_extension(
+    chunk: &dyn Array,
+    extension: Option<(GeoArrowType, Option<String>)>,
+) -> Result<Box<dyn Array>> {
+    let arr = chunk
+        .as_any()
+        .downcast_ref::<StructArray>()
+        .filter(|arr| !arr.values().is_empty())
+        .ok_or_else(|| unexpected_type(chunk))?;
+
+    let mut values = arr.values().to_vec();
+    let storage_type = values[0].data_type().to_logical_type().clone();
+    let data_type = match extension {
+        Some((geoarrow_type, metadata)) => ArrowDataType::Extension(
+            geoarrow_type.extension_name().to_string(),
+            Box::new(storage_type),
+            metadata,
+        ),
+        None => storage_type,
+    };
+    values[0] = with_data_type(&*values[0], data_type)?;
+    let fields = arr
+        .fields()
+        .iter()
+        .zip(values.iter())
+        .map(|(field, value)| ArrowField::new(&field.name, value.data_type().clone(), true))
+        .collect();
+    Ok(StructArray::new(
+        ArrowDataType::Struct(fields),
+        values,
+        arr.validity().cloned(),
+    )
+    .boxed())
+}
+
+/// Create a point Series from struct chunks
+///
+/// `Series::try_from` rejects chunks with extension data types, so the Series is assembled from
+/// its fields instead.
+pub(crate) fn point_series(name: &str, chunks: Vec<Box<dyn Array>>) -> Result<Series> {
+    let struct_fields = match chunks.first().map(|chunk| chunk.data_type()) {
+        Some(ArrowDataType::Struct(fields)) => fields.clone(),
+        _ => return Ok(Series::try_from((name, chunks))?),
+    };
+    let fields = struct_fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let field_chunks = chunks
+                .iter()
+                .map(|chunk| {
+                    chunk
+                        .as_any()
+                        .downcast_ref::<StructArray>()
+                        .map(|arr| arr.values()[i].clone())
+                        .ok_or_else(|| unexpected_type(&**chunk))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Float64Chunked::from_chunks(&field.name, field_chunks).into_series())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(StructChunked::new(name, &fields)?.into_series())
+}
+
+/// The chunk with any attached extension information removed, as expected by geoarrow
+pub fn storage_array(chunk: &dyn Array) -> Result<Box<dyn Array>> {
+    match chunk.data_type() {
+        ArrowDataType::Extension(_, storage, _) => with_data_type(chunk, *storage.clone()),
+        ArrowDataType::LargeList(field) if field.metadata.contains_key(EXTENSION_NAME_KEY) => {
+            let field = ArrowField::new(&field.name, field.data_type.clone(), field.is_nullable);
+            with_data_type(chunk, ArrowDataType::LargeList(Box::new(field)))
+        }
+        ArrowDataType::Struct(_) if chunk_extension(chunk).is_some() => {
+            with_x_extension(chunk, None)
+        }
+        _ => Ok(chunk.to_boxed()),
+    }
+}
+
+/// Wrap a geometry chunk in an Arrow extension data type, as used when exchanging geometries
+/// with other Arrow implementations
+pub fn extension_array(
+    chunk: &dyn Array,
+    geoarrow_type: GeoArrowType,
+    metadata: Option<String>,
+) -> Result<Box<dyn Array>> {
+    let storage = storage_array(chunk)?;
+    let data_type = ArrowDataType::Extension(
+        geoarrow_type.extension_name().to_string(),
+        Box::new(storage.data_type().clone()),
+        metadata,
+    );
+    with_data_type(&*storage, data_type)
+}
+
+/// Replace the data type of a geometry chunk without touching its buffers
+fn with_data_type(chunk: &dyn Array, data_type: ArrowDataType) -> Result<Box<dyn Array>> {
+    let any = chunk.as_any();
+    let array = if let Some(arr) = any.downcast_ref::<BinaryArray<i64>>() {
+        BinaryArray::<i64>::new(
+            data_type,
+            arr.offsets().clone(),
+            arr.values().clone(),
+            arr.validity().cloned(),
+        )
+        .boxed()
+    } else if let Some(arr) = any.downcast_ref::<BinaryArray<i32>>() {
+        BinaryArray::<i32>::new(
+            data_type,
+            arr.offsets().clone(),
+            arr.values().clone(),
+            arr.validity().cloned(),
+        )
+        .boxed()
+    } else if let Some(arr) = any.downcast_ref::<ListArray<i64>>() {
+        ListArray::<i64>::new(
+            data_type,
+            arr.offsets().clone(),
+            arr.values().clone(),
+            arr.validity().cloned(),
+        )
+        .boxed()
+    } else if let Some(arr) = any.downcast_ref::<PrimitiveArray<f64>>() {
+        PrimitiveArray::<f64>::new(data_type, arr.values().clone(), arr.validity().cloned()).boxed()
+    } else if let Some(arr) = any.downcast_ref::<StructArray>() {
+        StructArray::new(data_type, arr.values().to_vec(), arr.validity().cloned()).boxed()
+    } else {
+        return Err(unexpected_type(chunk));
+    };
+    Ok(array)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extension_metadata, geoarrow_type, with_geoarrow_extension, GeoArrowType};
+    use crate::error::GeopolarsError;
+    use crate::util::from_geom_vec;
+    use geo::{line_string, point, Geometry, MultiPoint};
+    use geoarrow::{GeometryArray, GeometryArrayTrait, MultiPointArray};
+    use polars::prelude::{NamedFrom, Series};
+
+    #[test]
+    fn infer_from_physical_type() {
+        let series = from_geom_vec(&[Geometry::LineString(line_string![
+            (x: 0., y: 0.),
+            (x: 1., y: 1.),
+        ])])
+        .unwrap();
+        assert_eq!(geoarrow_type(&series).unwrap(), GeoArrowType::WKB);
+        assert_eq!(extension_metadata(&series), None);
+    }
+
+    #[test]
+    fn multipoint_from_extension() {
+        let input_geom: MultiPoint = vec![point!(x: 0., y: 0.), point!(x: 1., y: 1.)].into();
+        let input_array: MultiPointArray = vec![input_geom].into();
+        let arrow_array = GeometryArray::MultiPoint(input_array).into_arrow();
+        let series = Series::try_from(("geometry", arrow_array)).unwrap();
+
+        // Without extension information a list of structs is indistinguishable from a LineString
+        assert_eq!(geoarrow_type(&series).unwrap(), GeoArrowType::LineString);
+
+        let metadata = r#"{"crs":"EPSG:4326"}"#.to_string();
+        let series =
+            with_geoarrow_extension(&series, GeoArrowType::MultiPoint, Some(metadata.clone()))
+                .unwrap();
+        assert_eq!(geoarrow_type(&series).unwrap(), GeoArrowType::MultiPoint);
+        assert_eq!(extension_metadata(&series), Some(metadata));
+
+        // Extension information survives slicing
+        assert_eq!(
+            geoarrow_type(&series.slice(0, 1)).unwrap(),
+            GeoArrowType::MultiPoint
+        );
+    }
+
+    #[test]
+    fn reject_non_geometry() {
+        let series = Series::new("geometry", [1_i32, 2]);
+        assert!(matches!(
+            geoarrow_type(&series),
+            Err(GeopolarsError::UnexpectedArrowType(_))
+        ));
+    }
+}
@@ -1,13 +1,16 @@
+use crate::crs::{check_crs_match, with_crs_of};
 use crate::error::Result;
 use crate::ops::affine::TransformOrigin;
 use crate::ops::length::GeodesicLengthMethod;
+use crate::ops::linear_referencing::Measure;
 #[cfg(feature = "proj")]
-use crate::ops::proj::ProjOptions;
-use crate::util::{series_from_any_chunks, struct_series_from_chunks};
+use crate::ops::proj::{ProjOptions, TransformationInfo};
+use crate::ops::simplify::SimplifyMethod;
+use crate::util::{geometry_array, geometry_series_from_arrays, struct_series_from_chunks};
 use geo::algorithm::affine_ops::AffineTransform;
-use geoarrow::{GeometryArray, GeometryArrayTrait};
-use polars::export::arrow::array::Array;
-use polars::prelude::{BooleanChunked, Float64Chunked, ListChunked, Series};
+use geo::{coord, Geometry, Rect};
+use geoarrow::GeometryArrayTrait;
+use polars::prelude::{BooleanChunked, DataFrame, Float64Chunked, Series};
 use polars::series::IntoSeries;
 use std::convert::Into;
 
@@ -19,16 +22,82 @@ pub trait GeoSeries {
     /// units of the CRS.
     fn area(&self) -> Result<Series>;
 
+    /// Returns a GeoSeries of the boundary of each geometry.
+    ///
+    /// The boundary of a polygon is a MultiLineString of its rings. The boundary of a line is a
+    /// MultiPoint of the endpoints that end an odd number of its parts, which is empty for closed
+    /// lines. Points have an empty boundary, which is returned as null.
+    fn boundary(&self) -> Result<Series>;
+
+    /// Returns a struct Series with the fields `minx`, `miny`, `maxx` and `maxy` holding the
+    /// bounds of each geometry.
+    ///
+    /// The bounds of null and empty geometries are null. For non-WKB geometries the bounds are
+    /// computed directly from the coordinate buffers.
+    fn bounds(&self) -> Result<Series>;
+
     /// Returns a GeoSeries of points representing the centroid of each geometry.
     ///
     /// Note that centroid does not have to be on or within original geometry.
     fn centroid(&self) -> Result<Series>;
 
+    /// Returns a GeoSeries of each geometry clipped to a polygonal mask, keeping the parts of the
+    /// geometry that intersect the mask.
+    ///
+    /// Lines and polygons become MultiLineStrings and MultiPolygons, as clipping may split them.
+    /// Points outside of the mask become null, and other geometries outside of it become empty.
+    /// A Rect mask takes the same fast path as `clip_by_rect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - The Polygon, MultiPolygon or Rect to clip to, in the CRS of the GeoSeries.
+    fn clip(&self, mask: &Geometry<f64>) -> Result<Series>;
+
+    /// Returns a GeoSeries of each geometry clipped to an axis-aligned rectangle.
+    ///
+    /// Lines are clipped segment by segment, and geometries entirely inside or outside of the
+    /// rectangle are not overlaid with it, which makes this faster than `clip` with a polygon.
+    /// The output types are as for `clip`.
+    ///
+    /// # Arguments
+    ///
+    /// * `minx`, `miny`, `maxx`, `maxy` - The bounds of the rectangle.
+    fn clip_by_rect(&self, minx: f64, miny: f64, maxx: f64, maxy: f64) -> Result<Series>;
+
+    /// Returns a GeoSeries of polygons representing the concave hull of each geometry.
+    ///
+    /// The hull is built by eroding the Delaunay triangulation of the vertices of the geometry
+    /// from the outside, as in GEOS and JTS. It is a single polygon without holes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ratio` - Between 0 and 1. Border edges longer than `ratio` of the way from the shortest
+    /// to the longest edge of the triangulation are eroded, so that 0 gives the tightest hull
+    /// and 1 the convex hull.
+    fn concave_hull(&self, ratio: f64) -> Result<Series>;
+
     /// Returns a GeoSeries of geometries representing the convex hull of each geometry.
     ///
     /// The convex hull of a geometry is the smallest convex Polygon containing all the points in each geometry
     fn convex_hull(&self) -> Result<Series>;
 
+    /// Returns the coordinate reference system of the GeoSeries, if one was set with `set_crs` or
+    /// read from a file.
+    fn crs(&self) -> Result<Option<String>>;
+
+    /// Returns a DataFrame of the triangles of the Delaunay triangulation of the vertices of all
+    /// geometries of the GeoSeries, typically points.
+    ///
+    /// The `vertex_0`, `vertex_1` and `vertex_2` columns hold the row of the geometry of each
+    /// counter-clockwise vertex of the triangle, and the `geometry` column the triangle as a
+    /// polygon. Vertices repeated in several rows belong to the first of those rows. Collinear
+    /// vertices have no triangles.
+    fn delaunay_triangles(&self) -> Result<DataFrame>;
+
+    /// Returns a GeoSeries of points holding the last vertex of each LineString, and null for other
+    /// geometry types.
+    fn end_point(&self) -> Result<Series>;
+
     /// Returns a GeoSeries of geometries representing the envelope of each geometry.
     ///
     /// The envelope of a geometry is the bounding rectangle. That is, the point or smallest
@@ -44,6 +113,15 @@ pub trait GeoSeries {
     /// it returns the
     fn euclidean_length(&self) -> Result<Series>;
 
+    /// Returns the UTM CRS best suited to the geometries of a WGS 84 GeoSeries, as an EPSG code
+    /// string that can be passed to `to_crs`
+    ///
+    /// The zone is chosen from the center of the total bounds of the GeoSeries, taking into
+    /// account the widened zones around Norway and Svalbard. Beyond 84°N and 80°S, where UTM is
+    /// not defined, the UPS North or South CRS is returned. Returns an error if the GeoSeries has
+    /// a CRS other than WGS 84 or contains no geometries.
+    fn estimate_utm_crs(&self) -> Result<String>;
+
     /// Returns a GeoSeries of LinearRings representing the outer boundary of each polygon in the
     /// GeoSeries.
     ///
@@ -53,6 +131,13 @@ pub trait GeoSeries {
     /// Explodes multi-part geometries into multiple single geometries.
     fn explode(&self) -> Result<Series>;
 
+    /// Returns a GeoSeries with great-circle points inserted into the lines and polygon rings of
+    /// each geometry, so that no segment is longer than `max_metres`.
+    ///
+    /// Coordinates must be longitude and latitude in degrees. This keeps long edges from being
+    /// distorted when the geometries are reprojected. Points are returned unchanged.
+    fn geodesic_densify(&self, max_metres: f64) -> Result<Series>;
+
     /// Returns a Series with the value of the geodesic length of each geometry
     ///
     /// Calculates the geodesic length of each geometry in the series and returns it as a series.
@@ -61,6 +146,36 @@ pub trait GeoSeries {
     /// it returns the
     fn geodesic_length(&self, method: GeodesicLengthMethod) -> Result<Series>;
 
+    /// Returns a DataFrame with one row per vertex of the geometries of the GeoSeries
+    ///
+    /// The `index` column holds the row of the geometry each vertex belongs to. Null and empty
+    /// geometries have no vertices. Use [`crate::util::from_coordinates`] to rebuild geometries
+    /// from the output.
+    ///
+    /// # Arguments
+    ///
+    /// * `include_z` - Add a `z` column. It is null for geometries without z coordinates, which
+    /// includes all non-WKB geometries.
+    ///
+    /// * `index_parts` - Add `part` and `ring` columns, holding the index of the part of a
+    /// multi-part geometry or GeometryCollection and of the ring of a polygon, where 0 is the
+    /// exterior ring.
+    fn get_coordinates(&self, include_z: bool, index_parts: bool) -> Result<DataFrame>;
+
+    /// Returns a GeoSeries of the `n`th part of each multi-part geometry or GeometryCollection.
+    ///
+    /// Negative values of `n` count from the last part. Single-part geometries are treated as
+    /// having one part. Null is returned where `n` is out of range. Multi-part native geometries
+    /// return their single-part type.
+    fn get_geometry(&self, n: i64) -> Result<Series>;
+
+    /// Returns a GeoSeries of points holding the `n`th vertex of each LineString, and null for
+    /// other geometry types.
+    ///
+    /// Negative values of `n` count from the last vertex. Null is returned where `n` is out of
+    /// range.
+    fn get_point(&self, n: i64) -> Result<Series>;
+
     /// Returns the type ids of each geometry
     /// This mimics the pygeos implementation
     /// <https://pygeos.readthedocs.io/en/latest/geometry.html?highlight=id#pygeos.geometry.get_type_id>
@@ -76,6 +191,23 @@ pub trait GeoSeries {
     /// GEOMETRYCOLLECTION is 7
     fn geom_type(&self) -> Result<Series>;
 
+    /// Returns a GeoSeries of MultiLineStrings holding the interior rings of each polygon, and
+    /// null for other geometry types.
+    ///
+    /// Use `explode` on the output to get one row per interior ring.
+    fn interiors(&self) -> Result<Series>;
+
+    /// Returns a boolean Series with value True for each geometry that intersects the aligned
+    /// geometry of `other`, i.e. shares at least one point with it.
+    ///
+    /// If `other` has a single row, every geometry is compared against that geometry. Returns an
+    /// error if both series have a CRS and they differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The GeoSeries (elementwise) or single geometry to test for intersection.
+    fn intersects(&self, other: &Series) -> Result<Series>;
+
     /// Returns a boolean Series with value True for empty geometries
     fn is_empty(&self) -> Result<Series>;
 
@@ -86,6 +218,167 @@ pub trait GeoSeries {
     /// implicitly closed by copying the first tuple to the last index.
     fn is_ring(&self) -> Result<Series>;
 
+    /// Returns a GeoSeries of the point at a distance along each line, and null for non-linear
+    /// geometries. The parts of a MultiLineString are measured one after another.
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - The distance along the line. Negative distances count from the end of the
+    ///   line, and distances beyond the line are clamped to its ends.
+    /// * `normalized` - Whether `distance` is a fraction of the length of the line.
+    /// * `measure` - Whether distances are euclidean in the units of the CRS or geodesic in metres.
+    fn line_interpolate_point(
+        &self,
+        distance: f64,
+        normalized: bool,
+        measure: Measure,
+    ) -> Result<Series>;
+
+    /// Returns a Series with the distance along each line to the point of the line nearest to
+    /// the aligned point of `other`, the inverse of `line_interpolate_point`.
+    ///
+    /// The nearest point is found in the plane of the CRS also for geodesic measures. If `other`
+    /// has a single row, that point is located on every line. Non-linear geometries and
+    /// non-point geometries of `other` return null.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The GeoSeries (elementwise) or single point to locate.
+    /// * `normalized` - Whether to return the distance as a fraction of the length of the line.
+    /// * `measure` - Whether distances are euclidean in the units of the CRS or geodesic in metres.
+    fn line_locate_point(
+        &self,
+        other: &Series,
+        normalized: bool,
+        measure: Measure,
+    ) -> Result<Series>;
+
+    /// Returns a single row GeoSeries with a MultiLineString of the lines of the GeoSeries merged
+    /// at the points where exactly two of them meet end to end, reversing lines as needed.
+    ///
+    /// The lines and polygon rings of all geometries are merged, so this is an aggregation; use
+    /// `explode` on the output to get one row per merged line. Lines are not split where they
+    /// cross, see `node`.
+    fn line_merge(&self) -> Result<Series>;
+
+    /// Returns a GeoSeries of the part of each line between two distances along it, and null for
+    /// non-linear geometries.
+    ///
+    /// The substring is reversed if `start` is after `end`. Lines stay lines, which are empty if
+    /// the substring is a single point, and MultiLineStrings keep their pieces of each part.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The distance along the line where the substring starts, interpreted as in
+    ///   `line_interpolate_point`.
+    /// * `end` - The distance along the line where the substring ends.
+    /// * `normalized` - Whether `start` and `end` are fractions of the length of the line.
+    /// * `measure` - Whether distances are euclidean in the units of the CRS or geodesic in metres.
+    fn line_substring(
+        &self,
+        start: f64,
+        end: f64,
+        normalized: bool,
+        measure: Measure,
+    ) -> Result<Series>;
+
+    /// Returns a GeoSeries of polygons approximating the smallest circle containing each
+    /// geometry, with 64 segments.
+    ///
+    /// Use `minimum_bounding_radius` for the exact radius of the circle.
+    fn minimum_bounding_circle(&self) -> Result<Series>;
+
+    /// Returns a Series with the radius of the smallest circle containing each geometry.
+    fn minimum_bounding_radius(&self) -> Result<Series>;
+
+    /// Returns a GeoSeries of the rectangle of smallest area containing each geometry, which
+    /// unlike the envelope may be rotated.
+    ///
+    /// Geometries without area whose points are all collinear return their convex hull, a
+    /// degenerate polygon.
+    fn minimum_rotated_rectangle(&self) -> Result<Series>;
+
+    /// Returns a single row GeoSeries with a MultiLineString of the lines of the GeoSeries split at
+    /// every point where they intersect themselves or each other.
+    ///
+    /// The lines and polygon rings of all geometries are noded together, so this is an
+    /// aggregation. Duplicate pieces, also in opposite directions, are only kept once.
+    fn node(&self) -> Result<Series>;
+
+    /// Returns a GeoSeries with each geometry in a canonical form, so that equal geometries have
+    /// equal coordinates.
+    ///
+    /// As in GEOS, exterior rings are clockwise and interior rings counter-clockwise, rings start
+    /// at their smallest coordinate, lines run in the direction with the smaller coordinates
+    /// first, and interior rings, parts of multi-part geometries and the points of MultiPoints
+    /// are sorted in descending order of their coordinates.
+    fn normalize(&self) -> Result<Series>;
+
+    /// Returns a Series with the number of parts of each multi-part geometry or
+    /// GeometryCollection. Single-part geometries have one part, or none if they are empty.
+    fn num_geometries(&self) -> Result<Series>;
+
+    /// Returns a Series with the number of interior rings of each polygon, and null for other
+    /// geometry types.
+    fn num_interior_rings(&self) -> Result<Series>;
+
+    /// Returns a Series with the number of vertices of each geometry, counting the closing vertex
+    /// of polygon rings.
+    fn num_points(&self) -> Result<Series>;
+
+    /// Returns a GeoSeries with the rings of each polygon oriented consistently, which sets the
+    /// sign of signed areas and the winding order expected by renderers.
+    ///
+    /// Interior rings are oriented opposite to the exterior ring. Other geometry types are
+    /// returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `exterior_ccw` - Whether exterior rings are counter-clockwise, as in the OGC simple
+    ///   features specification, or clockwise.
+    fn orient(&self, exterior_ccw: bool) -> Result<Series>;
+
+    /// Returns a GeoSeries of points at the pole of inaccessibility of each polygon, the interior
+    /// point farthest from its boundary, which is well suited for placing labels.
+    ///
+    /// Multi-part geometries use the pole of the part farthest from its boundary. Non-polygonal
+    /// geometries return null.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The precision of the search, in the units of the CRS. Must be positive.
+    fn pole_of_inaccessibility(&self, tolerance: f64) -> Result<Series>;
+
+    /// Returns a single row GeoSeries with a MultiPolygon of the polygons enclosed by the lines
+    /// of the GeoSeries.
+    ///
+    /// The lines and polygon rings of all geometries are used, so this is an aggregation. They
+    /// must be noded, i.e. only meet at their endpoints, which the output of `node` is. Lines
+    /// that don't enclose an area, such as dangling lines, are ignored. Use `explode` on the
+    /// output to get one row per polygon.
+    fn polygonize(&self) -> Result<Series>;
+
+    /// Returns a GeoSeries with the consecutive vertices of each geometry within `tolerance` of
+    /// each other removed.
+    ///
+    /// The first and last vertices of lines and rings are kept. Lines and rings that would be left
+    /// with too few vertices are returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The distance under which vertices are removed, in the units of the CRS.
+    ///   With 0, only exact repeats are removed.
+    fn remove_repeated_points(&self, tolerance: f64) -> Result<Series>;
+
+    /// Returns a GeoSeries of points that are guaranteed to lie within each geometry.
+    ///
+    /// Unlike the centroid, the point lies in the interior of polygons and on lines. It is cheap
+    /// to compute but not necessarily central; see `pole_of_inaccessibility` for labelling.
+    fn representative_point(&self) -> Result<Series>;
+
+    /// Returns a GeoSeries with the order of the vertices of each line and polygon ring reversed.
+    fn reverse(&self) -> Result<Series>;
+
     /// Returns a GeoSeries with each of the geometries rotated by a fixed x and y ammount around
     /// some origin.
     ///
@@ -110,6 +403,14 @@ pub trait GeoSeries {
     /// * `origin` - The origin around which to scale the geometry
     fn scale(&self, xfact: f64, yfact: f64, origin: TransformOrigin) -> Result<Series>;
 
+    /// Returns a GeoSeries with points inserted into the lines and polygon rings of each geometry,
+    /// evenly along each segment longer than `max_segment_length`, so that no segment is longer
+    /// than it.
+    ///
+    /// Lengths are planar, in the units of the CRS; see `geodesic_densify` for geographic
+    /// coordinates. Points are returned unchanged.
+    fn segmentize(&self, max_segment_length: f64) -> Result<Series>;
+
     /// Returns a GeoSeries containing a simplified representation of each geometry.
     ///
     /// The algorithm (Douglas-Peucker) recursively splits the original line into smaller parts and
@@ -119,6 +420,44 @@ pub trait GeoSeries {
     /// <https://docs.rs/geo/latest/geo/algorithm/simplify/trait.Simplify.html> for details
     fn simplify(&self, tolerance: f64) -> Result<Series>;
 
+    /// Returns a GeoSeries containing a simplified representation of each geometry, using the
+    /// given algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - A distance in the units of the CRS, or for the Visvalingam-Whyatt methods
+    /// an area in the squared units of the CRS.
+    ///
+    /// * `method` - The algorithm to use, see [`SimplifyMethod`]. Only
+    /// [`SimplifyMethod::VwPreserve`] prevents self-intersections, and only
+    /// [`SimplifyMethod::Coverage`] keeps adjacent polygons from drifting apart.
+    fn simplify_with(&self, tolerance: f64, method: SimplifyMethod) -> Result<Series>;
+
+    /// Returns a GeoSeries with its coordinate reference system set to `crs`.
+    ///
+    /// The coordinates are not modified; use `to_crs` to transform geometries to another CRS.
+    ///
+    /// # Arguments
+    ///
+    /// * `crs` - Any CRS definition accepted by PROJ, e.g. `"EPSG:4326"`, WKT or PROJJSON.
+    fn set_crs(&self, crs: &str) -> Result<Series>;
+
+    /// Returns a GeoSeries with the coordinates of each geometry rounded to a grid, removing the
+    /// repeated vertices and spikes this creates.
+    ///
+    /// Lines and polygons that collapse become empty, and collapsed parts of multi-part
+    /// geometries and holes are dropped. Polygons are kept valid: where rounding makes rings
+    /// cross or touch, e.g. turns a thin quadrilateral into a bow-tie, the rings are noded and
+    /// snap rounded so that the added vertices are on the grid too, and the polygon is rebuilt
+    /// from the area inside its exterior and outside its holes. A polygon split in several parts
+    /// becomes a MultiPolygon, and so does a Polygon GeoSeries holding one.
+    ///
+    /// # Arguments
+    ///
+    /// * `grid_size` - The size of the grid to round to, e.g. `0.001`. A size of 0 keeps the
+    ///   coordinates as they are and only removes repeated vertices and spikes.
+    fn set_precision(&self, grid_size: f64) -> Result<Series>;
+
     /// Returns a GeoSeries with each of the geometries skewed by a fixed x and y amount around a
     /// given origin
     ///
@@ -142,27 +481,80 @@ pub trait GeoSeries {
     /// ```
     fn skew(&self, xs: f64, ys: f64, origin: TransformOrigin) -> Result<Series>;
 
+    /// Returns a GeoSeries with the vertices of each geometry snapped to the vertices of the
+    /// aligned geometry of `other`.
+    ///
+    /// Vertices move to the nearest vertex of `other` within the tolerance, and vertices of
+    /// `other` within the tolerance of a segment are inserted into it. This closes sliver gaps
+    /// between neighbouring polygons. If `other` has a single row, every geometry is snapped to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The GeoSeries to snap to.
+    /// * `tolerance` - The maximum distance a vertex is moved, in the units of the CRS.
+    fn snap(&self, other: &Series, tolerance: f64) -> Result<Series>;
+
+    /// Returns a GeoSeries of points holding the first vertex of each LineString, and null for
+    /// other geometry types.
+    fn start_point(&self) -> Result<Series>;
+
     /// Returns a Series containing the distance to aligned other. Distance is cartesian distance in 2D space, and the units of the output are in terms of the CRS of the two input series. The operation works on a 1-to-1 row-wise manner.
     ///
+    /// Returns an error if both series have a CRS and they differ.
+    ///
     /// # Arguments
     ///
     /// * `other` - The Geoseries (elementwise) to find the distance to.
     fn distance(&self, other: &Series) -> Result<Series>;
 
-    // Note: Ideally we wouldn't have both `from` and `to` here, where the series would include the
-    // current CRS, but that would require polars to support extension types.
+    /// Returns a Series with the discrete Fréchet distance between each geometry and the aligned
+    /// geometry of `other`, in the units of their CRS.
+    ///
+    /// Unlike the Hausdorff distance this takes the order of the vertices into account, which
+    /// suits comparing traces that follow a path. If `other` has a single row, every geometry is
+    /// compared with it. Empty geometries give null.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The GeoSeries to compare with.
+    fn frechet_distance(&self, other: &Series) -> Result<Series>;
+
+    /// Returns a Series with the discrete Hausdorff distance between each geometry and the
+    /// aligned geometry of `other`, the largest distance from a vertex of either geometry to the
+    /// other geometry, in the units of their CRS.
+    ///
+    /// If `other` has a single row, every geometry is compared with it. Empty geometries give
+    /// null.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The GeoSeries to compare with.
+    /// * `densify` - If given, each segment is split into `1 / densify` equal parts whose
+    ///   endpoints are also measured, for a closer approximation of the exact distance. Must be
+    ///   in the range (0, 1].
+    fn hausdorff_distance(&self, other: &Series, densify: Option<f64>) -> Result<Series>;
+
+    /// Transform the geometries of the GeoSeries from its current CRS to `to`.
+    ///
+    /// The GeoSeries must have a CRS, see `set_crs`. The output has its CRS set to `to`.
+    ///
+    /// Without the `proj` feature only a built-in set of common CRSs is supported, see
+    /// [`crate::ops::builtin_crs`].
+    fn to_crs(&self, to: &str) -> Result<Series>;
+
+    /// Transform the geometries of the GeoSeries from its current CRS to `to`, with options
+    /// passed to PROJ.
     #[cfg(feature = "proj")]
-    fn to_crs(&self, from: &str, to: &str) -> Result<Series>;
+    fn to_crs_with_options(&self, to: &str, proj_options: ProjOptions) -> Result<Series>;
 
-    // Note: Ideally we wouldn't have both `from` and `to` here, where the series would include the
-    // current CRS, but that would require polars to support extension types.
+    /// Transform the geometries of the GeoSeries like `to_crs_with_options`, also returning the
+    /// transformation pipeline PROJ selected, so that reprojections can be audited.
     #[cfg(feature = "proj")]
-    fn to_crs_with_options(
+    fn to_crs_with_info(
         &self,
-        from: &str,
         to: &str,
         proj_options: ProjOptions,
-    ) -> Result<Series>;
+    ) -> Result<(Series, TransformationInfo)>;
 
     /// Returns a GeoSeries with each of the geometries translated by a fixed x and y amount
     ///
@@ -177,6 +569,26 @@ pub trait GeoSeries {
     /// * `origin` - The origin around which to scale the geometry
     fn translate(&self, x: f64, y: f64) -> Result<Series>;
 
+    /// Returns the bounds of all geometries of the GeoSeries as `[minx, miny, maxx, maxy]`, or
+    /// `None` if the GeoSeries has no non-empty geometries.
+    fn total_bounds(&self) -> Result<Option<[f64; 4]>>;
+
+    /// Returns a DataFrame of the Voronoi diagram of the vertices of all geometries of the
+    /// GeoSeries, typically points: the polygons of the area closer to each vertex than to any
+    /// other.
+    ///
+    /// The `index` column holds the row of the geometry of the vertex of each cell, and the
+    /// `geometry` column the cell. Vertices repeated in several rows belong to the first of those
+    /// rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `extent` - The `[minx, miny, maxx, maxy]` envelope to clip the cells to, such as the
+    /// output of `total_bounds`. Vertices outside of it may have no cell. If `None`, the
+    /// envelope of the vertices expanded by its width or height, whichever is larger, on every
+    /// side.
+    fn voronoi_polygons(&self, extent: Option<[f64; 4]>) -> Result<DataFrame>;
+
     /// Return the x location of point geometries in a GeoSeries
     fn x(&self) -> Result<Series>;
 
@@ -185,130 +597,223 @@ pub trait GeoSeries {
 }
 
 impl GeoSeries for Series {
-    #[allow(unused_variables)]
     fn affine_transform(&self, matrix: impl Into<AffineTransform<f64>>) -> Result<Series> {
-        unimplemented!()
-        // crate::ops::affine::affine_transform(self, matrix)
+        crate::ops::affine::affine_transform(self, matrix.into())
     }
 
     fn area(&self) -> Result<Series> {
-        let output_chunks: Vec<Box<dyn Array>> = self
+        let output_chunks = self
             .chunks()
             .iter()
             .map(|chunk| {
-                let geo_arr = GeometryArray::from_arrow(&**chunk, false);
-                let result_arr = crate::ops::area::area(geo_arr).unwrap();
-                result_arr.boxed()
+                let geo_arr = geometry_array(&**chunk)?;
+                let result_arr = crate::ops::area::area(geo_arr)?;
+                Ok(result_arr.boxed())
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(Float64Chunked::from_chunks("result", output_chunks).into_series())
     }
 
+    fn boundary(&self) -> Result<Series> {
+        crate::ops::parts::boundary(self)
+    }
+
+    fn bounds(&self) -> Result<Series> {
+        crate::ops::bounds::bounds(self)
+    }
+
     fn centroid(&self) -> Result<Series> {
-        let output_chunks: Vec<Box<dyn Array>> = self
+        let output_chunks = self
             .chunks()
             .iter()
             .map(|chunk| {
-                let geo_arr = GeometryArray::from_arrow(&**chunk, false);
-                let result_arr = crate::ops::centroid::centroid(geo_arr).unwrap();
-                result_arr.into_arrow().boxed()
+                let geo_arr = geometry_array(&**chunk)?;
+                let result_arr = crate::ops::centroid::centroid(geo_arr)?;
+                Ok(result_arr.into_arrow().boxed())
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
         // Need a workaround because StructChunked::from_chunks doesn't exist
         // Ok(StructChunked::from_chunks("result", output_chunks).into_series())
-        struct_series_from_chunks(output_chunks)
+        with_crs_of(struct_series_from_chunks(output_chunks)?, self)
+    }
+
+    fn clip(&self, mask: &Geometry<f64>) -> Result<Series> {
+        let output_arrays = self
+            .chunks()
+            .iter()
+            .map(|chunk| {
+                let geo_arr = geometry_array(&**chunk)?;
+                crate::ops::clip::clip(geo_arr, mask)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        with_crs_of(geometry_series_from_arrays(output_arrays)?, self)
+    }
+
+    fn clip_by_rect(&self, minx: f64, miny: f64, maxx: f64, maxy: f64) -> Result<Series> {
+        let rect = Rect::new(coord! { x: minx, y: miny }, coord! { x: maxx, y: maxy });
+        let output_arrays = self
+            .chunks()
+            .iter()
+            .map(|chunk| {
+                let geo_arr = geometry_array(&**chunk)?;
+                crate::ops::clip::clip_by_rect(geo_arr, rect)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        with_crs_of(geometry_series_from_arrays(output_arrays)?, self)
+    }
+
+    fn concave_hull(&self, ratio: f64) -> Result<Series> {
+        let output_arrays = self
+            .chunks()
+            .iter()
+            .map(|chunk| {
+                let geo_arr = geometry_array(&**chunk)?;
+                crate::ops::concave_hull::concave_hull(geo_arr, ratio)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        with_crs_of(geometry_series_from_arrays(output_arrays)?, self)
     }
 
     fn convex_hull(&self) -> Result<Series> {
-        let output_chunks: Vec<Box<dyn Array>> = self
+        let output_arrays = self
             .chunks()
             .iter()
             .map(|chunk| {
-                let geo_arr = GeometryArray::from_arrow(&**chunk, false);
-                let result_arr = crate::ops::convex_hull::convex_hull(geo_arr).unwrap();
-                result_arr.into_arrow()
+                let geo_arr = geometry_array(&**chunk)?;
+                crate::ops::convex_hull::convex_hull(geo_arr)
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
-        Ok(ListChunked::from_chunks("result", output_chunks).into_series())
+        with_crs_of(geometry_series_from_arrays(output_arrays)?, self)
+    }
+
+    fn crs(&self) -> Result<Option<String>> {
+        crate::crs::crs(self)
+    }
+
+    fn delaunay_triangles(&self) -> Result<DataFrame> {
+        crate::ops::voronoi::delaunay_triangles(self)
+    }
+
+    fn end_point(&self) -> Result<Series> {
+        crate::ops::parts::get_point(self, -1)
     }
 
     fn envelope(&self) -> Result<Series> {
-        let output_chunks: Vec<Box<dyn Array>> = self
+        let output_arrays = self
             .chunks()
             .iter()
             .map(|chunk| {
-                let geo_arr = GeometryArray::from_arrow(&**chunk, false);
-                let result_arr = crate::ops::envelope::envelope(geo_arr).unwrap();
-                result_arr.into_arrow()
+                let geo_arr = geometry_array(&**chunk)?;
+                crate::ops::envelope::envelope(geo_arr)
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
-        series_from_any_chunks(output_chunks)
+        with_crs_of(geometry_series_from_arrays(output_arrays)?, self)
     }
 
     fn euclidean_length(&self) -> Result<Series> {
-        let output_chunks: Vec<Box<dyn Array>> = self
+        let output_chunks = self
             .chunks()
             .iter()
             .map(|chunk| {
-                let geo_arr = GeometryArray::from_arrow(&**chunk, false);
-                let result_arr = crate::ops::length::euclidean_length(geo_arr).unwrap();
-                result_arr.boxed()
+                let geo_arr = geometry_array(&**chunk)?;
+                let result_arr = crate::ops::length::euclidean_length(geo_arr)?;
+                Ok(result_arr.boxed())
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(Float64Chunked::from_chunks("result", output_chunks).into_series())
     }
 
+    fn estimate_utm_crs(&self) -> Result<String> {
+        crate::ops::utm::estimate_utm_crs(self)
+    }
+
     fn explode(&self) -> Result<Series> {
-        let output_chunks: Vec<Box<dyn Array>> = self
+        let output_arrays = self
             .chunks()
             .iter()
             .map(|chunk| {
-                let geo_arr = GeometryArray::from_arrow(&**chunk, false);
-                let result_arr = crate::ops::explode::explode(geo_arr).unwrap();
-                result_arr.into_arrow()
+                let geo_arr = geometry_array(&**chunk)?;
+                crate::ops::explode::explode(geo_arr)
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
-        series_from_any_chunks(output_chunks)
+        with_crs_of(geometry_series_from_arrays(output_arrays)?, self)
     }
 
     fn exterior(&self) -> Result<Series> {
-        crate::ops::exterior::exterior(self)
+        with_crs_of(crate::ops::exterior::exterior(self)?, self)
+    }
+
+    fn geodesic_densify(&self, max_metres: f64) -> Result<Series> {
+        let output_arrays = self
+            .chunks()
+            .iter()
+            .map(|chunk| {
+                let geo_arr = geometry_array(&**chunk)?;
+                crate::ops::densify::geodesic_densify(geo_arr, max_metres)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        with_crs_of(geometry_series_from_arrays(output_arrays)?, self)
     }
 
     fn geodesic_length(&self, method: GeodesicLengthMethod) -> Result<Series> {
-        let output_chunks: Vec<Box<dyn Array>> = self
+        let output_chunks = self
             .chunks()
             .iter()
             .map(|chunk| {
-                let geo_arr = GeometryArray::from_arrow(&**chunk, false);
-                let result_arr = crate::ops::length::geodesic_length(geo_arr, &method).unwrap();
-                result_arr.boxed()
+                let geo_arr = geometry_array(&**chunk)?;
+                let result_arr = crate::ops::length::geodesic_length(geo_arr, &method)?;
+                Ok(result_arr.boxed())
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(Float64Chunked::from_chunks("result", output_chunks).into_series())
     }
 
+    fn get_coordinates(&self, include_z: bool, index_parts: bool) -> Result<DataFrame> {
+        crate::ops::coordinates::get_coordinates(self, include_z, index_parts)
+    }
+
+    fn get_geometry(&self, n: i64) -> Result<Series> {
+        crate::ops::parts::get_geometry(self, n)
+    }
+
+    fn get_point(&self, n: i64) -> Result<Series> {
+        crate::ops::parts::get_point(self, n)
+    }
+
     fn geom_type(&self) -> Result<Series> {
         crate::ops::geom_type::geom_type(self)
     }
 
+    fn interiors(&self) -> Result<Series> {
+        crate::ops::parts::interiors(self)
+    }
+
+    fn intersects(&self, other: &Series) -> Result<Series> {
+        check_crs_match(self, other)?;
+        crate::ops::intersects::intersects(self, other)
+    }
+
     fn is_empty(&self) -> Result<Series> {
-        let output_chunks: Vec<Box<dyn Array>> = self
+        let output_chunks = self
             .chunks()
             .iter()
             .map(|chunk| {
-                let geo_arr = GeometryArray::from_arrow(&**chunk, false);
-                let result_arr = crate::ops::is_empty::is_empty(geo_arr).unwrap();
-                result_arr.boxed()
+                let geo_arr = geometry_array(&**chunk)?;
+                let result_arr = crate::ops::is_empty::is_empty(geo_arr)?;
+                Ok(result_arr.boxed())
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(BooleanChunked::from_chunks("result", output_chunks).into_series())
     }
@@ -317,87 +822,284 @@ impl GeoSeries for Series {
         crate::ops::is_ring::is_ring(self)
     }
 
-    #[allow(unused_variables)]
+    fn line_interpolate_point(
+        &self,
+        distance: f64,
+        normalized: bool,
+        measure: Measure,
+    ) -> Result<Series> {
+        crate::ops::linear_referencing::line_interpolate_point(self, distance, normalized, measure)
+    }
+
+    fn line_locate_point(
+        &self,
+        other: &Series,
+        normalized: bool,
+        measure: Measure,
+    ) -> Result<Series> {
+        check_crs_match(self, other)?;
+        crate::ops::linear_referencing::line_locate_point(self, other, normalized, measure)
+    }
+
+    fn line_merge(&self) -> Result<Series> {
+        crate::ops::noding::line_merge(self)
+    }
+
+    fn line_substring(
+        &self,
+        start: f64,
+        end: f64,
+        normalized: bool,
+        measure: Measure,
+    ) -> Result<Series> {
+        crate::ops::linear_referencing::line_substring(self, start, end, normalized, measure)
+    }
+
+    fn minimum_bounding_circle(&self) -> Result<Series> {
+        let output_arrays = self
+            .chunks()
+            .iter()
+            .map(|chunk| {
+                let geo_arr = geometry_array(&**chunk)?;
+                crate::ops::minimum_bounding::minimum_bounding_circle(geo_arr)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        with_crs_of(geometry_series_from_arrays(output_arrays)?, self)
+    }
+
+    fn minimum_bounding_radius(&self) -> Result<Series> {
+        let output_chunks = self
+            .chunks()
+            .iter()
+            .map(|chunk| {
+                let geo_arr = geometry_array(&**chunk)?;
+                let result_arr = crate::ops::minimum_bounding::minimum_bounding_radius(geo_arr)?;
+                Ok(result_arr.boxed())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Float64Chunked::from_chunks("result", output_chunks).into_series())
+    }
+
+    fn minimum_rotated_rectangle(&self) -> Result<Series> {
+        let output_arrays = self
+            .chunks()
+            .iter()
+            .map(|chunk| {
+                let geo_arr = geometry_array(&**chunk)?;
+                crate::ops::minimum_bounding::minimum_rotated_rectangle(geo_arr)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        with_crs_of(geometry_series_from_arrays(output_arrays)?, self)
+    }
+
+    fn node(&self) -> Result<Series> {
+        crate::ops::noding::node(self)
+    }
+
+    fn normalize(&self) -> Result<Series> {
+        crate::ops::normalize::normalize(self)
+    }
+
+    fn num_geometries(&self) -> Result<Series> {
+        crate::ops::count::num_geometries(self)
+    }
+
+    fn num_interior_rings(&self) -> Result<Series> {
+        crate::ops::count::num_interior_rings(self)
+    }
+
+    fn num_points(&self) -> Result<Series> {
+        crate::ops::count::num_points(self)
+    }
+
+    fn orient(&self, exterior_ccw: bool) -> Result<Series> {
+        crate::ops::normalize::orient(self, exterior_ccw)
+    }
+
+    fn pole_of_inaccessibility(&self, tolerance: f64) -> Result<Series> {
+        let output_chunks = self
+            .chunks()
+            .iter()
+            .map(|chunk| {
+                let geo_arr = geometry_array(&**chunk)?;
+                let result_arr =
+                    crate::ops::interior_point::pole_of_inaccessibility(geo_arr, tolerance)?;
+                Ok(result_arr.into_arrow().boxed())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        with_crs_of(struct_series_from_chunks(output_chunks)?, self)
+    }
+
+    fn polygonize(&self) -> Result<Series> {
+        crate::ops::noding::polygonize(self)
+    }
+
+    fn remove_repeated_points(&self, tolerance: f64) -> Result<Series> {
+        crate::ops::normalize::remove_repeated_points(self, tolerance)
+    }
+
+    fn representative_point(&self) -> Result<Series> {
+        let output_chunks = self
+            .chunks()
+            .iter()
+            .map(|chunk| {
+                let geo_arr = geometry_array(&**chunk)?;
+                let result_arr = crate::ops::interior_point::representative_point(geo_arr)?;
+                Ok(result_arr.into_arrow().boxed())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        with_crs_of(struct_series_from_chunks(output_chunks)?, self)
+    }
+
+    fn reverse(&self) -> Result<Series> {
+        crate::ops::normalize::reverse(self)
+    }
+
     fn rotate(&self, angle: f64, origin: TransformOrigin) -> Result<Series> {
-        todo!()
-        // crate::ops::affine::rotate(self, angle, origin)
+        crate::ops::affine::rotate(self, angle, origin)
     }
 
-    #[allow(unused_variables)]
     fn scale(&self, xfact: f64, yfact: f64, origin: TransformOrigin) -> Result<Series> {
-        unimplemented!()
-        // crate::ops::affine::scale(self, xfact, yfact, origin)
+        crate::ops::affine::scale(self, xfact, yfact, origin)
+    }
+
+    fn segmentize(&self, max_segment_length: f64) -> Result<Series> {
+        let output_arrays = self
+            .chunks()
+            .iter()
+            .map(|chunk| {
+                let geo_arr = geometry_array(&**chunk)?;
+                crate::ops::densify::segmentize(geo_arr, max_segment_length)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        with_crs_of(geometry_series_from_arrays(output_arrays)?, self)
+    }
+
+    fn set_crs(&self, crs: &str) -> Result<Series> {
+        crate::crs::set_crs(self, Some(crs))
+    }
+
+    fn set_precision(&self, grid_size: f64) -> Result<Series> {
+        crate::ops::precision::set_precision(self, grid_size)
     }
 
     fn simplify(&self, tolerance: f64) -> Result<Series> {
-        let output_chunks: Vec<Box<dyn Array>> = self
+        self.simplify_with(tolerance, SimplifyMethod::Rdp)
+    }
+
+    fn simplify_with(&self, tolerance: f64, method: SimplifyMethod) -> Result<Series> {
+        if method == SimplifyMethod::Coverage {
+            return crate::ops::simplify::simplify_coverage(self, tolerance);
+        }
+
+        let output_arrays = self
             .chunks()
             .iter()
             .map(|chunk| {
-                let geo_arr = GeometryArray::from_arrow(&**chunk, false);
-                let result_arr = crate::ops::simplify::simplify(geo_arr, &tolerance).unwrap();
-                result_arr.into_arrow()
+                let geo_arr = geometry_array(&**chunk)?;
+                crate::ops::simplify::simplify(geo_arr, &tolerance, method)
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
-        series_from_any_chunks(output_chunks)
+        with_crs_of(geometry_series_from_arrays(output_arrays)?, self)
     }
 
-    #[allow(unused_variables)]
     fn skew(&self, xs: f64, ys: f64, origin: TransformOrigin) -> Result<Series> {
-        unimplemented!()
-        // crate::ops::affine::skew(self, xs, ys, origin)
+        crate::ops::affine::skew(self, xs, ys, origin)
+    }
+
+    fn snap(&self, other: &Series, tolerance: f64) -> Result<Series> {
+        check_crs_match(self, other)?;
+        crate::ops::precision::snap(self, other, tolerance)
+    }
+
+    fn start_point(&self) -> Result<Series> {
+        crate::ops::parts::get_point(self, 0)
     }
 
     fn distance(&self, other: &Series) -> Result<Series> {
+        check_crs_match(self, other)?;
         crate::ops::distance::euclidean_distance(self, other)
     }
 
+    fn frechet_distance(&self, other: &Series) -> Result<Series> {
+        check_crs_match(self, other)?;
+        crate::ops::distance::frechet_distance(self, other)
+    }
+
+    fn hausdorff_distance(&self, other: &Series, densify: Option<f64>) -> Result<Series> {
+        check_crs_match(self, other)?;
+        crate::ops::distance::hausdorff_distance(self, other, densify)
+    }
+
+    #[cfg(feature = "proj")]
+    fn to_crs(&self, to: &str) -> Result<Series> {
+        crate::ops::proj::to_crs(self, to)
+    }
+
+    #[cfg(not(feature = "proj"))]
+    fn to_crs(&self, to: &str) -> Result<Series> {
+        crate::ops::builtin_crs::to_crs(self, to)
+    }
+
     #[cfg(feature = "proj")]
-    fn to_crs(&self, from: &str, to: &str) -> Result<Series> {
-        crate::ops::proj::to_crs(self, from, to)
+    fn to_crs_with_options(&self, to: &str, proj_options: ProjOptions) -> Result<Series> {
+        crate::ops::proj::to_crs_with_options(self, to, proj_options)
     }
 
     #[cfg(feature = "proj")]
-    fn to_crs_with_options(
+    fn to_crs_with_info(
         &self,
-        from: &str,
         to: &str,
         proj_options: ProjOptions,
-    ) -> Result<Series> {
-        crate::ops::proj::to_crs_with_options(self, from, to, proj_options)
+    ) -> Result<(Series, TransformationInfo)> {
+        crate::ops::proj::to_crs_with_info(self, to, proj_options)
     }
 
-    #[allow(unused_variables)]
     fn translate(&self, x: f64, y: f64) -> Result<Series> {
-        unimplemented!()
-        // crate::ops::affine::translate(self, x, y)
+        crate::ops::affine::translate(self, x, y)
+    }
+
+    fn total_bounds(&self) -> Result<Option<[f64; 4]>> {
+        let bounds = crate::ops::bounds::total_bounds(self)?
+            .map(|rect| [rect.min().x, rect.min().y, rect.max().x, rect.max().y]);
+        Ok(bounds)
+    }
+
+    fn voronoi_polygons(&self, extent: Option<[f64; 4]>) -> Result<DataFrame> {
+        crate::ops::voronoi::voronoi_polygons(self, extent)
     }
 
     fn x(&self) -> Result<Series> {
-        let output_chunks: Vec<Box<dyn Array>> = self
+        let output_chunks = self
             .chunks()
             .iter()
             .map(|chunk| {
-                let geo_arr = GeometryArray::from_arrow(&**chunk, false);
-                let result_arr = crate::ops::point::x(geo_arr).unwrap();
-                result_arr.boxed()
+                let geo_arr = geometry_array(&**chunk)?;
+                let result_arr = crate::ops::point::x(geo_arr)?;
+                Ok(result_arr.boxed())
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(Float64Chunked::from_chunks("result", output_chunks).into_series())
     }
 
     fn y(&self) -> Result<Series> {
-        let output_chunks: Vec<Box<dyn Array>> = self
+        let output_chunks = self
             .chunks()
             .iter()
             .map(|chunk| {
-                let geo_arr = GeometryArray::from_arrow(&**chunk, false);
-                let result_arr = crate::ops::point::y(geo_arr).unwrap();
-                result_arr.boxed()
+                let geo_arr = geometry_array(&**chunk)?;
+                let result_arr = crate::ops::point::y(geo_arr)?;
+                Ok(result_arr.boxed())
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(Float64Chunked::from_chunks("result", output_chunks).into_series())
     }
@@ -1,6 +1,8 @@
 //! Pure-rust geo backend for operations in geopolars
 
+pub mod crs;
 pub mod error;
+pub mod extension;
 pub mod geoseries;
 pub mod ops;
 pub mod util;
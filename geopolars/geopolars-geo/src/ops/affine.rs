@@ -1,17 +1,17 @@
-// use crate::error::Result;
-// use crate::util::from_geom_vec;
-// use geo::algorithm::affine_ops::AffineTransform;
-// use geo::algorithm::bounding_rect::BoundingRect;
-// use geo::algorithm::centroid::Centroid;
-// use geo::map_coords::MapCoords;
-use geo::Point;
-// use geo::{Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
-// use geoarrow::GeometryArray;
-// use polars::prelude::Series;
-
-// use crate::util::iter_geom;
-
-// use super::centroid::centroid;
+use crate::error::Result;
+use crate::extension::{chunk_extension, chunk_geoarrow_type, extension_array, GeoArrowType};
+use crate::ops::centroid::centroid;
+use crate::util::{
+    geometries_of_array, geometry_array, geometry_array_from_geometries, map_coords,
+    row_coord_offsets, series_like,
+};
+use geo::algorithm::affine_ops::AffineTransform;
+use geo::algorithm::bounding_rect::BoundingRect;
+use geo::algorithm::centroid::Centroid;
+use geo::{Coord, Geometry, MapCoordsInPlace, Point};
+use geoarrow::GeometryArrayTrait;
+use polars::export::arrow::array::Array;
+use polars::prelude::Series;
 
 /// Used to express the origin for a given transform. Can be specified either be with reference to
 /// the geometry being transformed (Centroid, Center) or some arbitrary point.
@@ -26,335 +26,360 @@ pub enum TransformOrigin {
     Point(Point),
 }
 
-// pub(crate) fn affine_transform(
-//     array: GeometryArray,
-//     matrix: impl Into<AffineTransform<f64>>,
-// ) -> Result<GeometryArray> {
-//     let transform: AffineTransform<f64> = matrix.into();
-
-//     match array {
-//         GeometryArray::WKB(arr) => {
-//             let output_geoms: Vec<Option<Geometry>> = arr
-//                 .iter_geo()
-//                 .map(|maybe_g| maybe_g.map(|geom| geom.map_coords(|coord| transform.apply(coord))))
-//                 .collect();
-
-//             Ok(GeometryArray::WKB(output_geoms.into()))
-//         }
-//         GeometryArray::Point(arr) => {
-//             let output_geoms: Vec<Option<Point>> = arr
-//                 .iter_geo()
-//                 .map(|maybe_g| maybe_g.map(|geom| geom.map_coords(|coord| transform.apply(coord))))
-//                 .collect();
-
-//             Ok(GeometryArray::Point(output_geoms.into()))
-//         }
-
-//         GeometryArray::MultiPoint(arr) => {
-//             let output_geoms: Vec<Option<MultiPoint>> = arr
-//                 .iter_geo()
-//                 .map(|maybe_g| maybe_g.map(|geom| geom.map_coords(|coord| transform.apply(coord))))
-//                 .collect();
-
-//             Ok(GeometryArray::MultiPoint(output_geoms.into()))
-//         }
-//         GeometryArray::LineString(arr) => {
-//             let output_geoms: Vec<Option<LineString>> = arr
-//                 .iter_geo()
-//                 .map(|maybe_g| maybe_g.map(|geom| geom.map_coords(|coord| transform.apply(coord))))
-//                 .collect();
-
-//             Ok(GeometryArray::LineString(output_geoms.into()))
-//         }
-//         GeometryArray::MultiLineString(arr) => {
-//             let output_geoms: Vec<Option<MultiLineString>> = arr
-//                 .iter_geo()
-//                 .map(|maybe_g| maybe_g.map(|geom| geom.map_coords(|coord| transform.apply(coord))))
-//                 .collect();
-
-//             Ok(GeometryArray::MultiLineString(output_geoms.into()))
-//         }
-//         GeometryArray::Polygon(arr) => {
-//             let output_geoms: Vec<Option<Polygon>> = arr
-//                 .iter_geo()
-//                 .map(|maybe_g| maybe_g.map(|geom| geom.map_coords(|coord| transform.apply(coord))))
-//                 .collect();
-
-//             Ok(GeometryArray::Polygon(output_geoms.into()))
-//         }
-//         GeometryArray::MultiPolygon(arr) => {
-//             let output_geoms: Vec<Option<MultiPolygon>> = arr
-//                 .iter_geo()
-//                 .map(|maybe_g| maybe_g.map(|geom| geom.map_coords(|coord| transform.apply(coord))))
-//                 .collect();
-
-//             Ok(GeometryArray::MultiPolygon(output_geoms.into()))
-//         }
-//     }
-// }
-
-// // pub(crate) fn rotate(array: GeometryArray, angle: f64, origin: TransformOrigin) -> Result<GeometryArray> {
-// //     match origin {
-// //         TransformOrigin::Centroid => {
-// //             let centroid_arr = centroid(array)?;
-// //             centroid_arr.iter_geo().map(|maybe_g| maybe_g.map( ))
-// //         },
-// //         _ => todo!()
-// //     }
-
-// //     rotate_wkb(series, angle, origin)
-// // }
-
-// pub(crate) fn scale(
-//     series: &Series,
-//     xfact: f64,
-//     yfact: f64,
-//     origin: TransformOrigin,
-// ) -> Result<Series> {
-//     scale_wkb(series, xfact, yfact, origin)
-// }
-
-// pub(crate) fn skew(series: &Series, xs: f64, ys: f64, origin: TransformOrigin) -> Result<Series> {
-//     skew_wkb(series, xs, ys, origin)
-// }
-
-// pub(crate) fn translate(series: &Series, x: f64, y: f64) -> Result<Series> {
-//     let transform = AffineTransform::translate(x, y);
-//     affine_transform(series, transform)
-// }
-
-// fn rotate_wkb(series: &Series, angle: f64, origin: TransformOrigin) -> Result<Series> {
-//     match origin {
-//         TransformOrigin::Centroid => {
-//             let rotated_geoms: Vec<Geometry<f64>> = iter_geom(series)
-//                 .map(|geom| {
-//                     let centroid = geom.centroid().unwrap();
-//                     let transform = AffineTransform::rotate(angle, centroid);
-//                     geom.map_coords(|c| transform.apply(c))
-//                 })
-//                 .collect();
-//             from_geom_vec(&rotated_geoms)
-//         }
-//         TransformOrigin::Center => {
-//             let rotated_geoms: Vec<Geometry<f64>> = iter_geom(series)
-//                 .map(|geom| {
-//                     let center = geom.bounding_rect().unwrap().center();
-//                     let transform = AffineTransform::rotate(angle, center);
-//                     geom.map_coords(|c| transform.apply(c))
-//                 })
-//                 .collect();
-//             from_geom_vec(&rotated_geoms)
-//         }
-//         TransformOrigin::Point(point) => {
-//             let transform = AffineTransform::rotate(angle, point);
-//             affine_transform_wkb(series, transform)
-//         }
-//     }
-// }
-
-// fn scale_wkb(series: &Series, xfact: f64, yfact: f64, origin: TransformOrigin) -> Result<Series> {
-//     match origin {
-//         TransformOrigin::Centroid => {
-//             let rotated_geoms: Vec<Geometry<f64>> = iter_geom(series)
-//                 .map(|geom| {
-//                     let centroid = geom.centroid().unwrap();
-//                     let transform = AffineTransform::scale(xfact, yfact, centroid);
-//                     geom.map_coords(|c| transform.apply(c))
-//                 })
-//                 .collect();
-//             from_geom_vec(&rotated_geoms)
-//         }
-//         TransformOrigin::Center => {
-//             let rotated_geoms: Vec<Geometry<f64>> = iter_geom(series)
-//                 .map(|geom| {
-//                     let center = geom.bounding_rect().unwrap().center();
-//                     let transform = AffineTransform::scale(xfact, yfact, center);
-//                     geom.map_coords(|c| transform.apply(c))
-//                 })
-//                 .collect();
-//             from_geom_vec(&rotated_geoms)
-//         }
-//         TransformOrigin::Point(point) => {
-//             let transform = AffineTransform::scale(xfact, yfact, point);
-//             affine_transform_wkb(series, transform)
-//         }
-//     }
-// }
-
-// fn skew_wkb(series: &Series, xs: f64, ys: f64, origin: TransformOrigin) -> Result<Series> {
-//     match origin {
-//         TransformOrigin::Centroid => {
-//             let rotated_geoms: Vec<Geometry<f64>> = iter_geom(series)
-//                 .map(|geom| {
-//                     let centroid = geom.centroid().unwrap();
-//                     let transform = AffineTransform::skew(xs, ys, centroid);
-//                     geom.map_coords(|c| transform.apply(c))
-//                 })
-//                 .collect();
-//             from_geom_vec(&rotated_geoms)
-//         }
-//         TransformOrigin::Center => {
-//             let rotated_geoms: Vec<Geometry<f64>> = iter_geom(series)
-//                 .map(|geom| {
-//                     let center = geom.bounding_rect().unwrap().center();
-//                     let transform = AffineTransform::skew(xs, ys, center);
-//                     geom.map_coords(|c| transform.apply(c))
-//                 })
-//                 .collect();
-//             from_geom_vec(&rotated_geoms)
-//         }
-//         TransformOrigin::Point(point) => {
-//             let transform = AffineTransform::skew(xs, ys, point);
-//             affine_transform_wkb(series, transform)
-//         }
-//     }
-// }
-
-// #[cfg(test)]
-// mod tests {
-//     use super::TransformOrigin;
-//     use crate::geoseries::GeoSeries;
-//     use crate::util::from_geom_vec;
-//     use crate::util::iter_geom;
-//     use geo::{polygon, CoordsIter, Geometry, Point};
-
-//     #[test]
-//     fn rotate() {
-//         let geo_series = from_geom_vec(&[Geometry::Polygon(polygon!(
-//         (x: 0.0,y:0.0),
-//         (x: 0.0,y:1.0),
-//         (x: 1.0,y: 1.0),
-//         (x: 1.0,y: 0.0)
-//         ))])
-//         .unwrap();
-
-//         let result: Geometry<f64> = polygon!(
-//         (x:0.0,y:0.0),
-//         (x:-1.0,y:0.0),
-//         (x:-1.0, y:1.0),
-//         (x:0.0, y:1.0)
-//         )
-//         .into();
-
-//         let rotated_series = geo_series.rotate(90.0, TransformOrigin::Point(Point::new(0.0, 0.0)));
-//         assert!(rotated_series.is_ok(), "To get a series back");
-
-//         let geom = iter_geom(&rotated_series.unwrap()).next().unwrap();
-//         for (p1, p2) in geom.coords_iter().zip(result.coords_iter()) {
-//             assert!(
-//                 (p1.x - p2.x).abs() < 0.00000001,
-//                 "The geometries x coords to be correct to within some tollerenace"
-//             );
-//             assert!(
-//                 (p1.y - p2.y).abs() < 0.00000001,
-//                 "The geometries y coords to be correct to within some tollerenace"
-//             );
-//         }
-//     }
-
-//     #[test]
-//     fn scale() {
-//         let geo_series = from_geom_vec(&[Geometry::Polygon(polygon!(
-//         (x: 0.0,y:0.0),
-//         (x: 0.0,y:1.0),
-//         (x: 1.0,y: 1.0),
-//         (x: 1.0,y: 0.0)
-//         ))])
-//         .unwrap();
-
-//         let result_center: Geometry<f64> = polygon!(
-//         (x:-0.5,y:-0.5),
-//         (x:-0.5,y:1.5),
-//         (x:1.5, y:1.5),
-//         (x:1.5, y:-0.5)
-//         )
-//         .into();
-
-//         let result_point: Geometry<f64> = polygon!(
-//         (x:0.0,y:0.0),
-//         (x:0.0,y:2.0),
-//         (x:2.0, y:2.0),
-//         (x:2.0, y:0.0)
-//         )
-//         .into();
-
-//         let scaled_series = geo_series.scale(2.0, 2.0, TransformOrigin::Center);
-//         assert!(scaled_series.is_ok(), "To get a series back");
-
-//         let geom = iter_geom(&scaled_series.unwrap()).next().unwrap();
-//         assert_eq!(
-//             geom, result_center,
-//             "The geom to be approprietly scaled about it's center"
-//         );
-
-//         let scaled_series =
-//             geo_series.scale(2.0, 2.0, TransformOrigin::Point(Point::new(0.0, 0.0)));
-//         let geom = iter_geom(&scaled_series.unwrap()).next().unwrap();
-//         assert_eq!(
-//             geom, result_point,
-//             "The geom to be approprietly scaled about the point 0,0"
-//         );
-//     }
-
-//     #[test]
-//     fn skew() {
-//         let geo_series = from_geom_vec(&[Geometry::Polygon(polygon!(
-//         (x: 0.0,y:0.0),
-//         (x: 0.0,y:1.0),
-//         (x: 1.0,y: 1.0),
-//         (x: 1.0,y: 0.0)
-//         ))])
-//         .unwrap();
-
-//         let result: Geometry<f64> = polygon!(
-//             (x:-0.008727532464108793,y:-0.017460384745873865),
-//             (x:0.008727532464108793,y:0.9825396152541261),
-//             (x:1.008727532464109, y:1.0174603847458739),
-//             (x:0.9912724675358912, y:0.017460384745873865)
-//         )
-//         .into();
-
-//         let skewed_series = geo_series.skew(1.0, 2.0, TransformOrigin::Center);
-//         assert!(skewed_series.is_ok(), "To get a series back");
-
-//         let geom = iter_geom(&skewed_series.unwrap()).next().unwrap();
-
-//         assert_eq!(geom, result, "the polygon should be transformed correctly");
-
-//         for (p1, p2) in geom.coords_iter().zip(result.coords_iter()) {
-//             assert!(
-//                 (p1.x - p2.x).abs() < 0.00000001,
-//                 "The geometries x coords to be correct to within some tollerenace"
-//             );
-//             assert!(
-//                 (p1.y - p2.y).abs() < 0.00000001,
-//                 "The geometries y coords to be correct to within some tollerenace"
-//             );
-//         }
-//     }
-
-//     #[test]
-//     fn translate() {
-//         let geo_series = from_geom_vec(&[Geometry::Polygon(polygon!(
-//         (x: 0.0,y:0.0),
-//         (x: 0.0,y:1.0),
-//         (x: 1.0,y: 1.0),
-//         (x: 1.0,y: 0.0)
-//         ))])
-//         .unwrap();
-
-//         let result: Geometry<f64> = polygon!(
-//         (x:1.0,y:1.0),
-//         (x:1.0,y:2.0),
-//         (x:2.0, y:2.0),
-//         (x:2.0, y:1.0)
-//         )
-//         .into();
-
-//         let translated_series = geo_series.translate(1.0, 1.0);
-//         assert!(translated_series.is_ok(), "To get a series back");
-
-//         let geom = iter_geom(&translated_series.unwrap()).next().unwrap();
-//         assert_eq!(geom, result, "The geom to be approprietly translated");
-//     }
-// }
+pub(crate) fn affine_transform(series: &Series, transform: AffineTransform<f64>) -> Result<Series> {
+    let output_chunks = series
+        .chunks()
+        .iter()
+        .map(|chunk| match chunk_geoarrow_type(&**chunk)? {
+            GeoArrowType::WKB => transform_wkb(&**chunk, |_| Some(transform)),
+            _ => map_coords(&**chunk, &mut |coords: &mut [Coord<f64>]| {
+                coords
+                    .iter_mut()
+                    .for_each(|coord| *coord = transform.apply(*coord));
+                Ok(())
+            }),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    series_like(series, output_chunks)
+}
+
+pub(crate) fn rotate(series: &Series, angle: f64, origin: TransformOrigin) -> Result<Series> {
+    transform_about(series, origin, |origin| {
+        AffineTransform::rotate(angle, origin)
+    })
+}
+
+pub(crate) fn scale(
+    series: &Series,
+    xfact: f64,
+    yfact: f64,
+    origin: TransformOrigin,
+) -> Result<Series> {
+    transform_about(series, origin, |origin| {
+        AffineTransform::scale(xfact, yfact, origin)
+    })
+}
+
+pub(crate) fn skew(series: &Series, xs: f64, ys: f64, origin: TransformOrigin) -> Result<Series> {
+    transform_about(series, origin, |origin| {
+        AffineTransform::skew(xs, ys, origin)
+    })
+}
+
+pub(crate) fn translate(series: &Series, x: f64, y: f64) -> Result<Series> {
+    affine_transform(series, AffineTransform::translate(x, y))
+}
+
+/// Apply to each geometry the transform built by `transform` around its origin
+///
+/// A fixed origin gives the same transform for every geometry. Otherwise the origin of each row
+/// of a native chunk is found from the coordinates of that row, and its transform applied to
+/// them in place. Empty geometries have no origin and are left as they are.
+fn transform_about(
+    series: &Series,
+    origin: TransformOrigin,
+    transform: impl Fn(Coord<f64>) -> AffineTransform<f64>,
+) -> Result<Series> {
+    if let TransformOrigin::Point(point) = origin {
+        return affine_transform(series, transform(point.0));
+    }
+
+    let output_chunks = series
+        .chunks()
+        .iter()
+        .map(|chunk| match chunk_geoarrow_type(&**chunk)? {
+            GeoArrowType::WKB => transform_wkb(&**chunk, |geom| {
+                let origin = match origin {
+                    TransformOrigin::Centroid => geom.centroid().map(|point| point.0),
+                    _ => geom.bounding_rect().map(|rect| rect.center()),
+                };
+                origin.map(&transform)
+            }),
+            _ => {
+                let centroids: Option<Vec<Option<Coord<f64>>>> = match origin {
+                    TransformOrigin::Centroid => Some(
+                        centroid(geometry_array(&**chunk)?)?
+                            .iter_geo()
+                            .map(|maybe_p| maybe_p.map(|point| point.0))
+                            .collect(),
+                    ),
+                    _ => None,
+                };
+                let offsets = row_coord_offsets(&**chunk);
+                map_coords(&**chunk, &mut |coords: &mut [Coord<f64>]| {
+                    for (row, window) in offsets.windows(2).enumerate() {
+                        let row_coords = &mut coords[window[0]..window[1]];
+                        let origin = match &centroids {
+                            Some(centroids) => centroids[row],
+                            None => bounding_center(row_coords),
+                        };
+                        if let Some(origin) = origin {
+                            let row_transform = transform(origin);
+                            row_coords
+                                .iter_mut()
+                                .for_each(|coord| *coord = row_transform.apply(*coord));
+                        }
+                    }
+                    Ok(())
+                })
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    series_like(series, output_chunks)
+}
+
+/// The center of the bounding box of coordinates, or `None` if there are none
+fn bounding_center(coords: &[Coord<f64>]) -> Option<Coord<f64>> {
+    let first = coords.first()?;
+    let (mut min, mut max) = (*first, *first);
+    for coord in coords {
+        min.x = min.x.min(coord.x);
+        min.y = min.y.min(coord.y);
+        max.x = max.x.max(coord.x);
+        max.y = max.y.max(coord.y);
+    }
+    Some((min + max) / 2.)
+}
+
+/// Apply to each geometry of a WKB chunk the transform returned for it, if any, keeping the
+/// extension information of the chunk
+fn transform_wkb(
+    chunk: &dyn Array,
+    transform_of: impl Fn(&Geometry) -> Option<AffineTransform<f64>>,
+) -> Result<Box<dyn Array>> {
+    let extension =
+        chunk_extension(chunk).map(|(_, metadata)| metadata.map(|metadata| metadata.to_string()));
+    let output_geoms = geometries_of_array(geometry_array(chunk)?)
+        .into_iter()
+        .map(|maybe_g| {
+            maybe_g.map(|mut geom| {
+                if let Some(transform) = transform_of(&geom) {
+                    geom.map_coords_in_place(|coord| transform.apply(coord));
+                }
+                geom
+            })
+        })
+        .collect();
+    let output = geometry_array_from_geometries(output_geoms, GeoArrowType::WKB)?.into_arrow();
+    Ok(match extension {
+        Some(metadata) => extension_array(&*output, GeoArrowType::WKB, metadata)?,
+        None => output,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TransformOrigin;
+    use crate::extension::GeoArrowType;
+    use crate::geoseries::GeoSeries;
+    use crate::util::from_geom_vec;
+    use crate::util::iter_geom;
+    use crate::util::{geometries, geometry_array_from_geometries, geometry_series_from_arrays};
+    use geo::{line_string, point, polygon, CoordsIter, Geometry, MultiPoint, Point};
+
+    #[test]
+    fn rotate() {
+        let geo_series = from_geom_vec(&[Geometry::Polygon(polygon!(
+        (x: 0.0,y:0.0),
+        (x: 0.0,y:1.0),
+        (x: 1.0,y: 1.0),
+        (x: 1.0,y: 0.0)
+        ))])
+        .unwrap();
+
+        let result: Geometry<f64> = polygon!(
+        (x:0.0,y:0.0),
+        (x:-1.0,y:0.0),
+        (x:-1.0, y:1.0),
+        (x:0.0, y:1.0)
+        )
+        .into();
+
+        let rotated_series = geo_series.rotate(90.0, TransformOrigin::Point(Point::new(0.0, 0.0)));
+        assert!(rotated_series.is_ok(), "To get a series back");
+
+        let geom = iter_geom(&rotated_series.unwrap()).next().unwrap();
+        for (p1, p2) in geom.coords_iter().zip(result.coords_iter()) {
+            assert!(
+                (p1.x - p2.x).abs() < 0.00000001,
+                "The geometries x coords to be correct to within some tollerenace"
+            );
+            assert!(
+                (p1.y - p2.y).abs() < 0.00000001,
+                "The geometries y coords to be correct to within some tollerenace"
+            );
+        }
+    }
+
+    #[test]
+    fn scale() {
+        let geo_series = from_geom_vec(&[Geometry::Polygon(polygon!(
+        (x: 0.0,y:0.0),
+        (x: 0.0,y:1.0),
+        (x: 1.0,y: 1.0),
+        (x: 1.0,y: 0.0)
+        ))])
+        .unwrap();
+
+        let result_center: Geometry<f64> = polygon!(
+        (x:-0.5,y:-0.5),
+        (x:-0.5,y:1.5),
+        (x:1.5, y:1.5),
+        (x:1.5, y:-0.5)
+        )
+        .into();
+
+        let result_point: Geometry<f64> = polygon!(
+        (x:0.0,y:0.0),
+        (x:0.0,y:2.0),
+        (x:2.0, y:2.0),
+        (x:2.0, y:0.0)
+        )
+        .into();
+
+        let scaled_series = geo_series.scale(2.0, 2.0, TransformOrigin::Center);
+        assert!(scaled_series.is_ok(), "To get a series back");
+
+        let geom = iter_geom(&scaled_series.unwrap()).next().unwrap();
+        assert_eq!(
+            geom, result_center,
+            "The geom to be approprietly scaled about it's center"
+        );
+
+        let scaled_series =
+            geo_series.scale(2.0, 2.0, TransformOrigin::Point(Point::new(0.0, 0.0)));
+        let geom = iter_geom(&scaled_series.unwrap()).next().unwrap();
+        assert_eq!(
+            geom, result_point,
+            "The geom to be approprietly scaled about the point 0,0"
+        );
+    }
+
+    #[test]
+    fn skew() {
+        let geo_series = from_geom_vec(&[Geometry::Polygon(polygon!(
+        (x: 0.0,y:0.0),
+        (x: 0.0,y:1.0),
+        (x: 1.0,y: 1.0),
+        (x: 1.0,y: 0.0)
+        ))])
+        .unwrap();
+
+        let result: Geometry<f64> = polygon!(
+            (x:-0.008727532464108793,y:-0.017460384745873865),
+            (x:0.008727532464108793,y:0.9825396152541261),
+            (x:1.008727532464109, y:1.0174603847458739),
+            (x:0.9912724675358912, y:0.017460384745873865)
+        )
+        .into();
+
+        let skewed_series = geo_series.skew(1.0, 2.0, TransformOrigin::Center);
+        assert!(skewed_series.is_ok(), "To get a series back");
+
+        let geom = iter_geom(&skewed_series.unwrap()).next().unwrap();
+
+        assert_eq!(geom, result, "the polygon should be transformed correctly");
+
+        for (p1, p2) in geom.coords_iter().zip(result.coords_iter()) {
+            assert!(
+                (p1.x - p2.x).abs() < 0.00000001,
+                "The geometries x coords to be correct to within some tollerenace"
+            );
+            assert!(
+                (p1.y - p2.y).abs() < 0.00000001,
+                "The geometries y coords to be correct to within some tollerenace"
+            );
+        }
+    }
+
+    #[test]
+    fn translate() {
+        let geo_series = from_geom_vec(&[Geometry::Polygon(polygon!(
+        (x: 0.0,y:0.0),
+        (x: 0.0,y:1.0),
+        (x: 1.0,y: 1.0),
+        (x: 1.0,y: 0.0)
+        ))])
+        .unwrap();
+
+        let result: Geometry<f64> = polygon!(
+        (x:1.0,y:1.0),
+        (x:1.0,y:2.0),
+        (x:2.0, y:2.0),
+        (x:2.0, y:1.0)
+        )
+        .into();
+
+        let translated_series = geo_series.translate(1.0, 1.0);
+        assert!(translated_series.is_ok(), "To get a series back");
+
+        let geom = iter_geom(&translated_series.unwrap()).next().unwrap();
+        assert_eq!(geom, result, "The geom to be approprietly translated");
+    }
+
+    #[test]
+    fn native_arrays() {
+        let native = |geoms: Vec<Geometry>, geometry_type: GeoArrowType| {
+            let geoms = geoms.into_iter().map(Some).collect();
+            let array = geometry_array_from_geometries(geoms, geometry_type).unwrap();
+            geometry_series_from_arrays(vec![array]).unwrap()
+        };
+
+        let points = native(
+            vec![
+                Geometry::Point(point!(x: 1., y: 2.)),
+                Geometry::Point(point!(x: 3., y: 4.)),
+            ],
+            GeoArrowType::Point,
+        );
+        assert_eq!(
+            geometries(&points.translate(1., -1.).unwrap()).unwrap(),
+            vec![
+                Some(Geometry::Point(point!(x: 2., y: 1.))),
+                Some(Geometry::Point(point!(x: 4., y: 3.))),
+            ]
+        );
+
+        // Each row is scaled around the center of its own bounding box
+        let lines = native(
+            vec![
+                Geometry::LineString(line_string![(x: 0., y: 0.), (x: 2., y: 0.)]),
+                Geometry::LineString(line_string![(x: 10., y: 10.), (x: 10., y: 14.)]),
+            ],
+            GeoArrowType::LineString,
+        );
+        assert_eq!(
+            geometries(&lines.scale(2., 2., TransformOrigin::Center).unwrap()).unwrap(),
+            vec![
+                Some(Geometry::LineString(
+                    line_string![(x: -1., y: 0.), (x: 3., y: 0.)]
+                )),
+                Some(Geometry::LineString(
+                    line_string![(x: 10., y: 8.), (x: 10., y: 16.)]
+                )),
+            ]
+        );
+
+        let multi_points = native(
+            vec![Geometry::MultiPoint(MultiPoint::from(vec![
+                (0., 0.),
+                (2., 2.),
+            ]))],
+            GeoArrowType::MultiPoint,
+        );
+        assert_eq!(
+            geometries(
+                &multi_points
+                    .scale(0.5, 0.5, TransformOrigin::Centroid)
+                    .unwrap()
+            )
+            .unwrap(),
+            vec![Some(Geometry::MultiPoint(MultiPoint::from(vec![
+                (0.5, 0.5),
+                (1.5, 1.5)
+            ])))]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use crate::error::Result;
+use crate::extension::{chunk_geoarrow_type, storage_array, GeoArrowType};
+use crate::util::{coord_buffers, geometry_array, unexpected_coord_type};
+use geo::algorithm::bounding_rect::BoundingRect;
+use geo::{coord, Rect};
+use geoarrow::{GeometryArray, GeometryArrayTrait};
+use polars::export::arrow::array::{Array, ListArray, StructArray};
+use polars::prelude::{Float64Chunked, NewChunkedArray, Series, StructChunked};
+use polars::series::IntoSeries;
+use std::ops::Range;
+
+pub(crate) fn bounds(series: &Series) -> Result<Series> {
+    let mut rects: Vec<Option<Rect>> = Vec::with_capacity(series.len());
+    for chunk in series.chunks() {
+        rects.extend(chunk_bounds(&**chunk)?);
+    }
+
+    let field = |name: &str, f: fn(&Rect) -> f64| {
+        let values: Vec<Option<f64>> = rects.iter().map(|rect| rect.as_ref().map(f)).collect();
+        Float64Chunked::from_slice_options(name, &values).into_series()
+    };
+    let fields = [
+        field("minx", |rect| rect.min().x),
+        field("miny", |rect| rect.min().y),
+        field("maxx", |rect| rect.max().x),
+        field("maxy", |rect| rect.max().y),
+    ];
+    Ok(StructChunked::new("bounds", &fields)?.into_series())
+}
+
+/// The bounding rectangle of all geometries of a geometry Series, or `None` if it has no
+/// non-empty geometries
+pub(crate) fn total_bounds(series: &Series) -> Result<Option<Rect>> {
+    let mut bounds = None;
+    for chunk in series.chunks() {
+        bounds = chunk_bounds(&**chunk)?.into_iter().flatten().fold(
+            bounds,
+            |acc: Option<Rect>, rect| match acc {
+                Some(acc) => Some(merge_rects(acc, rect)),
+                None => Some(rect),
+            },
+        );
+    }
+    Ok(bounds)
+}
+
+pub(crate) fn merge_rects(a: Rect, b: Rect) -> Rect {
+    Rect::new(
+        coord! { x: a.min().x.min(b.min().x), y: a.min().y.min(b.min().y) },
+        coord! { x: a.max().x.max(b.max().x), y: a.max().y.max(b.max().y) },
+    )
+}
+
+/// The bounding rectangle of each geometry of a chunk, `None` for null and empty geometries
+pub(crate) fn chunk_bounds(chunk: &dyn Array) -> Result<Vec<Option<Rect>>> {
+    if chunk_geoarrow_type(chunk)? == GeoArrowType::WKB {
+        return match geometry_array(chunk)? {
+            GeometryArray::WKB(arr) => Ok(arr
+                .iter_geo()
+                .map(|maybe_g| maybe_g.and_then(|g| g.bounding_rect()))
+                .collect()),
+            _ => unreachable!(),
+        };
+    }
+
+    // Native geometries store their coordinates contiguously, so the bounds of a row are those of
+    // a range of the coordinate buffers and no geometries need to be built
+    let storage = storage_array(chunk)?;
+    (0..storage.len())
+        .map(|i| -> Result<Option<Rect>> {
+            if storage.is_null(i) {
+                return Ok(None);
+            }
+            let (range, x, y) = coord_range(&*storage, i..i + 1)?;
+            Ok(coords_bounds(&x[range.clone()], &y[range]))
+        })
+        .collect()
+}
+
+/// The range of coordinates of the geometries in `rows` of a native geometry array, and the x and
+/// y coordinate buffers it indexes into
+fn coord_range(array: &dyn Array, rows: Range<usize>) -> Result<(Range<usize>, &[f64], &[f64])> {
+    let any = array.as_any();
+    if let Some(arr) = any.downcast_ref::<ListArray<i64>>() {
+        let offsets = arr.offsets().buffer();
+        let rows = offsets[rows.start] as usize..offsets[rows.end] as usize;
+        coord_range(arr.values().as_ref(), rows)
+    } else if let Some(arr) = any.downcast_ref::<ListArray<i32>>() {
+        let offsets = arr.offsets().buffer();
+        let rows = offsets[rows.start] as usize..offsets[rows.end] as usize;
+        coord_range(arr.values().as_ref(), rows)
+    } else if let Some(arr) = any.downcast_ref::<StructArray>() {
+        let (x, y) = coord_buffers(arr)?;
+        Ok((rows, x, y))
+    } else {
+        Err(unexpected_coord_type(array))
+    }
+}
+
+fn coords_bounds(x: &[f64], y: &[f64]) -> Option<Rect> {
+    if x.is_empty() {
+        return None;
+    }
+    let (mut minx, mut miny) = (f64::INFINITY, f64::INFINITY);
+    let (mut maxx, mut maxy) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for (x, y) in x.iter().zip(y.iter()) {
+        minx = minx.min(*x);
+        miny = miny.min(*y);
+        maxx = maxx.max(*x);
+        maxy = maxy.max(*y);
+    }
+    Some(Rect::new(
+        coord! { x: minx, y: miny },
+        coord! { x: maxx, y: maxy },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoseries::GeoSeries;
+    use crate::util::from_geom_vec;
+    use geo::{point, polygon, Geometry, LineString, MultiPoint};
+    use geoarrow::{GeometryArray, GeometryArrayTrait, MultiPointArray};
+    use polars::prelude::Series;
+
+    #[test]
+    fn bounds_wkb() {
+        let series = from_geom_vec(&[
+            Geometry::Point(point!(x: 1., y: 2.)),
+            Geometry::LineString(LineString::<f64>::new(vec![])),
+            Geometry::Polygon(polygon![
+                (x: 0., y: 0.),
+                (x: 4., y: 0.),
+                (x: 4., y: 3.),
+            ]),
+        ])
+        .unwrap();
+
+        let bounds = series.bounds().unwrap();
+        let bounds = bounds.struct_().unwrap();
+        let minx: Vec<Option<f64>> = bounds
+            .field_by_name("minx")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        let maxy: Vec<Option<f64>> = bounds
+            .field_by_name("maxy")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(minx, vec![Some(1.), None, Some(0.)]);
+        assert_eq!(maxy, vec![Some(2.), None, Some(3.)]);
+
+        assert_eq!(series.total_bounds().unwrap(), Some([0., 0., 4., 3.]));
+    }
+
+    #[test]
+    fn bounds_native() {
+        let geoms: Vec<MultiPoint> = vec![
+            vec![point!(x: 0., y: 1.), point!(x: 2., y: -1.)].into(),
+            vec![point!(x: -5., y: 5.)].into(),
+        ];
+        let array: MultiPointArray = geoms.into();
+        let series =
+            Series::try_from(("geometry", GeometryArray::MultiPoint(array).into_arrow())).unwrap();
+
+        assert_eq!(series.total_bounds().unwrap(), Some([-5., -1., 2., 5.]));
+
+        // An empty GeoSeries has no bounds
+        assert_eq!(series.slice(0, 0).total_bounds().unwrap(), None);
+    }
+}
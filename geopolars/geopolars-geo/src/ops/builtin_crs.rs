@@ -0,0 +1,670 @@
+//! Built-in reprojection between common CRSs, used by `to_crs` when the `proj` feature is disabled
+//!
+//! Supported CRSs are
+//! - EPSG:4326, WGS 84 longitude/latitude
+//! - EPSG:3857, Web Mercator
+//! - EPSG:32601 to EPSG:32660 and EPSG:32701 to EPSG:32760, WGS 84 / UTM zones north and south
+//! - EPSG:32661 and EPSG:32761, WGS 84 / UPS North and South
+//! - EPSG:3035, ETRS89 / LAEA Europe
+//! - EPSG:5070, NAD83 / Conus Albers
+//!
+//! ETRS89 and NAD83 are treated as identical to WGS 84, i.e. no datum shift is applied. The
+//! difference is at most a few metres in the area of use of these CRSs.
+
+use crate::crs::{crs, set_crs};
+use crate::error::{GeopolarsError, Result};
+use crate::extension::{chunk_geoarrow_type, GeoArrowType};
+use crate::util::{map_coords, map_wkb, series_like};
+use geo::{Coord, MapCoordsInPlace};
+use polars::prelude::Series;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+/// Semi-major axis and flattening of an ellipsoid
+#[derive(Debug, Clone, Copy)]
+struct Ellipsoid {
+    a: f64,
+    f: f64,
+}
+
+const WGS84: Ellipsoid = Ellipsoid {
+    a: 6_378_137.,
+    f: 1. / 298.257_223_563,
+};
+
+const GRS80: Ellipsoid = Ellipsoid {
+    a: 6_378_137.,
+    f: 1. / 298.257_222_101,
+};
+
+impl Ellipsoid {
+    /// First eccentricity
+    fn e(&self) -> f64 {
+        (self.f * (2. - self.f)).sqrt()
+    }
+
+    /// Authalic function q of latitude `phi`, Snyder (1987) eq. 3-12
+    fn q(&self, phi: f64) -> f64 {
+        let e = self.e();
+        let sin_phi = phi.sin();
+        (1. - e * e)
+            * (sin_phi / (1. - e * e * sin_phi * sin_phi)
+                - (1. / (2. * e)) * ((1. - e * sin_phi) / (1. + e * sin_phi)).ln())
+    }
+
+    /// Inverse of [`Ellipsoid::q`], by Newton iteration, Snyder (1987) eq. 3-16
+    fn phi_from_q(&self, q: f64) -> f64 {
+        let e = self.e();
+        let mut phi = (q / 2.).clamp(-1., 1.).asin();
+        for _ in 0..15 {
+            let sin_phi = phi.sin();
+            let cos_phi = phi.cos();
+            if cos_phi.abs() < 1e-12 {
+                break;
+            }
+            let w = 1. - e * e * sin_phi * sin_phi;
+            let delta = w * w / (2. * cos_phi)
+                * (q / (1. - e * e) - sin_phi / w
+                    + (1. / (2. * e)) * ((1. - e * sin_phi) / (1. + e * sin_phi)).ln());
+            phi += delta;
+            if delta.abs() < 1e-14 {
+                break;
+            }
+        }
+        phi
+    }
+
+    /// Radius of the parallel at latitude `phi` divided by the semi-major axis, Snyder eq. 14-15
+    fn m(&self, phi: f64) -> f64 {
+        let e = self.e();
+        phi.cos() / (1. - e * e * phi.sin().powi(2)).sqrt()
+    }
+}
+
+/// A CRS supported by the built-in projections
+#[derive(Debug, Clone, Copy)]
+enum BuiltinCrs {
+    Geographic,
+    WebMercator,
+    Utm { zone: u8, north: bool },
+    Ups { north: bool },
+    LambertAzimuthalEqualArea(LambertAzimuthalEqualArea),
+    AlbersEqualArea(AlbersEqualArea),
+}
+
+impl BuiltinCrs {
+    fn from_code(crs: &str) -> Result<Self> {
+        let unsupported = || GeopolarsError::UnsupportedCrs(crs.to_string());
+
+        let (authority, code) = crs.split_once(':').ok_or_else(unsupported)?;
+        if !authority.eq_ignore_ascii_case("EPSG") {
+            return Err(unsupported());
+        }
+        let code: u32 = code.trim().parse().map_err(|_| unsupported())?;
+
+        let builtin = match code {
+            4326 => BuiltinCrs::Geographic,
+            3857 => BuiltinCrs::WebMercator,
+            32601..=32660 => BuiltinCrs::Utm {
+                zone: (code - 32600) as u8,
+                north: true,
+            },
+            32701..=32760 => BuiltinCrs::Utm {
+                zone: (code - 32700) as u8,
+                north: false,
+            },
+            32661 => BuiltinCrs::Ups { north: true },
+            32761 => BuiltinCrs::Ups { north: false },
+            3035 => BuiltinCrs::LambertAzimuthalEqualArea(LambertAzimuthalEqualArea::new(
+                GRS80, 52., 10., 4_321_000., 3_210_000.,
+            )),
+            5070 => BuiltinCrs::AlbersEqualArea(AlbersEqualArea::new(
+                GRS80, 29.5, 45.5, 23., -96., 0., 0.,
+            )),
+            _ => return Err(unsupported()),
+        };
+        Ok(builtin)
+    }
+
+    /// Project longitude/latitude in degrees to coordinates of this CRS
+    fn forward(&self, coord: Coord<f64>) -> Coord<f64> {
+        match self {
+            BuiltinCrs::Geographic => coord,
+            BuiltinCrs::WebMercator => web_mercator_forward(coord),
+            BuiltinCrs::Utm { zone, north } => utm(*zone, *north).forward(coord),
+            BuiltinCrs::Ups { north } => ups(*north).forward(coord),
+            BuiltinCrs::LambertAzimuthalEqualArea(projection) => projection.forward(coord),
+            BuiltinCrs::AlbersEqualArea(projection) => projection.forward(coord),
+        }
+    }
+
+    /// Unproject coordinates of this CRS to longitude/latitude in degrees
+    fn inverse(&self, coord: Coord<f64>) -> Coord<f64> {
+        match self {
+            BuiltinCrs::Geographic => coord,
+            BuiltinCrs::WebMercator => web_mercator_inverse(coord),
+            BuiltinCrs::Utm { zone, north } => utm(*zone, *north).inverse(coord),
+            BuiltinCrs::Ups { north } => ups(*north).inverse(coord),
+            BuiltinCrs::LambertAzimuthalEqualArea(projection) => projection.inverse(coord),
+            BuiltinCrs::AlbersEqualArea(projection) => projection.inverse(coord),
+        }
+    }
+}
+
+fn web_mercator_forward(coord: Coord<f64>) -> Coord<f64> {
+    let r = WGS84.a;
+    Coord {
+        x: r * coord.x.to_radians(),
+        y: r * (FRAC_PI_4 + coord.y.to_radians() / 2.).tan().ln(),
+    }
+}
+
+fn web_mercator_inverse(coord: Coord<f64>) -> Coord<f64> {
+    let r = WGS84.a;
+    Coord {
+        x: (coord.x / r).to_degrees(),
+        y: (2. * (coord.y / r).exp().atan() - FRAC_PI_2).to_degrees(),
+    }
+}
+
+/// Transverse Mercator using the Krüger series to third order in n, accurate to about a
+/// millimetre within a UTM zone
+#[derive(Debug, Clone, Copy)]
+struct TransverseMercator {
+    ellipsoid: Ellipsoid,
+    lon0: f64,
+    k0: f64,
+    false_easting: f64,
+    false_northing: f64,
+}
+
+fn utm(zone: u8, north: bool) -> TransverseMercator {
+    TransverseMercator {
+        ellipsoid: WGS84,
+        lon0: (zone as f64 - 1.) * 6. - 180. + 3.,
+        k0: 0.9996,
+        false_easting: 500_000.,
+        false_northing: if north { 0. } else { 10_000_000. },
+    }
+}
+
+impl TransverseMercator {
+    fn n(&self) -> f64 {
+        self.ellipsoid.f / (2. - self.ellipsoid.f)
+    }
+
+    /// Radius of the rectifying sphere
+    fn rectifying_radius(&self) -> f64 {
+        let n = self.n();
+        self.ellipsoid.a / (1. + n) * (1. + n.powi(2) / 4. + n.powi(4) / 64.)
+    }
+
+    fn forward(&self, coord: Coord<f64>) -> Coord<f64> {
+        let n = self.n();
+        let alpha = [
+            n / 2. - 2. * n.powi(2) / 3. + 5. * n.powi(3) / 16.,
+            13. * n.powi(2) / 48. - 3. * n.powi(3) / 5.,
+            61. * n.powi(3) / 240.,
+        ];
+
+        let phi = coord.y.to_radians();
+        let lambda = (coord.x - self.lon0).to_radians();
+        let e = self.ellipsoid.e();
+
+        let t = (phi.sin().atanh() - e * (e * phi.sin()).atanh()).sinh();
+        let xi_prime = t.atan2(lambda.cos());
+        let eta_prime = (lambda.sin() / (1. + t * t).sqrt()).atanh();
+
+        let mut xi = xi_prime;
+        let mut eta = eta_prime;
+        for (j, alpha_j) in alpha.iter().enumerate() {
+            let k = 2. * (j + 1) as f64;
+            xi += alpha_j * (k * xi_prime).sin() * (k * eta_prime).cosh();
+            eta += alpha_j * (k * xi_prime).cos() * (k * eta_prime).sinh();
+        }
+
+        let scale = self.k0 * self.rectifying_radius();
+        Coord {
+            x: self.false_easting + scale * eta,
+            y: self.false_northing + scale * xi,
+        }
+    }
+
+    fn inverse(&self, coord: Coord<f64>) -> Coord<f64> {
+        let n = self.n();
+        let beta = [
+            n / 2. - 2. * n.powi(2) / 3. + 37. * n.powi(3) / 96.,
+            n.powi(2) / 48. + n.powi(3) / 15.,
+            17. * n.powi(3) / 480.,
+        ];
+        let delta = [
+            2. * n - 2. * n.powi(2) / 3. - 2. * n.powi(3),
+            7. * n.powi(2) / 3. - 8. * n.powi(3) / 5.,
+            56. * n.powi(3) / 15.,
+        ];
+
+        let scale = self.k0 * self.rectifying_radius();
+        let xi = (coord.y - self.false_northing) / scale;
+        let eta = (coord.x - self.false_easting) / scale;
+
+        let mut xi_prime = xi;
+        let mut eta_prime = eta;
+        for (j, beta_j) in beta.iter().enumerate() {
+            let k = 2. * (j + 1) as f64;
+            xi_prime -= beta_j * (k * xi).sin() * (k * eta).cosh();
+            eta_prime -= beta_j * (k * xi).cos() * (k * eta).sinh();
+        }
+
+        let chi = (xi_prime.sin() / eta_prime.cosh()).asin();
+        let mut phi = chi;
+        for (j, delta_j) in delta.iter().enumerate() {
+            phi += delta_j * (2. * (j + 1) as f64 * chi).sin();
+        }
+        let lambda = eta_prime.sinh().atan2(xi_prime.cos());
+
+        Coord {
+            x: self.lon0 + lambda.to_degrees(),
+            y: phi.to_degrees(),
+        }
+    }
+}
+
+/// Polar stereographic projection on the ellipsoid, variant A, Snyder (1987) p. 161
+#[derive(Debug, Clone, Copy)]
+struct PolarStereographic {
+    ellipsoid: Ellipsoid,
+    north: bool,
+    lon0: f64,
+    k0: f64,
+    false_easting: f64,
+    false_northing: f64,
+}
+
+fn ups(north: bool) -> PolarStereographic {
+    PolarStereographic {
+        ellipsoid: WGS84,
+        north,
+        lon0: 0.,
+        k0: 0.994,
+        false_easting: 2_000_000.,
+        false_northing: 2_000_000.,
+    }
+}
+
+impl PolarStereographic {
+    /// The factor relating the polar distance rho to t, Snyder (1987) eq. 21-33
+    fn scale(&self) -> f64 {
+        let e = self.ellipsoid.e();
+        2. * self.ellipsoid.a * self.k0 / ((1. + e).powf(1. + e) * (1. - e).powf(1. - e)).sqrt()
+    }
+
+    /// Snyder eq. 15-9, for the latitude mirrored into the northern hemisphere
+    fn t(&self, phi: f64) -> f64 {
+        let e = self.ellipsoid.e();
+        let e_sin_phi = e * phi.sin();
+        (FRAC_PI_4 - phi / 2.).tan() / ((1. - e_sin_phi) / (1. + e_sin_phi)).powf(e / 2.)
+    }
+
+    fn forward(&self, coord: Coord<f64>) -> Coord<f64> {
+        let phi = coord.y.to_radians();
+        let phi = if self.north { phi } else { -phi };
+        let lambda = (coord.x - self.lon0).to_radians();
+
+        let rho = self.scale() * self.t(phi);
+        let y = rho * lambda.cos();
+        Coord {
+            x: self.false_easting + rho * lambda.sin(),
+            y: if self.north {
+                self.false_northing - y
+            } else {
+                self.false_northing + y
+            },
+        }
+    }
+
+    /// Inverse by fixed point iteration, Snyder (1987) eq. 7-9
+    fn inverse(&self, coord: Coord<f64>) -> Coord<f64> {
+        let e = self.ellipsoid.e();
+        let x = coord.x - self.false_easting;
+        let y = coord.y - self.false_northing;
+        let y = if self.north { -y } else { y };
+
+        let t = x.hypot(y) / self.scale();
+        let mut phi = FRAC_PI_2 - 2. * t.atan();
+        for _ in 0..15 {
+            let e_sin_phi = e * phi.sin();
+            let next =
+                FRAC_PI_2 - 2. * (t * ((1. - e_sin_phi) / (1. + e_sin_phi)).powf(e / 2.)).atan();
+            let delta = next - phi;
+            phi = next;
+            if delta.abs() < 1e-14 {
+                break;
+            }
+        }
+
+        let lambda = if t == 0. { 0. } else { x.atan2(y) };
+        Coord {
+            x: self.lon0 + lambda.to_degrees(),
+            y: if self.north { phi } else { -phi }.to_degrees(),
+        }
+    }
+}
+
+/// Lambert azimuthal equal-area projection on the ellipsoid, oblique aspect, Snyder (1987) p. 187
+#[derive(Debug, Clone, Copy)]
+struct LambertAzimuthalEqualArea {
+    ellipsoid: Ellipsoid,
+    lon0: f64,
+    false_easting: f64,
+    false_northing: f64,
+    qp: f64,
+    beta1: f64,
+    rq: f64,
+    d: f64,
+}
+
+impl LambertAzimuthalEqualArea {
+    fn new(
+        ellipsoid: Ellipsoid,
+        lat0: f64,
+        lon0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    ) -> Self {
+        let phi1 = lat0.to_radians();
+        let qp = ellipsoid.q(FRAC_PI_2);
+        let beta1 = (ellipsoid.q(phi1) / qp).asin();
+        let rq = ellipsoid.a * (qp / 2.).sqrt();
+        let d = ellipsoid.a * ellipsoid.m(phi1) / (rq * beta1.cos());
+        Self {
+            ellipsoid,
+            lon0,
+            false_easting,
+            false_northing,
+            qp,
+            beta1,
+            rq,
+            d,
+        }
+    }
+
+    fn forward(&self, coord: Coord<f64>) -> Coord<f64> {
+        let phi = coord.y.to_radians();
+        let lambda = (coord.x - self.lon0).to_radians();
+        let beta = (self.ellipsoid.q(phi) / self.qp).clamp(-1., 1.).asin();
+
+        let b = self.rq
+            * (2.
+                / (1.
+                    + self.beta1.sin() * beta.sin()
+                    + self.beta1.cos() * beta.cos() * lambda.cos()))
+            .sqrt();
+        Coord {
+            x: self.false_easting + b * self.d * beta.cos() * lambda.sin(),
+            y: self.false_northing
+                + (b / self.d)
+                    * (self.beta1.cos() * beta.sin()
+                        - self.beta1.sin() * beta.cos() * lambda.cos()),
+        }
+    }
+
+    fn inverse(&self, coord: Coord<f64>) -> Coord<f64> {
+        let x = coord.x - self.false_easting;
+        let y = coord.y - self.false_northing;
+        let rho = ((x / self.d).powi(2) + (self.d * y).powi(2)).sqrt();
+        if rho < 1e-12 {
+            return Coord {
+                x: self.lon0,
+                y: self.beta1.to_degrees(),
+            };
+        }
+
+        let ce = 2. * (rho / (2. * self.rq)).clamp(-1., 1.).asin();
+        let q = self.qp
+            * (ce.cos() * self.beta1.sin() + self.d * y * ce.sin() * self.beta1.cos() / rho);
+        let lambda = (x * ce.sin()).atan2(
+            self.d * rho * self.beta1.cos() * ce.cos()
+                - self.d.powi(2) * y * self.beta1.sin() * ce.sin(),
+        );
+
+        Coord {
+            x: self.lon0 + lambda.to_degrees(),
+            y: self.ellipsoid.phi_from_q(q).to_degrees(),
+        }
+    }
+}
+
+/// Albers equal-area conic projection on the ellipsoid, Snyder (1987) p. 101
+#[derive(Debug, Clone, Copy)]
+struct AlbersEqualArea {
+    ellipsoid: Ellipsoid,
+    lon0: f64,
+    false_easting: f64,
+    false_northing: f64,
+    n: f64,
+    c: f64,
+    rho0: f64,
+}
+
+impl AlbersEqualArea {
+    fn new(
+        ellipsoid: Ellipsoid,
+        lat1: f64,
+        lat2: f64,
+        lat0: f64,
+        lon0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    ) -> Self {
+        let (phi1, phi2, phi0) = (lat1.to_radians(), lat2.to_radians(), lat0.to_radians());
+        let (m1, m2) = (ellipsoid.m(phi1), ellipsoid.m(phi2));
+        let (q1, q2, q0) = (ellipsoid.q(phi1), ellipsoid.q(phi2), ellipsoid.q(phi0));
+
+        let n = (m1 * m1 - m2 * m2) / (q2 - q1);
+        let c = m1 * m1 + n * q1;
+        let rho0 = ellipsoid.a * (c - n * q0).sqrt() / n;
+        Self {
+            ellipsoid,
+            lon0,
+            false_easting,
+            false_northing,
+            n,
+            c,
+            rho0,
+        }
+    }
+
+    fn forward(&self, coord: Coord<f64>) -> Coord<f64> {
+        let q = self.ellipsoid.q(coord.y.to_radians());
+        let rho = self.ellipsoid.a * (self.c - self.n * q).sqrt() / self.n;
+        let theta = self.n * (coord.x - self.lon0).to_radians();
+        Coord {
+            x: self.false_easting + rho * theta.sin(),
+            y: self.false_northing + self.rho0 - rho * theta.cos(),
+        }
+    }
+
+    fn inverse(&self, coord: Coord<f64>) -> Coord<f64> {
+        let x = coord.x - self.false_easting;
+        let y = self.rho0 - (coord.y - self.false_northing);
+        let rho = (x * x + y * y).sqrt();
+        let theta = x.atan2(y);
+        let q = (self.c - (rho * self.n / self.ellipsoid.a).powi(2)) / self.n;
+        Coord {
+            x: self.lon0 + (theta / self.n).to_degrees(),
+            y: self.ellipsoid.phi_from_q(q).to_degrees(),
+        }
+    }
+}
+
+pub(crate) fn to_crs(series: &Series, to: &str) -> Result<Series> {
+    let from = crs(series)?.ok_or(GeopolarsError::MissingCrs)?;
+    let output = transform(series, &from, to)?;
+    set_crs(&output, Some(to))
+}
+
+/// Transform every chunk of a geometry Series from `from` to `to`, on multiple threads with the
+/// `parallel` feature
+fn transform(series: &Series, from: &str, to: &str) -> Result<Series> {
+    let from = BuiltinCrs::from_code(from)?;
+    let to = BuiltinCrs::from_code(to)?;
+    let convert = |coord: Coord<f64>| to.forward(from.inverse(coord));
+
+    let output_chunks = series
+        .chunks()
+        .iter()
+        .map(|chunk| match chunk_geoarrow_type(&**chunk)? {
+            GeoArrowType::WKB => map_wkb(
+                &**chunk,
+                || (),
+                |_, mut geom| {
+                    geom.map_coords_in_place(convert);
+                    Ok(geom)
+                },
+            ),
+            _ => map_coords(&**chunk, &mut |coords: &mut [Coord<f64>]| {
+                #[cfg(feature = "parallel")]
+                let coords = coords.par_iter_mut();
+                #[cfg(not(feature = "parallel"))]
+                let coords = coords.iter_mut();
+                coords.for_each(|coord| *coord = convert(*coord));
+                Ok(())
+            }),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    series_like(series, output_chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BuiltinCrs;
+    use crate::error::GeopolarsError;
+    use crate::geoseries::GeoSeries;
+    use crate::util::{from_geom_vec, iter_geom};
+    use geo::{point, Coord, Geometry, Point};
+    use geoarrow::{GeometryArray, GeometryArrayTrait, PointArray};
+    use polars::prelude::{DataType, Series};
+
+    fn assert_round_trip(code: &str, coord: Coord<f64>, expected: Coord<f64>, tolerance: f64) {
+        let crs = BuiltinCrs::from_code(code).unwrap();
+        let projected = crs.forward(coord);
+        assert!(
+            (projected.x - expected.x).abs() < tolerance
+                && (projected.y - expected.y).abs() < tolerance,
+            "{code}: expected {expected:?}, got {projected:?}"
+        );
+
+        // 1e-7 degrees is about a centimetre
+        let unprojected = crs.inverse(projected);
+        assert!((unprojected.x - coord.x).abs() < 1e-7);
+        assert!((unprojected.y - coord.y).abs() < 1e-7);
+    }
+
+    #[test]
+    fn projections() {
+        assert_round_trip(
+            "EPSG:3857",
+            Coord { x: 10., y: 50. },
+            Coord {
+                x: 1_113_194.908,
+                y: 6_446_275.841,
+            },
+            1e-3,
+        );
+        // Reference values from the sixth order Krüger series
+        assert_round_trip(
+            "EPSG:32632",
+            Coord { x: 10., y: 50. },
+            Coord {
+                x: 571_666.448,
+                y: 5_539_109.815,
+            },
+            1e-3,
+        );
+        assert_round_trip(
+            "EPSG:32733",
+            Coord { x: 13.2, y: -8.8 },
+            Coord {
+                x: 302_016.100,
+                y: 9_026_783.255,
+            },
+            1e-3,
+        );
+        // Example from EPSG Guidance Note 7-2
+        assert_round_trip(
+            "EPSG:3035",
+            Coord { x: 5., y: 50. },
+            Coord {
+                x: 3_962_799.45,
+                y: 2_999_718.85,
+            },
+            1e-2,
+        );
+        // Example from EPSG Guidance Note 7-2, which uses the parameters of UPS North
+        assert_round_trip(
+            "EPSG:32661",
+            Coord { x: 44., y: 73. },
+            Coord {
+                x: 3_320_416.75,
+                y: 632_668.43,
+            },
+            1e-2,
+        );
+        assert_round_trip(
+            "EPSG:32761",
+            Coord { x: 44., y: -73. },
+            Coord {
+                x: 3_320_416.75,
+                y: 3_367_331.57,
+            },
+            1e-2,
+        );
+        // The projection origin maps onto the false easting and northing
+        assert_round_trip(
+            "EPSG:5070",
+            Coord { x: -96., y: 23. },
+            Coord { x: 0., y: 0. },
+            1e-3,
+        );
+        let albers = BuiltinCrs::from_code("EPSG:5070").unwrap();
+        let coord = albers.inverse(albers.forward(Coord { x: -100., y: 40. }));
+        assert!((coord.x + 100.).abs() < 1e-9 && (coord.y - 40.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn to_crs() {
+        let series = from_geom_vec(&[Geometry::Point(point!(x: 10., y: 50.))])
+            .unwrap()
+            .set_crs("EPSG:4326")
+            .unwrap();
+
+        let output = series.to_crs("EPSG:3857").unwrap();
+        assert_eq!(output.crs().unwrap(), Some("EPSG:3857".to_string()));
+
+        let point = Point::try_from(iter_geom(&output).next().unwrap()).unwrap();
+        assert!((point.x() - 1_113_194.908).abs() < 1e-3);
+
+        assert!(matches!(
+            series.to_crs("EPSG:2056"),
+            Err(GeopolarsError::UnsupportedCrs(_))
+        ));
+    }
+
+    #[test]
+    fn to_crs_points() {
+        let input_array: PointArray = vec![point!(x: 1., y: 0.)].into();
+        let arrow_array = GeometryArray::Point(input_array).into_arrow();
+        let series = Series::try_from(("geometry", arrow_array))
+            .unwrap()
+            .set_crs("EPSG:4326")
+            .unwrap();
+
+        let output = series.to_crs("EPSG:3857").unwrap();
+        assert!(matches!(output.dtype(), DataType::Struct(_)));
+        assert_eq!(output.crs().unwrap(), Some("EPSG:3857".to_string()));
+        let x = GeoSeries::x(&output).unwrap();
+        assert!((x.f64().unwrap().get(0).unwrap() - 111_319.49).abs() < 0.01);
+    }
+}
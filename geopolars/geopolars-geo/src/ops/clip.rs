@@ -0,0 +1,259 @@
+use crate::error::Result;
+use crate::extension::GeoArrowType;
+use crate::util::{geometries_of_array, geometry_array_from_geometries};
+use geo::algorithm::bool_ops::BooleanOps;
+use geo::algorithm::bounding_rect::BoundingRect;
+use geo::algorithm::dimensions::HasDimensions;
+use geo::algorithm::Intersects;
+use geo::{
+    Coord, Geometry, GeometryCollection, Line, LineString, MultiLineString, MultiPolygon, Point,
+    Rect,
+};
+use geoarrow::GeometryArray;
+use polars::error::ErrString;
+use polars::prelude::PolarsError;
+
+pub(crate) fn clip_by_rect(array: GeometryArray, rect: Rect) -> Result<GeometryArray> {
+    clip_array(array, &rect)
+}
+
+pub(crate) fn clip(array: GeometryArray, mask: &Geometry) -> Result<GeometryArray> {
+    match mask {
+        Geometry::Rect(rect) => clip_array(array, rect),
+        Geometry::Polygon(polygon) => clip_array(array, &MultiPolygon::new(vec![polygon.clone()])),
+        Geometry::MultiPolygon(polygons) => clip_array(array, polygons),
+        Geometry::Triangle(triangle) => {
+            clip_array(array, &MultiPolygon::new(vec![triangle.to_polygon()]))
+        }
+        _ => Err(PolarsError::ComputeError(ErrString::from(
+            "The mask to clip to must be a Polygon, MultiPolygon or Rect",
+        ))
+        .into()),
+    }
+}
+
+/// A polygonal area that geometries are clipped to
+trait Mask {
+    /// Whether the coordinate is in the interior or on the boundary of the mask
+    fn covers(&self, coord: Coord) -> bool;
+
+    fn clip_lines(&self, lines: Vec<LineString>) -> Vec<LineString>;
+
+    fn clip_polygons(&self, polygons: MultiPolygon) -> MultiPolygon;
+}
+
+impl Mask for MultiPolygon {
+    fn covers(&self, coord: Coord) -> bool {
+        self.intersects(&Point(coord))
+    }
+
+    fn clip_lines(&self, lines: Vec<LineString>) -> Vec<LineString> {
+        self.clip(&MultiLineString::new(lines), false).0
+    }
+
+    fn clip_polygons(&self, polygons: MultiPolygon) -> MultiPolygon {
+        self.intersection(&polygons)
+    }
+}
+
+/// Axis-aligned rectangles clip lines segment by segment, and skip the polygon overlay for
+/// polygons entirely inside or outside of them
+impl Mask for Rect {
+    fn covers(&self, coord: Coord) -> bool {
+        self.min().x <= coord.x
+            && coord.x <= self.max().x
+            && self.min().y <= coord.y
+            && coord.y <= self.max().y
+    }
+
+    fn clip_lines(&self, lines: Vec<LineString>) -> Vec<LineString> {
+        let mut pieces = vec![];
+        let mut push_piece = |mut coords: Vec<Coord>| {
+            coords.dedup();
+            if coords.len() >= 2 {
+                pieces.push(LineString::new(coords));
+            }
+        };
+
+        for line in lines {
+            if let Some(bounds) = line.bounding_rect() {
+                if self.covers(bounds.min()) && self.covers(bounds.max()) {
+                    push_piece(line.0);
+                    continue;
+                }
+            }
+
+            let mut piece: Vec<Coord> = vec![];
+            for segment in line.lines() {
+                if let Some((start, end)) = clip_segment(segment, self) {
+                    // The piece continues if the previous segment ended inside the rectangle
+                    if piece.last() != Some(&start) {
+                        push_piece(std::mem::take(&mut piece));
+                        piece.push(start);
+                    }
+                    piece.push(end);
+                }
+            }
+            push_piece(piece);
+        }
+        pieces
+    }
+
+    fn clip_polygons(&self, polygons: MultiPolygon) -> MultiPolygon {
+        match polygons.bounding_rect() {
+            Some(bounds) if self.covers(bounds.min()) && self.covers(bounds.max()) => polygons,
+            Some(bounds) if self.intersects(&bounds) => {
+                MultiPolygon::new(vec![self.to_polygon()]).intersection(&polygons)
+            }
+            _ => MultiPolygon::new(vec![]),
+        }
+    }
+}
+
+/// The part of a segment inside a rectangle, with the Liang-Barsky algorithm
+fn clip_segment(segment: Line, rect: &Rect) -> Option<(Coord, Coord)> {
+    let (start, delta) = (segment.start, segment.delta());
+    let (mut t0, mut t1) = (0_f64, 1_f64);
+    for (p, q) in [
+        (-delta.x, start.x - rect.min().x),
+        (delta.x, rect.max().x - start.x),
+        (-delta.y, start.y - rect.min().y),
+        (delta.y, rect.max().y - start.y),
+    ] {
+        if p == 0. {
+            // Parallel to this side of the rectangle, and outside of it
+            if q < 0. {
+                return None;
+            }
+        } else if p < 0. {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+
+    // Keep the exact ends of segments that aren't clipped, so that pieces join up
+    let at = |t: f64| {
+        if t == 0. {
+            segment.start
+        } else if t == 1. {
+            segment.end
+        } else {
+            start + delta * t
+        }
+    };
+    Some((at(t0), at(t1)))
+}
+
+/// Clip each geometry of an array to a mask
+///
+/// Lines and polygons become MultiLineStrings and MultiPolygons, as clipping may split them.
+/// Points outside of the mask become null, while other geometries outside of it become empty.
+fn clip_array(array: GeometryArray, mask: &impl Mask) -> Result<GeometryArray> {
+    let geometry_type = match GeoArrowType::from(&array) {
+        GeoArrowType::LineString => GeoArrowType::MultiLineString,
+        GeoArrowType::Polygon => GeoArrowType::MultiPolygon,
+        geometry_type => geometry_type,
+    };
+    let output_geoms = geometries_of_array(array)
+        .into_iter()
+        .map(|maybe_g| maybe_g.and_then(|geom| clip_geometry(geom, mask)))
+        .collect();
+    geometry_array_from_geometries(output_geoms, geometry_type)
+}
+
+fn clip_geometry(geom: Geometry, mask: &impl Mask) -> Option<Geometry> {
+    let clipped = match geom {
+        Geometry::Point(g) => return mask.covers(g.0).then(|| Geometry::Point(g)),
+        Geometry::MultiPoint(g) => {
+            Geometry::MultiPoint(g.into_iter().filter(|point| mask.covers(point.0)).collect())
+        }
+        Geometry::Line(g) => Geometry::MultiLineString(MultiLineString::new(
+            mask.clip_lines(vec![LineString::new(vec![g.start, g.end])]),
+        )),
+        Geometry::LineString(g) => {
+            Geometry::MultiLineString(MultiLineString::new(mask.clip_lines(vec![g])))
+        }
+        Geometry::MultiLineString(g) => {
+            Geometry::MultiLineString(MultiLineString::new(mask.clip_lines(g.0)))
+        }
+        Geometry::Polygon(g) => {
+            Geometry::MultiPolygon(mask.clip_polygons(MultiPolygon::new(vec![g])))
+        }
+        Geometry::MultiPolygon(g) => Geometry::MultiPolygon(mask.clip_polygons(g)),
+        Geometry::Rect(g) => {
+            Geometry::MultiPolygon(mask.clip_polygons(MultiPolygon::new(vec![g.to_polygon()])))
+        }
+        Geometry::Triangle(g) => {
+            Geometry::MultiPolygon(mask.clip_polygons(MultiPolygon::new(vec![g.to_polygon()])))
+        }
+        Geometry::GeometryCollection(g) => Geometry::GeometryCollection(GeometryCollection(
+            g.into_iter()
+                .filter_map(|geom| clip_geometry(geom, mask))
+                .filter(|geom| !geom.is_empty())
+                .collect(),
+        )),
+    };
+    Some(clipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoseries::GeoSeries;
+    use crate::util::{from_geom_vec, geometries};
+    use geo::prelude::Area;
+    use geo::{line_string, point, polygon, Geometry, MultiLineString};
+
+    #[test]
+    fn clip_by_rect() {
+        let series = from_geom_vec(&[
+            Geometry::Polygon(polygon![
+                (x: 1., y: 1.),
+                (x: 3., y: 1.),
+                (x: 3., y: 3.),
+                (x: 1., y: 3.),
+            ]),
+            Geometry::LineString(line_string![
+                (x: -1., y: 1.),
+                (x: 1., y: 1.),
+                (x: 1., y: 3.),
+                (x: 1.5, y: 3.),
+                (x: 1.5, y: 1.),
+            ]),
+            Geometry::Point(point!(x: 5., y: 5.)),
+        ])
+        .unwrap();
+
+        let clipped = geometries(&series.clip_by_rect(0., 0., 2., 2.).unwrap()).unwrap();
+        assert!((clipped[0].as_ref().unwrap().unsigned_area() - 1.).abs() < 1e-12);
+        assert_eq!(
+            clipped[1],
+            Some(Geometry::MultiLineString(MultiLineString::new(vec![
+                line_string![(x: 0., y: 1.), (x: 1., y: 1.), (x: 1., y: 2.)],
+                line_string![(x: 1.5, y: 2.), (x: 1.5, y: 1.)],
+            ])))
+        );
+        assert_eq!(clipped[2], None);
+    }
+
+    #[test]
+    fn clip_by_polygon() {
+        let series = from_geom_vec(&[Geometry::Polygon(polygon![
+            (x: 1., y: 1.),
+            (x: 3., y: 1.),
+            (x: 3., y: 3.),
+            (x: 1., y: 3.),
+        ])])
+        .unwrap();
+        let mask = Geometry::Polygon(polygon![(x: 0., y: 0.), (x: 4., y: 0.), (x: 0., y: 4.)]);
+
+        let clipped = geometries(&GeoSeries::clip(&series, &mask).unwrap()).unwrap();
+        assert!((clipped[0].as_ref().unwrap().unsigned_area() - 2.).abs() < 1e-12);
+
+        let mask = Geometry::Point(point!(x: 0., y: 0.));
+        assert!(GeoSeries::clip(&series, &mask).is_err());
+    }
+}
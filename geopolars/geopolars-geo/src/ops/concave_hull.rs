@@ -0,0 +1,235 @@
+use crate::error::Result;
+use crate::ops::delaunay::{edges, triangulate};
+use geo::algorithm::convex_hull::ConvexHull;
+use geo::{Coord, CoordsIter, LineString, MultiPoint, Point, Polygon};
+use geoarrow::{GeometryArray, GeometryArrayTrait};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+pub(crate) fn concave_hull(array: GeometryArray, ratio: f64) -> Result<GeometryArray> {
+    let output_geoms: Vec<Option<Polygon>> = match array {
+        GeometryArray::WKB(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| concave_hull_of(geom.coords_iter(), ratio)))
+            .collect(),
+        GeometryArray::Point(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| concave_hull_of(geom.coords_iter(), ratio)))
+            .collect(),
+        GeometryArray::MultiPoint(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| concave_hull_of(geom.coords_iter(), ratio)))
+            .collect(),
+        GeometryArray::LineString(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| concave_hull_of(geom.coords_iter(), ratio)))
+            .collect(),
+        GeometryArray::MultiLineString(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| concave_hull_of(geom.coords_iter(), ratio)))
+            .collect(),
+        GeometryArray::Polygon(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| concave_hull_of(geom.coords_iter(), ratio)))
+            .collect(),
+        GeometryArray::MultiPolygon(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| concave_hull_of(geom.coords_iter(), ratio)))
+            .collect(),
+    };
+
+    Ok(GeometryArray::Polygon(output_geoms.into()))
+}
+
+/// A border edge of the triangulation being eroded, ordered by length
+struct BorderEdge {
+    length: f64,
+    triangle: usize,
+    edge: (usize, usize),
+}
+
+impl PartialEq for BorderEdge {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for BorderEdge {}
+
+impl PartialOrd for BorderEdge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BorderEdge {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.length
+            .partial_cmp(&other.length)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// The concave hull of coordinates, computed like the JTS and GEOS `ConcaveHull`
+///
+/// Starting from the Delaunay triangulation of the coordinates, border triangles are removed
+/// longest border edge first, as long as that edge is longer than the threshold
+/// `min + ratio * (max - min)` of the triangulation's edge lengths. A triangle is only removed
+/// if its third vertex is not yet on the border, which keeps the hull a single polygon without
+/// holes.
+fn concave_hull_of(coords: impl Iterator<Item = Coord>, ratio: f64) -> Polygon {
+    let mut points: Vec<Coord> = coords.collect();
+    points.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .unwrap_or(Ordering::Equal)
+            .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+    });
+    points.dedup();
+
+    let convex_hull =
+        |points: Vec<Coord>| MultiPoint::new(points.into_iter().map(Point).collect()).convex_hull();
+    let triangles = if ratio < 1. {
+        triangulate(&points)
+    } else {
+        vec![]
+    };
+    if triangles.is_empty() {
+        return convex_hull(points);
+    }
+
+    let length =
+        |(a, b): (usize, usize)| (points[b].x - points[a].x).hypot(points[b].y - points[a].y);
+    let key = |(a, b): (usize, usize)| (a.min(b), a.max(b));
+
+    let mut adjacent: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (t, triangle) in triangles.iter().enumerate() {
+        for edge in edges(triangle) {
+            adjacent.entry(key(edge)).or_default().push(t);
+        }
+    }
+    let (min, max) = adjacent
+        .keys()
+        .map(|edge| length(*edge))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), l| {
+            (min.min(l), max.max(l))
+        });
+    let threshold = min + ratio.max(0.) * (max - min);
+
+    let mut alive = vec![true; triangles.len()];
+    let mut on_border = vec![false; points.len()];
+    let mut queue = BinaryHeap::new();
+    for (t, triangle) in triangles.iter().enumerate() {
+        for edge in edges(triangle) {
+            if adjacent[&key(edge)].len() == 1 {
+                on_border[edge.0] = true;
+                on_border[edge.1] = true;
+                queue.push(BorderEdge {
+                    length: length(edge),
+                    triangle: t,
+                    edge,
+                });
+            }
+        }
+    }
+
+    while let Some(BorderEdge {
+        length: edge_length,
+        triangle: t,
+        edge,
+    }) = queue.pop()
+    {
+        if edge_length <= threshold {
+            break;
+        }
+        let triangle = triangles[t];
+        let opposite = triangle
+            .iter()
+            .copied()
+            .find(|v| *v != edge.0 && *v != edge.1)
+            .unwrap();
+        // Once the opposite vertex is on the border it stays there, so the triangle can't be
+        // removed later either
+        if !alive[t] || on_border[opposite] {
+            continue;
+        }
+
+        alive[t] = false;
+        on_border[opposite] = true;
+        for other_edge in edges(&triangle) {
+            if key(other_edge) == key(edge) {
+                continue;
+            }
+            if let Some(&neighbour) = adjacent[&key(other_edge)]
+                .iter()
+                .find(|n| **n != t && alive[**n])
+            {
+                queue.push(BorderEdge {
+                    length: length(other_edge),
+                    triangle: neighbour,
+                    edge: other_edge,
+                });
+            }
+        }
+    }
+
+    // Trace the border of the remaining triangles, whose counter-clockwise edges are chained
+    // into a single ring
+    let mut next: HashMap<usize, usize> = HashMap::new();
+    for (t, triangle) in triangles.iter().enumerate() {
+        if !alive[t] {
+            continue;
+        }
+        for edge in edges(triangle) {
+            let is_border = adjacent[&key(edge)].iter().all(|n| *n == t || !alive[*n]);
+            if is_border {
+                next.insert(edge.0, edge.1);
+            }
+        }
+    }
+
+    let start = *next.keys().min().unwrap();
+    let mut ring = vec![points[start]];
+    let mut vertex = next[&start];
+    while vertex != start && ring.len() <= next.len() {
+        ring.push(points[vertex]);
+        vertex = next[&vertex];
+    }
+    Polygon::new(LineString::new(ring), vec![])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::concave_hull;
+    use crate::util::geometries_of_array;
+    use geo::algorithm::convex_hull::ConvexHull;
+    use geo::prelude::Area;
+    use geo::{Geometry, MultiPoint, Point};
+    use geoarrow::{GeometryArray, WKBArray};
+
+    #[test]
+    fn concave_hull_of_c() {
+        // Points along a C shape, open to the right
+        let mut points = vec![];
+        for i in 0..=4 {
+            let v = i as f64;
+            points.push(Point::new(v, 0.));
+            points.push(Point::new(v, 4.));
+            points.push(Point::new(0., v));
+        }
+        points.push(Point::new(1., 1.));
+        points.push(Point::new(1., 3.));
+        let geom = Geometry::MultiPoint(MultiPoint::new(points));
+        let convex_area = geom.convex_hull().unsigned_area();
+
+        let input = || {
+            let array: WKBArray = vec![Some(geom.clone())].into();
+            GeometryArray::WKB(array)
+        };
+        let tight = geometries_of_array(concave_hull(input(), 0.).unwrap());
+        let loose = geometries_of_array(concave_hull(input(), 1.).unwrap());
+
+        let tight_area = tight[0].as_ref().unwrap().unsigned_area();
+        assert!(tight_area > 0. && tight_area < convex_area);
+        assert_eq!(loose[0].as_ref().unwrap().unsigned_area(), convex_area);
+    }
+}
@@ -0,0 +1,462 @@
+use crate::crs::with_crs_of;
+use crate::error::Result;
+use crate::extension::{chunk_geoarrow_type, storage_array, GeoArrowType};
+use crate::util::{geometries_of_array, geometry_array, geometry_series_from_arrays, wkb_rows};
+use geo::{Coord, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
+use geoarrow::GeometryArray;
+use geozero::error::Result as GeozeroResult;
+use geozero::wkb::Wkb;
+use geozero::{CoordDimensions, GeomProcessor, GeozeroGeometry};
+use polars::error::ErrString;
+use polars::prelude::{DataFrame, DataType, NamedFrom, PolarsError, Series};
+use std::collections::BTreeMap;
+
+/// Collects the coordinates of geometries together with the index of the row, part and ring they
+/// belong to
+#[derive(Default)]
+struct CoordinateCollector {
+    include_z: bool,
+
+    row: u32,
+    part: u32,
+    ring: u32,
+    in_multipoint: bool,
+    in_polygon: bool,
+    in_collection: bool,
+
+    index: Vec<u32>,
+    parts: Vec<u32>,
+    rings: Vec<u32>,
+    x: Vec<f64>,
+    y: Vec<f64>,
+    z: Vec<Option<f64>>,
+}
+
+impl CoordinateCollector {
+    fn start_row(&mut self, row: u32) {
+        self.row = row;
+        self.part = 0;
+        self.ring = 0;
+        self.in_multipoint = false;
+        self.in_polygon = false;
+        self.in_collection = false;
+    }
+
+    fn push(&mut self, x: f64, y: f64, z: Option<f64>, idx: usize) {
+        // The points of a MultiPoint have no begin event of their own
+        let part = if self.in_multipoint {
+            idx as u32
+        } else {
+            self.part
+        };
+        self.index.push(self.row);
+        self.parts.push(part);
+        self.rings.push(self.ring);
+        self.x.push(x);
+        self.y.push(y);
+        self.z.push(z);
+    }
+
+    /// Members of a GeometryCollection are parts of the row
+    fn member_begin(&mut self, idx: usize) {
+        if self.in_collection {
+            self.part = idx as u32;
+            self.ring = 0;
+        }
+    }
+}
+
+impl GeomProcessor for CoordinateCollector {
+    fn dimensions(&self) -> CoordDimensions {
+        if self.include_z {
+            CoordDimensions::xyz()
+        } else {
+            CoordDimensions::xy()
+        }
+    }
+
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> GeozeroResult<()> {
+        self.push(x, y, None, idx);
+        Ok(())
+    }
+
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        idx: usize,
+    ) -> GeozeroResult<()> {
+        self.push(x, y, z, idx);
+        Ok(())
+    }
+
+    fn point_begin(&mut self, idx: usize) -> GeozeroResult<()> {
+        self.member_begin(idx);
+        Ok(())
+    }
+
+    fn multipoint_begin(&mut self, _size: usize, idx: usize) -> GeozeroResult<()> {
+        self.member_begin(idx);
+        self.in_multipoint = true;
+        Ok(())
+    }
+
+    fn multipoint_end(&mut self, _idx: usize) -> GeozeroResult<()> {
+        self.in_multipoint = false;
+        Ok(())
+    }
+
+    fn linestring_begin(&mut self, tagged: bool, _size: usize, idx: usize) -> GeozeroResult<()> {
+        if tagged {
+            self.member_begin(idx);
+        } else if self.in_polygon {
+            self.ring = idx as u32;
+        } else {
+            // A member of a MultiLineString
+            self.part = idx as u32;
+        }
+        Ok(())
+    }
+
+    fn multilinestring_begin(&mut self, _size: usize, idx: usize) -> GeozeroResult<()> {
+        self.member_begin(idx);
+        Ok(())
+    }
+
+    fn polygon_begin(&mut self, tagged: bool, _size: usize, idx: usize) -> GeozeroResult<()> {
+        if tagged {
+            self.member_begin(idx);
+        } else {
+            // A member of a MultiPolygon
+            self.part = idx as u32;
+        }
+        self.ring = 0;
+        self.in_polygon = true;
+        Ok(())
+    }
+
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> GeozeroResult<()> {
+        self.in_polygon = false;
+        Ok(())
+    }
+
+    fn multipolygon_begin(&mut self, _size: usize, idx: usize) -> GeozeroResult<()> {
+        self.member_begin(idx);
+        Ok(())
+    }
+
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> GeozeroResult<()> {
+        self.in_collection = true;
+        Ok(())
+    }
+}
+
+pub(crate) fn get_coordinates(
+    series: &Series,
+    include_z: bool,
+    index_parts: bool,
+) -> Result<DataFrame> {
+    let mut collector = CoordinateCollector {
+        include_z,
+        ..Default::default()
+    };
+
+    let mut row = 0;
+    for chunk in series.chunks() {
+        if chunk_geoarrow_type(&**chunk)? == GeoArrowType::WKB {
+            // Read WKB directly rather than through geo, which would drop z coordinates
+            let storage = storage_array(&**chunk)?;
+            for value in wkb_rows(&*storage)? {
+                collector.start_row(row);
+                if let Some(value) = value {
+                    Wkb(value.to_vec()).process_geom(&mut collector)?;
+                }
+                row += 1;
+            }
+        } else {
+            for geom in geometries_of_array(geometry_array(&**chunk)?) {
+                collector.start_row(row);
+                if let Some(geom) = geom {
+                    geom.process_geom(&mut collector)?;
+                }
+                row += 1;
+            }
+        }
+    }
+
+    let mut columns = vec![Series::new("index", collector.index)];
+    if index_parts {
+        columns.push(Series::new("part", collector.parts));
+        columns.push(Series::new("ring", collector.rings));
+    }
+    columns.push(Series::new("x", collector.x));
+    columns.push(Series::new("y", collector.y));
+    if include_z {
+        columns.push(Series::new("z", collector.z));
+    }
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Coordinates grouped by row, part and ring
+type Rows = BTreeMap<u32, BTreeMap<u32, BTreeMap<u32, Vec<Coord>>>>;
+
+fn u32_column(df: &DataFrame, name: &str) -> Result<Option<Vec<Option<u32>>>> {
+    match df.column(name) {
+        Ok(series) => Ok(Some(
+            series.cast(&DataType::UInt32)?.u32()?.into_iter().collect(),
+        )),
+        Err(_) => Ok(None),
+    }
+}
+
+fn f64_column(df: &DataFrame, name: &str) -> Result<Vec<Option<f64>>> {
+    Ok(df
+        .column(name)?
+        .cast(&DataType::Float64)?
+        .f64()?
+        .into_iter()
+        .collect())
+}
+
+pub(crate) fn from_coordinates(
+    df: &DataFrame,
+    geometry_type: GeoArrowType,
+    source: &Series,
+) -> Result<Series> {
+    let missing = |name: &str| {
+        PolarsError::NotFound(ErrString::from(format!(
+            "Coordinate DataFrame has no {} column",
+            name
+        )))
+    };
+    let index = u32_column(df, "index")?.ok_or_else(|| missing("index"))?;
+    let parts = u32_column(df, "part")?;
+    let rings = u32_column(df, "ring")?;
+    let x = f64_column(df, "x")?;
+    let y = f64_column(df, "y")?;
+
+    let mut rows = Rows::new();
+    for i in 0..df.height() {
+        let (row, x, y) = match (index[i], x[i], y[i]) {
+            (Some(row), Some(x), Some(y)) => (row, x, y),
+            _ => continue,
+        };
+        let part = parts.as_ref().and_then(|parts| parts[i]).unwrap_or(0);
+        let ring = rings.as_ref().and_then(|rings| rings[i]).unwrap_or(0);
+        rows.entry(row)
+            .or_default()
+            .entry(part)
+            .or_default()
+            .entry(ring)
+            .or_default()
+            .push(Coord { x, y });
+    }
+
+    let len = source.len();
+    if let Some(row) = rows.keys().next_back() {
+        if *row as usize >= len {
+            return Err(PolarsError::ComputeError(ErrString::from(format!(
+                "Coordinate index {} is out of bounds for a Series of length {}",
+                row, len
+            )))
+            .into());
+        }
+    }
+
+    // Rows that are null in the source stay null, other rows without coordinates are empty
+    let is_null: Vec<bool> = source
+        .is_null()
+        .into_iter()
+        .map(|is_null| is_null.unwrap_or(false))
+        .collect();
+    let no_parts = BTreeMap::new();
+    let row_parts = |row: u32| {
+        if is_null[row as usize] {
+            None
+        } else {
+            Some(rows.get(&row).unwrap_or(&no_parts))
+        }
+    };
+
+    let array =
+        match geometry_type {
+            GeoArrowType::Point => {
+                let geoms: Vec<Option<Point>> = (0..len as u32)
+                    .map(|row| {
+                        row_parts(row)
+                            .and_then(|parts| all_coords(parts).first().map(|c| Point(*c)))
+                    })
+                    .collect();
+                GeometryArray::Point(geoms.into())
+            }
+            GeoArrowType::LineString => {
+                let geoms: Vec<Option<LineString>> = (0..len as u32)
+                    .map(|row| row_parts(row).map(|parts| LineString::new(all_coords(parts))))
+                    .collect();
+                GeometryArray::LineString(geoms.into())
+            }
+            GeoArrowType::Polygon => {
+                let geoms: Vec<Option<Polygon>> =
+                    (0..len as u32)
+                        .map(|row| {
+                            row_parts(row).map(|parts| {
+                                parts.values().next().map(polygon).unwrap_or_else(|| {
+                                    Polygon::new(LineString::new(vec![]), vec![])
+                                })
+                            })
+                        })
+                        .collect();
+                GeometryArray::Polygon(geoms.into())
+            }
+            GeoArrowType::MultiPoint => {
+                let geoms: Vec<Option<MultiPoint>> = (0..len as u32)
+                    .map(|row| {
+                        row_parts(row).map(|parts| {
+                            MultiPoint::new(all_coords(parts).into_iter().map(Point).collect())
+                        })
+                    })
+                    .collect();
+                GeometryArray::MultiPoint(geoms.into())
+            }
+            GeoArrowType::MultiLineString => {
+                let geoms: Vec<Option<MultiLineString>> = (0..len as u32)
+                    .map(|row| {
+                        row_parts(row).map(|parts| {
+                            MultiLineString::new(
+                                parts
+                                    .values()
+                                    .map(|rings| {
+                                        LineString::new(rings.values().flatten().copied().collect())
+                                    })
+                                    .collect(),
+                            )
+                        })
+                    })
+                    .collect();
+                GeometryArray::MultiLineString(geoms.into())
+            }
+            GeoArrowType::MultiPolygon => {
+                let geoms: Vec<Option<MultiPolygon>> = (0..len as u32)
+                    .map(|row| {
+                        row_parts(row)
+                            .map(|parts| MultiPolygon::new(parts.values().map(polygon).collect()))
+                    })
+                    .collect();
+                GeometryArray::MultiPolygon(geoms.into())
+            }
+            GeoArrowType::WKB => return Err(PolarsError::ComputeError(ErrString::from(
+                "Cannot rebuild geometries from coordinates without a geometry type, WKB is only \
+                an encoding",
+            ))
+            .into()),
+        };
+
+    with_crs_of(geometry_series_from_arrays(vec![array])?, source)
+}
+
+/// All coordinates of a row, in part and ring order
+fn all_coords(parts: &BTreeMap<u32, BTreeMap<u32, Vec<Coord>>>) -> Vec<Coord> {
+    parts
+        .values()
+        .flat_map(|rings| rings.values().flatten().copied())
+        .collect()
+}
+
+fn polygon(rings: &BTreeMap<u32, Vec<Coord>>) -> Polygon {
+    let mut rings = rings.values().map(|coords| LineString::new(coords.clone()));
+    let exterior = rings.next().unwrap_or_else(|| LineString::new(vec![]));
+    Polygon::new(exterior, rings.collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::extension::GeoArrowType;
+    use crate::geoseries::GeoSeries;
+    use crate::util::{from_coordinates, from_geom_vec, geometries};
+    use geo::{line_string, point, polygon, Geometry, LineString, MultiPolygon};
+    use geoarrow::{GeometryArray, GeometryArrayTrait, LineStringArray};
+    use polars::prelude::Series;
+
+    #[test]
+    fn get_coordinates() {
+        let series = from_geom_vec(&[
+            Geometry::Point(point!(x: 0., y: 1.)),
+            Geometry::LineString(line_string![(x: 1., y: 1.), (x: 2., y: 2.)]),
+        ])
+        .unwrap();
+
+        let df = series.get_coordinates(false, false).unwrap();
+        assert_eq!(df.get_column_names(), vec!["index", "x", "y"]);
+        let index: Vec<Option<u32>> = df
+            .column("index")
+            .unwrap()
+            .u32()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(index, vec![Some(0), Some(1), Some(1)]);
+
+        let df = series.get_coordinates(true, true).unwrap();
+        assert_eq!(
+            df.get_column_names(),
+            vec!["index", "part", "ring", "x", "y", "z"]
+        );
+        assert_eq!(df.column("z").unwrap().null_count(), 3);
+    }
+
+    #[test]
+    fn round_trip_multipolygon() {
+        let square = polygon!(
+            exterior: [(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 4.)],
+            interiors: [[(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.)]],
+        );
+        let triangle = polygon![(x: 10., y: 10.), (x: 11., y: 10.), (x: 11., y: 11.)];
+        let geom = Geometry::MultiPolygon(MultiPolygon::new(vec![square, triangle]));
+        let series = from_geom_vec(&[geom.clone()]).unwrap();
+
+        let df = series.get_coordinates(false, true).unwrap();
+        let rings: Vec<Option<u32>> = df
+            .column("ring")
+            .unwrap()
+            .u32()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(rings.iter().filter(|ring| **ring == Some(1)).count(), 4);
+
+        let result = from_coordinates(&df, GeoArrowType::MultiPolygon, &series).unwrap();
+        assert_eq!(geometries(&result).unwrap(), vec![Some(geom)]);
+    }
+
+    #[test]
+    fn round_trip_empty_and_null() {
+        let line = line_string![(x: 0., y: 0.), (x: 1., y: 1.)];
+        let input_array: LineStringArray =
+            vec![Some(line.clone()), Some(LineString::new(vec![])), None].into();
+        let series = Series::try_from((
+            "geometry",
+            GeometryArray::LineString(input_array).into_arrow(),
+        ))
+        .unwrap()
+        .set_crs("EPSG:4326")
+        .unwrap();
+
+        let df = series.get_coordinates(false, false).unwrap();
+        assert_eq!(df.height(), 2);
+
+        let result = from_coordinates(&df, GeoArrowType::LineString, &series).unwrap();
+        assert_eq!(
+            geometries(&result).unwrap(),
+            vec![
+                Some(Geometry::LineString(line)),
+                Some(Geometry::LineString(LineString::new(vec![]))),
+                None
+            ]
+        );
+        assert_eq!(result.crs().unwrap(), Some("EPSG:4326".to_string()));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::error::Result;
+use crate::util::geometries;
+use geo::dimensions::HasDimensions;
+use geo::{CoordsIter, Geometry};
+use polars::prelude::{NewChunkedArray, Series, UInt32Chunked};
+use polars::series::IntoSeries;
+
+pub(crate) fn num_geometries(series: &Series) -> Result<Series> {
+    count(series, |geom| match geom {
+        Geometry::MultiPoint(g) => Some(g.0.len()),
+        Geometry::MultiLineString(g) => Some(g.0.len()),
+        Geometry::MultiPolygon(g) => Some(g.0.len()),
+        Geometry::GeometryCollection(g) => Some(g.0.len()),
+        geom if geom.is_empty() => Some(0),
+        _ => Some(1),
+    })
+}
+
+pub(crate) fn num_points(series: &Series) -> Result<Series> {
+    count(series, |geom| Some(geom.coords_count()))
+}
+
+pub(crate) fn num_interior_rings(series: &Series) -> Result<Series> {
+    count(series, |geom| match geom {
+        Geometry::Polygon(g) => Some(g.interiors().len()),
+        _ => None,
+    })
+}
+
+/// Count something about each geometry of a Series, with nulls for null geometries and where `f`
+/// returns `None`
+fn count<F>(series: &Series, f: F) -> Result<Series>
+where
+    F: Fn(&Geometry<f64>) -> Option<usize>,
+{
+    let counts: Vec<Option<u32>> = geometries(series)?
+        .iter()
+        .map(|maybe_g| maybe_g.as_ref().and_then(&f).map(|n| n as u32))
+        .collect();
+    Ok(UInt32Chunked::from_slice_options("result", &counts).into_series())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoseries::GeoSeries;
+    use crate::util::from_geom_vec;
+    use geo::{line_string, point, polygon, Geometry, MultiPoint};
+    use polars::prelude::Series;
+
+    fn values(series: Series) -> Vec<Option<u32>> {
+        series.u32().unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn counts() {
+        let series = from_geom_vec(&[
+            Geometry::Point(point!(x: 0., y: 0.)),
+            Geometry::MultiPoint(MultiPoint::new(vec![
+                point!(x: 0., y: 0.),
+                point!(x: 1., y: 1.),
+            ])),
+            Geometry::LineString(line_string![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.)]),
+            Geometry::Polygon(polygon!(
+                exterior: [(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.), (x: 0., y: 10.)],
+                interiors: [[(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.)]],
+            )),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            values(series.num_geometries().unwrap()),
+            vec![Some(1), Some(2), Some(1), Some(1)]
+        );
+        assert_eq!(
+            values(series.num_points().unwrap()),
+            vec![Some(1), Some(2), Some(3), Some(9)]
+        );
+        assert_eq!(
+            values(series.num_interior_rings().unwrap()),
+            vec![None, None, None, Some(1)]
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use geo::Coord;
+use spade::{DelaunayTriangulation, Point2, Triangulation};
+
+/// The Delaunay triangulation of distinct coordinates, as counter-clockwise triples of indices
+/// into `coords`
+///
+/// Coordinates are inserted one at a time into a spade triangulation, which locates each one by
+/// walking from the previously inserted coordinate. Returns no triangles if there are fewer than
+/// three coordinates or all are collinear. Coordinates spade can't represent, e.g. NaN, are left
+/// out.
+pub(crate) fn triangulate(coords: &[Coord]) -> Vec<[usize; 3]> {
+    let mut triangulation: DelaunayTriangulation<Point2<f64>> = DelaunayTriangulation::new();
+
+    // The index into `coords` of each vertex of the triangulation, by vertex index
+    let mut indices = Vec::with_capacity(coords.len());
+    for (i, c) in coords.iter().enumerate() {
+        if let Ok(vertex) = triangulation.insert(Point2::new(c.x, c.y)) {
+            // A repeated coordinate returns the existing vertex
+            if vertex.index() == indices.len() {
+                indices.push(i);
+            }
+        }
+    }
+
+    triangulation
+        .inner_faces()
+        .map(|face| face.vertices().map(|vertex| indices[vertex.fix().index()]))
+        .collect()
+}
+
+/// The directed edges of a counter-clockwise triangle
+pub(crate) fn edges(triangle: &[usize; 3]) -> [(usize, usize); 3] {
+    [
+        (triangle[0], triangle[1]),
+        (triangle[1], triangle[2]),
+        (triangle[2], triangle[0]),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::triangulate;
+    use geo::prelude::Area;
+    use geo::{coord, ConvexHull, Coord, MultiPoint, Point};
+
+    #[test]
+    fn triangulate_square() {
+        let coords = [
+            coord! { x: 0., y: 0. },
+            coord! { x: 1., y: 0. },
+            coord! { x: 1., y: 1. },
+            coord! { x: 0., y: 1. },
+            coord! { x: 0.5, y: 0.4 },
+        ];
+        let triangles = triangulate(&coords);
+
+        // Four triangles around the interior point, together covering the square
+        assert_eq!(triangles.len(), 4);
+        assert!(triangles.iter().all(|t| t.contains(&4)));
+        let area: f64 = triangles
+            .iter()
+            .map(|t| {
+                let (a, b, c) = (coords[t[0]], coords[t[1]], coords[t[2]]);
+                ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)) / 2.
+            })
+            .sum();
+        assert!((area - 1.).abs() < 1e-12);
+
+        // Collinear points have no triangulation
+        let line = [
+            coord! { x: 0., y: 0. },
+            coord! { x: 1., y: 1. },
+            coord! { x: 2., y: 2. },
+        ];
+        assert!(triangulate(&line).is_empty());
+    }
+
+    #[test]
+    fn triangulate_many() {
+        // Pseudo-random coordinates from a linear congruential generator
+        let mut state: u64 = 42;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        let coords: Vec<Coord> = (0..20_000)
+            .map(|_| coord! { x: next(), y: next() })
+            .collect();
+        let triangles = triangulate(&coords);
+
+        // The triangles are counter-clockwise and together cover the convex hull
+        let areas: Vec<f64> = triangles
+            .iter()
+            .map(|t| {
+                let (a, b, c) = (coords[t[0]], coords[t[1]], coords[t[2]]);
+                ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)) / 2.
+            })
+            .collect();
+        assert!(areas.iter().all(|area| *area > 0.));
+        let hull = MultiPoint::new(coords.iter().copied().map(Point).collect()).convex_hull();
+        assert!((areas.iter().sum::<f64>() - hull.unsigned_area()).abs() < 1e-9);
+    }
+}
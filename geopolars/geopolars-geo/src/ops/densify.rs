@@ -0,0 +1,285 @@
+use crate::error::Result;
+use geo::algorithm::haversine_distance::HaversineDistance;
+use geo::algorithm::haversine_intermediate::HaversineIntermediate;
+use geo::{
+    Coord, Geometry, GeometryCollection, LineString, MultiLineString, MultiPolygon, Point, Polygon,
+};
+use geoarrow::GeometryArray;
+use polars::error::ErrString;
+use polars::prelude::PolarsError;
+
+/// Pushes the coordinates to insert between the two ends of a segment
+type Interpolate<'a> = &'a dyn Fn(Coord, Coord, &mut Vec<Coord>) -> Result<()>;
+
+/// The most vertices inserted into a single segment. Segments needing more are either of
+/// non-finite length or much longer than the maximum length, which is more likely a mistake than
+/// a reason to run out of memory.
+const MAX_INSERTED_VERTICES: f64 = 1e7;
+
+pub(crate) fn segmentize(array: GeometryArray, max_segment_length: f64) -> Result<GeometryArray> {
+    check_max_length(max_segment_length)?;
+    densify(array, &|start, end, coords| {
+        let length = (end.x - start.x).hypot(end.y - start.y);
+        let segments = segment_count(length, max_segment_length)?;
+        for i in 1..segments {
+            let fraction = i as f64 / segments as f64;
+            coords.push(start + (end - start) * fraction);
+        }
+        Ok(())
+    })
+}
+
+pub(crate) fn geodesic_densify(array: GeometryArray, max_metres: f64) -> Result<GeometryArray> {
+    check_max_length(max_metres)?;
+    densify(array, &|start, end, coords| {
+        let (start, end) = (Point(start), Point(end));
+        segment_count(start.haversine_distance(&end), max_metres)?;
+        let points = start.haversine_intermediate_fill(&end, max_metres, false);
+        coords.extend(points.into_iter().map(|point| point.0));
+        Ok(())
+    })
+}
+
+fn check_max_length(max_length: f64) -> Result<()> {
+    if max_length.is_nan() || max_length <= 0. {
+        return Err(PolarsError::ComputeError(ErrString::from(
+            "The maximum segment length must be positive",
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// The number of parts of at most `max_length` a segment of `length` is split into
+fn segment_count(length: f64, max_length: f64) -> Result<usize> {
+    if !length.is_finite() {
+        return Err(PolarsError::ComputeError(ErrString::from(
+            "Cannot densify a segment of non-finite length",
+        ))
+        .into());
+    }
+    let count = (length / max_length).ceil();
+    if count > MAX_INSERTED_VERTICES {
+        return Err(PolarsError::ComputeError(ErrString::from(format!(
+            "Densifying a segment of length {} to parts of at most {} would insert more than {} \
+             vertices",
+            length, max_length, MAX_INSERTED_VERTICES
+        )))
+        .into());
+    }
+    Ok(count as usize)
+}
+
+fn densify(array: GeometryArray, interpolate: Interpolate) -> Result<GeometryArray> {
+    match array {
+        GeometryArray::WKB(arr) => {
+            let output_geoms = arr
+                .iter_geo()
+                .map(|maybe_g| {
+                    maybe_g
+                        .map(|geom| densify_geometry(geom, interpolate))
+                        .transpose()
+                })
+                .collect::<Result<Vec<Option<Geometry>>>>()?;
+
+            Ok(GeometryArray::WKB(output_geoms.into()))
+        }
+        GeometryArray::Point(arr) => Ok(GeometryArray::Point(arr)),
+        GeometryArray::MultiPoint(arr) => Ok(GeometryArray::MultiPoint(arr)),
+        GeometryArray::LineString(arr) => {
+            let output_geoms = arr
+                .iter_geo()
+                .map(|maybe_g| {
+                    maybe_g
+                        .map(|geom| densify_line_string(&geom, interpolate))
+                        .transpose()
+                })
+                .collect::<Result<Vec<Option<LineString>>>>()?;
+
+            Ok(GeometryArray::LineString(output_geoms.into()))
+        }
+        GeometryArray::MultiLineString(arr) => {
+            let output_geoms = arr
+                .iter_geo()
+                .map(|maybe_g| {
+                    maybe_g
+                        .map(|geom| densify_multi_line_string(&geom, interpolate))
+                        .transpose()
+                })
+                .collect::<Result<Vec<Option<MultiLineString>>>>()?;
+
+            Ok(GeometryArray::MultiLineString(output_geoms.into()))
+        }
+        GeometryArray::Polygon(arr) => {
+            let output_geoms = arr
+                .iter_geo()
+                .map(|maybe_g| {
+                    maybe_g
+                        .map(|geom| densify_polygon(&geom, interpolate))
+                        .transpose()
+                })
+                .collect::<Result<Vec<Option<Polygon>>>>()?;
+
+            Ok(GeometryArray::Polygon(output_geoms.into()))
+        }
+        GeometryArray::MultiPolygon(arr) => {
+            let output_geoms = arr
+                .iter_geo()
+                .map(|maybe_g| {
+                    maybe_g
+                        .map(|geom| densify_multi_polygon(&geom, interpolate))
+                        .transpose()
+                })
+                .collect::<Result<Vec<Option<MultiPolygon>>>>()?;
+
+            Ok(GeometryArray::MultiPolygon(output_geoms.into()))
+        }
+    }
+}
+
+fn densify_geometry(geom: Geometry, interpolate: Interpolate) -> Result<Geometry> {
+    Ok(match geom {
+        Geometry::Point(g) => Geometry::Point(g),
+        Geometry::MultiPoint(g) => Geometry::MultiPoint(g),
+        Geometry::Line(g) => Geometry::LineString(densify_line_string(
+            &LineString::new(vec![g.start, g.end]),
+            interpolate,
+        )?),
+        Geometry::LineString(g) => Geometry::LineString(densify_line_string(&g, interpolate)?),
+        Geometry::MultiLineString(g) => {
+            Geometry::MultiLineString(densify_multi_line_string(&g, interpolate)?)
+        }
+        Geometry::Polygon(g) => Geometry::Polygon(densify_polygon(&g, interpolate)?),
+        Geometry::MultiPolygon(g) => {
+            Geometry::MultiPolygon(densify_multi_polygon(&g, interpolate)?)
+        }
+        Geometry::Rect(g) => Geometry::Polygon(densify_polygon(&g.to_polygon(), interpolate)?),
+        Geometry::Triangle(g) => Geometry::Polygon(densify_polygon(&g.to_polygon(), interpolate)?),
+        Geometry::GeometryCollection(g) => Geometry::GeometryCollection(GeometryCollection(
+            g.0.into_iter()
+                .map(|geom| densify_geometry(geom, interpolate))
+                .collect::<Result<_>>()?,
+        )),
+    })
+}
+
+fn densify_line_string(line: &LineString, interpolate: Interpolate) -> Result<LineString> {
+    let mut coords = Vec::with_capacity(line.0.len());
+    for segment in line.lines() {
+        coords.push(segment.start);
+        interpolate(segment.start, segment.end, &mut coords)?;
+    }
+    if let Some(last) = line.0.last() {
+        coords.push(*last);
+    }
+    Ok(LineString::new(coords))
+}
+
+fn densify_multi_line_string(
+    lines: &MultiLineString,
+    interpolate: Interpolate,
+) -> Result<MultiLineString> {
+    Ok(MultiLineString::new(
+        lines
+            .0
+            .iter()
+            .map(|line| densify_line_string(line, interpolate))
+            .collect::<Result<_>>()?,
+    ))
+}
+
+fn densify_polygon(polygon: &Polygon, interpolate: Interpolate) -> Result<Polygon> {
+    Ok(Polygon::new(
+        densify_line_string(polygon.exterior(), interpolate)?,
+        polygon
+            .interiors()
+            .iter()
+            .map(|ring| densify_line_string(ring, interpolate))
+            .collect::<Result<_>>()?,
+    ))
+}
+
+fn densify_multi_polygon(
+    polygons: &MultiPolygon,
+    interpolate: Interpolate,
+) -> Result<MultiPolygon> {
+    Ok(MultiPolygon::new(
+        polygons
+            .0
+            .iter()
+            .map(|polygon| densify_polygon(polygon, interpolate))
+            .collect::<Result<_>>()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{geodesic_densify, segmentize};
+    use geo::algorithm::haversine_distance::HaversineDistance;
+    use geo::{line_string, polygon, Geometry, LineString, Point};
+    use geoarrow::{GeometryArray, GeometryArrayTrait, LineStringArray, PolygonArray};
+
+    #[test]
+    fn segmentize_line_and_polygon() {
+        let input_array: LineStringArray =
+            vec![line_string![(x: 0., y: 0.), (x: 10., y: 0.)]].into();
+        let result_array = segmentize(GeometryArray::LineString(input_array), 3.).unwrap();
+        let expected = line_string![
+            (x: 0., y: 0.),
+            (x: 2.5, y: 0.),
+            (x: 5., y: 0.),
+            (x: 7.5, y: 0.),
+            (x: 10., y: 0.),
+        ];
+        assert_eq!(
+            Geometry::LineString(expected),
+            result_array.get_as_geo(0).unwrap()
+        );
+
+        let input_array: PolygonArray = vec![polygon![
+            (x: 0., y: 0.),
+            (x: 2., y: 0.),
+            (x: 2., y: 2.),
+            (x: 0., y: 2.),
+        ]]
+        .into();
+        let result_array = segmentize(GeometryArray::Polygon(input_array), 1.).unwrap();
+        match result_array.get_as_geo(0).unwrap() {
+            Geometry::Polygon(p) => assert_eq!(p.exterior().0.len(), 9),
+            _ => panic!("Expected a polygon"),
+        }
+
+        let input_array: LineStringArray = vec![LineString::new(vec![])].into();
+        assert!(segmentize(GeometryArray::LineString(input_array), 0.).is_err());
+
+        // Infinite segments, or ones needing too many vertices, are errors instead of hanging
+        for line in [
+            line_string![(x: 0., y: 0.), (x: f64::INFINITY, y: 0.)],
+            line_string![(x: 0., y: 0.), (x: 1e12, y: 0.)],
+        ] {
+            let input_array: LineStringArray = vec![line].into();
+            assert!(segmentize(GeometryArray::LineString(input_array), 1.).is_err());
+        }
+    }
+
+    #[test]
+    fn geodesic_densify_line() {
+        let input_array: LineStringArray =
+            vec![line_string![(x: 0., y: 0.), (x: 10., y: 10.)]].into();
+        let result_array =
+            geodesic_densify(GeometryArray::LineString(input_array), 200_000.).unwrap();
+
+        let line = match result_array.get_as_geo(0).unwrap() {
+            Geometry::LineString(line) => line,
+            _ => panic!("Expected a line string"),
+        };
+        // About 1,569 km long, so split into 8 segments of at most 200 km
+        assert_eq!(line.0.len(), 9);
+        assert_eq!(line.0.first(), Some(&(0., 0.).into()));
+        assert_eq!(line.0.last(), Some(&(10., 10.).into()));
+        for segment in line.lines() {
+            let length = Point(segment.start).haversine_distance(&Point(segment.end));
+            assert!(length <= 200_000.);
+        }
+    }
+}
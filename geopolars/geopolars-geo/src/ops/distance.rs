@@ -1,9 +1,12 @@
 use crate::error::Result;
-use crate::util::iter_geom;
+use crate::ops::noding::linear_components;
+use crate::util::{geometries, iter_geom};
 use geo::algorithm::EuclideanDistance;
-use geo::Geometry;
+use geo::{Coord, CoordsIter, Geometry, Line};
+use polars::error::ErrString;
 use polars::export::arrow::array::{Array, MutablePrimitiveArray, PrimitiveArray};
-use polars::prelude::Series;
+use polars::prelude::{Float64Chunked, NewChunkedArray, PolarsError, Series};
+use polars::series::IntoSeries;
 
 pub(crate) fn euclidean_distance(series: &Series, other: &Series) -> Result<Series> {
     euclidean_distance_wkb(series, other)
@@ -61,11 +64,167 @@ fn euclidean_distance_wkb(series: &Series, other: &Series) -> Result<Series> {
     Ok(series)
 }
 
+pub(crate) fn hausdorff_distance(
+    series: &Series,
+    other: &Series,
+    densify: Option<f64>,
+) -> Result<Series> {
+    if let Some(fraction) = densify {
+        if !(fraction > 0. && fraction <= 1.) {
+            return Err(PolarsError::ComputeError(ErrString::from(
+                "The densify fraction must be in the range (0, 1]",
+            ))
+            .into());
+        }
+    }
+    pairwise("hausdorff_distance", series, other, |g1, g2| {
+        discrete_hausdorff(g1, g2, densify)
+    })
+}
+
+pub(crate) fn frechet_distance(series: &Series, other: &Series) -> Result<Series> {
+    pairwise("frechet_distance", series, other, discrete_frechet)
+}
+
+/// Apply `f` to the aligned geometries of two series, or to every geometry of `series` and the
+/// geometry of `other` if it has a single row. Nulls, and rows where `f` returns `None`, are null.
+fn pairwise(
+    name: &str,
+    series: &Series,
+    other: &Series,
+    f: impl Fn(&Geometry, &Geometry) -> Option<f64>,
+) -> Result<Series> {
+    let left = geometries(series)?;
+    let right = geometries(other)?;
+    let apply = |g1: &Option<Geometry>, g2: &Option<Geometry>| match (g1, g2) {
+        (Some(g1), Some(g2)) => f(g1, g2),
+        _ => None,
+    };
+
+    let result: Vec<Option<f64>> = match right.len() {
+        // A single geometry, e.g. from a literal, is compared against every row
+        1 => left.iter().map(|g1| apply(g1, &right[0])).collect(),
+        len if len == left.len() => left
+            .iter()
+            .zip(right.iter())
+            .map(|(g1, g2)| apply(g1, g2))
+            .collect(),
+        len => {
+            return Err(PolarsError::ShapeMisMatch(ErrString::from(format!(
+                "Cannot compare a GeoSeries of length {} with one of length {}",
+                left.len(),
+                len
+            )))
+            .into())
+        }
+    };
+
+    Ok(Float64Chunked::from_slice_options(name, &result).into_series())
+}
+
+/// The discrete Hausdorff distance between two geometries, like GEOS
+/// `DiscreteHausdorffDistance`: the largest distance from a vertex of either geometry to the
+/// other geometry, where segments may be densified into `1 / densify` equal parts to sample
+/// more points along them. Empty geometries have no distance.
+fn discrete_hausdorff(g1: &Geometry, g2: &Geometry, densify: Option<f64>) -> Option<f64> {
+    let (points1, segments1) = (sample_points(g1, densify), segments(g1));
+    let (points2, segments2) = (sample_points(g2, densify), segments(g2));
+    if points1.is_empty() || points2.is_empty() {
+        return None;
+    }
+
+    let directed = |points: &[Coord], targets: &[Coord], segments: &[Line]| {
+        points
+            .iter()
+            .map(|point| {
+                let to_vertex = targets.iter().map(|target| distance(*point, *target));
+                let to_segment = segments
+                    .iter()
+                    .map(|segment| segment_distance(*point, *segment));
+                to_vertex.chain(to_segment).fold(f64::INFINITY, f64::min)
+            })
+            .fold(0., f64::max)
+    };
+    let (vertices1, vertices2) = (vertices(g1), vertices(g2));
+    Some(directed(&points1, &vertices2, &segments2).max(directed(&points2, &vertices1, &segments1)))
+}
+
+/// The discrete Fréchet distance between the vertex sequences of two geometries, like GEOS
+/// `DiscreteFrechetDistance`. Empty geometries have no distance.
+fn discrete_frechet(g1: &Geometry, g2: &Geometry) -> Option<f64> {
+    let (coords1, coords2) = (vertices(g1), vertices(g2));
+    if coords1.is_empty() || coords2.is_empty() {
+        return None;
+    }
+
+    // The coupling distance up to each vertex of `coords2`, for the previous and current vertex
+    // of `coords1`
+    let mut previous: Vec<f64> = Vec::with_capacity(coords2.len());
+    for (j, c2) in coords2.iter().enumerate() {
+        let d = distance(coords1[0], *c2);
+        previous.push(if j == 0 { d } else { d.max(previous[j - 1]) });
+    }
+    let mut current = vec![0.; coords2.len()];
+    for c1 in &coords1[1..] {
+        for (j, c2) in coords2.iter().enumerate() {
+            let d = distance(*c1, *c2);
+            let reachable = if j == 0 {
+                previous[0]
+            } else {
+                previous[j].min(previous[j - 1]).min(current[j - 1])
+            };
+            current[j] = d.max(reachable);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous.last().copied()
+}
+
+fn vertices(geom: &Geometry) -> Vec<Coord> {
+    geom.coords_iter().collect()
+}
+
+fn segments(geom: &Geometry) -> Vec<Line> {
+    let mut lines = vec![];
+    linear_components(geom, &mut lines);
+    lines.iter().flat_map(|line| line.lines()).collect()
+}
+
+/// The vertices of a geometry, and points splitting each of its segments into `1 / densify`
+/// equal parts
+fn sample_points(geom: &Geometry, densify: Option<f64>) -> Vec<Coord> {
+    let mut points = vertices(geom);
+    if let Some(fraction) = densify {
+        let parts = (1. / fraction).round().max(1.) as usize;
+        for segment in segments(geom) {
+            let delta = segment.delta();
+            points.extend((1..parts).map(|k| segment.start + delta * (k as f64 / parts as f64)));
+        }
+    }
+    points
+}
+
+fn distance(a: Coord, b: Coord) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+fn segment_distance(c: Coord, segment: Line) -> f64 {
+    let delta = segment.delta();
+    let squared_length = delta.x * delta.x + delta.y * delta.y;
+    if squared_length == 0. {
+        return distance(c, segment.start);
+    }
+    let offset = c - segment.start;
+    let fraction = ((offset.x * delta.x + offset.y * delta.y) / squared_length).clamp(0., 1.);
+    distance(c, segment.start + delta * fraction)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::geoseries::GeoSeries;
     use crate::util::from_geom_vec;
-    use geo::{Geometry, LineString, Point};
+    use geo::{line_string, Geometry, LineString, Point};
+    use polars::prelude::Series;
 
     #[test]
     fn euclidean_distance() {
@@ -96,4 +255,47 @@ mod tests {
             assert_eq!(d1, d2, "Distances differ, should be the same");
         }
     }
+
+    #[test]
+    fn hausdorff_and_frechet() {
+        let route = from_geom_vec(&[Geometry::LineString(line_string![
+            (x: 0., y: 0.),
+            (x: 10., y: 0.),
+        ])])
+        .unwrap();
+        let traces = from_geom_vec(&[
+            Geometry::LineString(line_string![(x: 0., y: 1.), (x: 5., y: 2.), (x: 10., y: 1.)]),
+            // The same path, walked backwards
+            Geometry::LineString(line_string![(x: 10., y: 0.), (x: 0., y: 0.)]),
+        ])
+        .unwrap();
+
+        let values =
+            |series: Series| -> Vec<f64> { series.f64().unwrap().into_no_null_iter().collect() };
+        let hausdorff = values(traces.hausdorff_distance(&route, None).unwrap());
+        assert_eq!(hausdorff, vec![2., 0.]);
+        let frechet = values(traces.frechet_distance(&route).unwrap());
+        assert_eq!(frechet, vec![5_f64.hypot(2.), 10.]);
+
+        // Densifying samples points along the segments, which may be farther from the other
+        // geometry than the vertices
+        let a = from_geom_vec(&[Geometry::LineString(line_string![
+            (x: 130., y: 0.),
+            (x: 0., y: 0.),
+            (x: 0., y: 150.),
+        ])])
+        .unwrap();
+        let b = from_geom_vec(&[Geometry::LineString(line_string![
+            (x: 10., y: 10.),
+            (x: 10., y: 150.),
+            (x: 130., y: 10.),
+        ])])
+        .unwrap();
+        let hausdorff = values(a.hausdorff_distance(&b, None).unwrap());
+        assert!((hausdorff[0] - 200_f64.sqrt()).abs() < 1e-12);
+        let hausdorff = values(a.hausdorff_distance(&b, Some(0.5)).unwrap());
+        assert_eq!(hausdorff, vec![70.]);
+
+        assert!(a.hausdorff_distance(&b, Some(2.)).is_err());
+    }
 }
@@ -0,0 +1,333 @@
+use crate::error::Result;
+use crate::util::geometries_of_array;
+use geo::algorithm::bounding_rect::BoundingRect;
+use geo::algorithm::centroid::Centroid;
+use geo::algorithm::{EuclideanDistance, Intersects};
+use geo::{Coord, Geometry, LineString, MultiLineString, MultiPoint, Point, Polygon};
+use geoarrow::{GeometryArray, GeometryArrayTrait, MutablePointArray, PointArray};
+use polars::error::ErrString;
+use polars::prelude::PolarsError;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::iter::once;
+
+pub(crate) fn representative_point(array: GeometryArray) -> Result<PointArray> {
+    let mut output_array = MutablePointArray::with_capacity(array.len());
+    for maybe_g in geometries_of_array(array) {
+        output_array.push_geo(maybe_g.and_then(|g| {
+            let parts = Parts::of(g);
+            polygons_interior_point(&parts.polygons)
+                .or_else(|| lines_interior_point(&parts.lines))
+                .or_else(|| points_interior_point(&parts.points))
+        }));
+    }
+    Ok(output_array.into())
+}
+
+pub(crate) fn pole_of_inaccessibility(array: GeometryArray, tolerance: f64) -> Result<PointArray> {
+    if tolerance.is_nan() || tolerance <= 0. {
+        return Err(PolarsError::ComputeError(ErrString::from(
+            "The tolerance of pole_of_inaccessibility must be positive",
+        ))
+        .into());
+    }
+
+    let mut output_array = MutablePointArray::with_capacity(array.len());
+    for maybe_g in geometries_of_array(array) {
+        output_array.push_geo(maybe_g.and_then(|g| {
+            Parts::of(g)
+                .polygons
+                .iter()
+                .filter_map(|polygon| polylabel(polygon, tolerance))
+                .max_by(|a, b| cmp_f64(a.d, b.d))
+                .map(|cell| Point::new(cell.x, cell.y))
+        }));
+    }
+    Ok(output_array.into())
+}
+
+/// The points, lines and polygons a geometry is made of
+#[derive(Default)]
+struct Parts {
+    points: Vec<Point>,
+    lines: Vec<LineString>,
+    polygons: Vec<Polygon>,
+}
+
+impl Parts {
+    fn of(geom: Geometry) -> Self {
+        let mut parts = Self::default();
+        parts.add(geom);
+        parts
+    }
+
+    fn add(&mut self, geom: Geometry) {
+        match geom {
+            Geometry::Point(g) => self.points.push(g),
+            Geometry::MultiPoint(g) => self.points.extend(g.0),
+            Geometry::Line(g) => self.lines.push(LineString::new(vec![g.start, g.end])),
+            Geometry::LineString(g) => self.lines.push(g),
+            Geometry::MultiLineString(g) => self.lines.extend(g.0),
+            Geometry::Polygon(g) => self.polygons.push(g),
+            Geometry::MultiPolygon(g) => self.polygons.extend(g.0),
+            Geometry::Rect(g) => self.polygons.push(g.to_polygon()),
+            Geometry::Triangle(g) => self.polygons.push(g.to_polygon()),
+            Geometry::GeometryCollection(g) => g.0.into_iter().for_each(|geom| self.add(geom)),
+        }
+    }
+}
+
+fn cmp_f64(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+/// The midpoint of the widest interval in which a horizontal scan line crosses the interior of
+/// the polygons, falling back to their rings if they have no area
+fn polygons_interior_point(polygons: &[Polygon]) -> Option<Point> {
+    polygons
+        .iter()
+        .filter_map(scanline_interior_point)
+        .max_by(|a, b| cmp_f64(a.1, b.1))
+        .map(|(point, _)| point)
+        .or_else(|| {
+            let rings: Vec<LineString> = polygons
+                .iter()
+                .flat_map(|polygon| once(polygon.exterior()).chain(polygon.interiors()))
+                .cloned()
+                .collect();
+            lines_interior_point(&rings)
+        })
+}
+
+/// The midpoint of the widest interior interval of a polygon on a scan line, and its width
+pub(crate) fn scanline_interior_point(polygon: &Polygon) -> Option<(Point, f64)> {
+    let rect = polygon.bounding_rect()?;
+    let rings = || once(polygon.exterior()).chain(polygon.interiors());
+
+    // Scan halfway between the vertices nearest to the middle of the polygon, so that the scan
+    // line doesn't pass through any vertex
+    let center = rect.center().y;
+    let (mut below, mut above) = (rect.min().y, rect.max().y);
+    for coord in rings().flat_map(|ring| ring.0.iter()) {
+        if coord.y < center && coord.y > below {
+            below = coord.y;
+        }
+        if coord.y > center && coord.y < above {
+            above = coord.y;
+        }
+    }
+    let y = (below + above) / 2.;
+
+    let mut crossings: Vec<f64> = rings()
+        .flat_map(|ring| ring.lines())
+        .filter(|line| (line.start.y > y) != (line.end.y > y))
+        .map(|line| line.start.x + (y - line.start.y) * (line.end.x - line.start.x) / line.dy())
+        .collect();
+    crossings.sort_by(|a, b| cmp_f64(*a, *b));
+
+    crossings
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .max_by(|a, b| cmp_f64(a.1 - a.0, b.1 - b.0))
+        .filter(|(min, max)| max > min)
+        .map(|(min, max)| (Point::new((min + max) / 2., y), max - min))
+}
+
+/// The interior vertex of the lines nearest to their centroid, or the nearest endpoint if the
+/// lines have no interior vertices
+fn lines_interior_point(lines: &[LineString]) -> Option<Point> {
+    let centroid = MultiLineString::new(lines.to_vec()).centroid()?;
+    let interior_vertices = lines
+        .iter()
+        .flat_map(|line| line.0.iter().skip(1).take(line.0.len().saturating_sub(2)));
+    nearest(interior_vertices, centroid).or_else(|| {
+        let endpoints = lines
+            .iter()
+            .flat_map(|line| line.0.first().into_iter().chain(line.0.last()));
+        nearest(endpoints, centroid)
+    })
+}
+
+/// The point nearest to the centroid of the points
+fn points_interior_point(points: &[Point]) -> Option<Point> {
+    let centroid = MultiPoint::new(points.to_vec()).centroid()?;
+    nearest(points.iter().map(|point| &point.0), centroid)
+}
+
+fn nearest<'a>(coords: impl Iterator<Item = &'a Coord>, target: Point) -> Option<Point> {
+    coords
+        .map(|coord| Point(*coord))
+        .min_by(|a, b| cmp_f64(a.euclidean_distance(&target), b.euclidean_distance(&target)))
+}
+
+/// A square cell of the polylabel search, centered on `(x, y)` with half-size `h`
+struct Cell {
+    x: f64,
+    y: f64,
+    h: f64,
+    /// Signed distance from the center to the polygon boundary, positive inside the polygon
+    d: f64,
+    /// Upper bound of the distance to the boundary of any point within the cell
+    max: f64,
+}
+
+impl Cell {
+    fn new(x: f64, y: f64, h: f64, polygon: &Polygon) -> Self {
+        let point = Point::new(x, y);
+        let distance = once(polygon.exterior())
+            .chain(polygon.interiors())
+            .map(|ring| point.euclidean_distance(ring))
+            .fold(f64::INFINITY, f64::min);
+        let d = if polygon.intersects(&point) {
+            distance
+        } else {
+            -distance
+        };
+        Self {
+            x,
+            y,
+            h,
+            d,
+            max: d + h * std::f64::consts::SQRT_2,
+        }
+    }
+}
+
+impl PartialEq for Cell {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Cell {}
+
+impl PartialOrd for Cell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_f64(self.max, other.max)
+    }
+}
+
+/// The pole of inaccessibility of a polygon, the interior point farthest from its boundary,
+/// found to within `tolerance` with the polylabel algorithm
+/// (<https://github.com/mapbox/polylabel>)
+fn polylabel(polygon: &Polygon, tolerance: f64) -> Option<Cell> {
+    let rect = polygon.bounding_rect()?;
+    let cell_size = rect.width().min(rect.height());
+    if cell_size == 0. {
+        return Some(Cell::new(rect.min().x, rect.min().y, 0., polygon));
+    }
+
+    // Cover the polygon with square cells, and search them from the most promising one,
+    // splitting each cell that may contain a better point than the best one so far
+    let h = cell_size / 2.;
+    let mut queue = BinaryHeap::new();
+    let mut x = rect.min().x;
+    while x < rect.max().x {
+        let mut y = rect.min().y;
+        while y < rect.max().y {
+            queue.push(Cell::new(x + h, y + h, h, polygon));
+            y += cell_size;
+        }
+        x += cell_size;
+    }
+
+    let center = rect.center();
+    let mut best = Cell::new(center.x, center.y, 0., polygon);
+    if let Some(centroid) = polygon.centroid() {
+        let centroid_cell = Cell::new(centroid.x(), centroid.y(), 0., polygon);
+        if centroid_cell.d > best.d {
+            best = centroid_cell;
+        }
+    }
+
+    while let Some(cell) = queue.pop() {
+        if cell.d > best.d {
+            best = Cell::new(cell.x, cell.y, 0., polygon);
+        }
+        if cell.max - best.d <= tolerance {
+            continue;
+        }
+
+        let h = cell.h / 2.;
+        queue.push(Cell::new(cell.x - h, cell.y - h, h, polygon));
+        queue.push(Cell::new(cell.x + h, cell.y - h, h, polygon));
+        queue.push(Cell::new(cell.x - h, cell.y + h, h, polygon));
+        queue.push(Cell::new(cell.x + h, cell.y + h, h, polygon));
+    }
+
+    Some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pole_of_inaccessibility, representative_point};
+    use crate::util::geometries_of_array;
+    use geo::algorithm::Intersects;
+    use geo::{line_string, point, polygon, Geometry, Polygon};
+    use geoarrow::{GeometryArray, WKBArray};
+
+    fn u_shape() -> Polygon {
+        // A U whose centroid lies in the gap between its arms
+        polygon![
+            (x: 0., y: 0.),
+            (x: 10., y: 0.),
+            (x: 10., y: 10.),
+            (x: 8., y: 10.),
+            (x: 8., y: 2.),
+            (x: 2., y: 2.),
+            (x: 2., y: 10.),
+            (x: 0., y: 10.),
+        ]
+    }
+
+    #[test]
+    fn representative_point_inside() {
+        let input: WKBArray = vec![
+            Some(Geometry::Polygon(u_shape())),
+            Some(Geometry::LineString(
+                line_string![(x: 0., y: 0.), (x: 1., y: 1.), (x: 2., y: 0.)],
+            )),
+            Some(Geometry::Point(point!(x: 3., y: 4.))),
+            None,
+        ]
+        .into();
+        let result = representative_point(GeometryArray::WKB(input)).unwrap();
+        let result = geometries_of_array(GeometryArray::Point(result));
+
+        match &result[0] {
+            Some(Geometry::Point(p)) => assert!(u_shape().intersects(p)),
+            _ => panic!("Expected a point"),
+        }
+        assert_eq!(result[1], Some(Geometry::Point(point!(x: 1., y: 1.))));
+        assert_eq!(result[2], Some(Geometry::Point(point!(x: 3., y: 4.))));
+        assert_eq!(result[3], None);
+    }
+
+    #[test]
+    fn pole_of_inaccessibility_square() {
+        let square = polygon![
+            (x: 0., y: 0.),
+            (x: 4., y: 0.),
+            (x: 4., y: 4.),
+            (x: 0., y: 4.),
+        ];
+        let input: WKBArray = vec![Some(Geometry::Polygon(square)), Some(u_shape().into())].into();
+        let result = pole_of_inaccessibility(GeometryArray::WKB(input), 0.01).unwrap();
+        let result = geometries_of_array(GeometryArray::Point(result));
+
+        assert_eq!(result[0], Some(Geometry::Point(point!(x: 2., y: 2.))));
+        match &result[1] {
+            Some(Geometry::Point(p)) => assert!(u_shape().intersects(p)),
+            _ => panic!("Expected a point"),
+        }
+
+        let empty: WKBArray = Vec::<Option<Geometry>>::new().into();
+        assert!(pole_of_inaccessibility(GeometryArray::WKB(empty), 0.).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+use crate::error::Result;
+use crate::util::geometries;
+use geo::algorithm::Intersects;
+use polars::error::ErrString;
+use polars::prelude::{BooleanChunked, NewChunkedArray, PolarsError, Series};
+use polars::series::IntoSeries;
+
+pub(crate) fn intersects(series: &Series, other: &Series) -> Result<Series> {
+    let left = geometries(series)?;
+    let right = geometries(other)?;
+
+    let result: Vec<Option<bool>> = match right.len() {
+        // A single geometry, e.g. from a literal, is compared against every row
+        1 => left
+            .iter()
+            .map(|g1| match (g1, &right[0]) {
+                (Some(g1), Some(g2)) => Some(g1.intersects(g2)),
+                _ => None,
+            })
+            .collect(),
+        len if len == left.len() => left
+            .iter()
+            .zip(right.iter())
+            .map(|(g1, g2)| match (g1, g2) {
+                (Some(g1), Some(g2)) => Some(g1.intersects(g2)),
+                _ => None,
+            })
+            .collect(),
+        len => {
+            return Err(PolarsError::ShapeMisMatch(ErrString::from(format!(
+                "Cannot compare a GeoSeries of length {} with one of length {}",
+                left.len(),
+                len
+            )))
+            .into())
+        }
+    };
+
+    Ok(BooleanChunked::from_slice_options("intersects", &result).into_series())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoseries::GeoSeries;
+    use crate::util::from_geom_vec;
+    use geo::{line_string, point, polygon, Geometry};
+
+    #[test]
+    fn intersects() {
+        let series = from_geom_vec(&[
+            Geometry::Point(point!(x: 0.5, y: 0.5)),
+            Geometry::Point(point!(x: 2., y: 2.)),
+            Geometry::LineString(line_string![(x: -1., y: 0.5), (x: 0.5, y: 0.5)]),
+        ])
+        .unwrap();
+        let square = from_geom_vec(&[Geometry::Polygon(polygon![
+            (x: 0., y: 0.),
+            (x: 1., y: 0.),
+            (x: 1., y: 1.),
+            (x: 0., y: 1.),
+        ])])
+        .unwrap();
+
+        let result = series.intersects(&square).unwrap();
+        let result: Vec<Option<bool>> = result.bool().unwrap().into_iter().collect();
+        assert_eq!(result, vec![Some(true), Some(false), Some(true)]);
+
+        assert!(series.intersects(&series.slice(0, 2)).is_err());
+    }
+}
@@ -11,6 +11,7 @@ use polars::export::arrow::bitmap::Bitmap;
 use polars::export::arrow::datatypes::DataType as ArrowDataType;
 use polars::prelude::PolarsError;
 
+#[derive(Debug, Clone, Copy)]
 pub enum GeodesicLengthMethod {
     Haversine,
     Geodesic,
@@ -0,0 +1,387 @@
+use crate::crs::with_crs_of;
+use crate::error::Result;
+use crate::extension::GeoArrowType;
+use crate::ops::length::GeodesicLengthMethod;
+use crate::util::{
+    geometries, geometries_of_array, geometry_array, geometry_array_from_geometries,
+    geometry_series_from_arrays,
+};
+use geo::algorithm::geodesic_distance::GeodesicDistance;
+use geo::algorithm::haversine_distance::HaversineDistance;
+use geo::algorithm::haversine_intermediate::HaversineIntermediate;
+use geo::algorithm::vincenty_distance::VincentyDistance;
+use geo::{Coord, Geometry, LineString, MultiLineString, Point};
+use polars::error::ErrString;
+use polars::prelude::{Float64Chunked, NewChunkedArray, PolarsError, Series};
+use polars::series::IntoSeries;
+
+/// How distances along lines are measured
+#[derive(Debug, Clone, Copy)]
+pub enum Measure {
+    /// Planar distances in the units of the CRS, as in `euclidean_length`
+    Euclidean,
+    /// Distances in metres on the WGS 84 ellipsoid or sphere, as in `geodesic_length`. Positions
+    /// within a segment are interpolated along the great circle.
+    Geodesic(GeodesicLengthMethod),
+}
+
+impl Measure {
+    fn length(&self, start: Coord, end: Coord) -> f64 {
+        let (start, end) = (Point(start), Point(end));
+        match self {
+            Measure::Euclidean => (end.x() - start.x()).hypot(end.y() - start.y()),
+            Measure::Geodesic(GeodesicLengthMethod::Haversine) => start.haversine_distance(&end),
+            Measure::Geodesic(GeodesicLengthMethod::Geodesic) => start.geodesic_distance(&end),
+            // Vincenty's formulae fail to converge for nearly antipodal points
+            Measure::Geodesic(GeodesicLengthMethod::Vincenty) => start
+                .vincenty_distance(&end)
+                .unwrap_or_else(|_| start.geodesic_distance(&end)),
+        }
+    }
+
+    fn interpolate(&self, start: Coord, end: Coord, fraction: f64) -> Coord {
+        match self {
+            Measure::Euclidean => start + (end - start) * fraction,
+            Measure::Geodesic(_) => Point(start).haversine_intermediate(&Point(end), fraction).0,
+        }
+    }
+}
+
+pub(crate) fn line_interpolate_point(
+    series: &Series,
+    distance: f64,
+    normalized: bool,
+    measure: Measure,
+) -> Result<Series> {
+    let output_arrays = series
+        .chunks()
+        .iter()
+        .map(|chunk| {
+            let output_geoms = geometries_of_array(geometry_array(&**chunk)?)
+                .into_iter()
+                .map(|maybe_g| {
+                    let parts = maybe_g.as_ref().and_then(line_parts)?;
+                    let total = total_length(&parts, measure);
+                    let distance = resolve_distance(distance, normalized, total);
+                    interpolate_point(&parts, distance, measure).map(Geometry::Point)
+                })
+                .collect();
+            geometry_array_from_geometries(output_geoms, GeoArrowType::Point)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    with_crs_of(geometry_series_from_arrays(output_arrays)?, series)
+}
+
+pub(crate) fn line_locate_point(
+    series: &Series,
+    other: &Series,
+    normalized: bool,
+    measure: Measure,
+) -> Result<Series> {
+    let lines = geometries(series)?;
+    let points = geometries(other)?;
+
+    let locate = |line: &Option<Geometry>, point: &Option<Geometry>| {
+        let parts = line.as_ref().and_then(line_parts)?;
+        let point = match point {
+            Some(Geometry::Point(point)) => point.0,
+            _ => return None,
+        };
+        let distance = locate_point(&parts, point, measure)?;
+        if normalized {
+            let total = total_length(&parts, measure);
+            (total > 0.).then(|| distance / total)
+        } else {
+            Some(distance)
+        }
+    };
+
+    let result: Vec<Option<f64>> = match points.len() {
+        // A single point, e.g. from a literal, is located on every line
+        1 => lines.iter().map(|line| locate(line, &points[0])).collect(),
+        len if len == lines.len() => lines
+            .iter()
+            .zip(points.iter())
+            .map(|(line, point)| locate(line, point))
+            .collect(),
+        len => {
+            return Err(PolarsError::ShapeMisMatch(ErrString::from(format!(
+                "Cannot locate a GeoSeries of length {} on one of length {}",
+                len,
+                lines.len()
+            )))
+            .into())
+        }
+    };
+
+    Ok(Float64Chunked::from_slice_options("result", &result).into_series())
+}
+
+pub(crate) fn line_substring(
+    series: &Series,
+    start: f64,
+    end: f64,
+    normalized: bool,
+    measure: Measure,
+) -> Result<Series> {
+    let output_arrays = series
+        .chunks()
+        .iter()
+        .map(|chunk| {
+            let array = geometry_array(&**chunk)?;
+            let geometry_type = GeoArrowType::from(&array);
+            let output_geoms = geometries_of_array(array)
+                .into_iter()
+                .map(|maybe_g| {
+                    let geom = maybe_g?;
+                    let parts = line_parts(&geom)?;
+                    let total = total_length(&parts, measure);
+                    let start = resolve_distance(start, normalized, total);
+                    let end = resolve_distance(end, normalized, total);
+                    let mut pieces = substring(&parts, start, end, measure);
+                    match geom {
+                        Geometry::MultiLineString(_) => {
+                            Some(Geometry::MultiLineString(MultiLineString::new(pieces)))
+                        }
+                        _ => Some(Geometry::LineString(if pieces.is_empty() {
+                            LineString::new(vec![])
+                        } else {
+                            pieces.remove(0)
+                        })),
+                    }
+                })
+                .collect();
+            geometry_array_from_geometries(output_geoms, geometry_type)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    with_crs_of(geometry_series_from_arrays(output_arrays)?, series)
+}
+
+/// The lines of a linear geometry, or `None` for other geometry types
+fn line_parts(geom: &Geometry) -> Option<Vec<LineString>> {
+    match geom {
+        Geometry::Line(g) => Some(vec![LineString::new(vec![g.start, g.end])]),
+        Geometry::LineString(g) => Some(vec![g.clone()]),
+        Geometry::MultiLineString(g) => Some(g.0.clone()),
+        _ => None,
+    }
+}
+
+fn total_length(parts: &[LineString], measure: Measure) -> f64 {
+    parts
+        .iter()
+        .flat_map(|part| part.lines())
+        .map(|segment| measure.length(segment.start, segment.end))
+        .sum()
+}
+
+/// Scale a normalized distance to the length of the line, count a negative distance from its
+/// end, and clamp the result to the line
+fn resolve_distance(distance: f64, normalized: bool, total: f64) -> f64 {
+    let distance = if normalized {
+        distance * total
+    } else {
+        distance
+    };
+    let distance = if distance < 0. {
+        total + distance
+    } else {
+        distance
+    };
+    distance.clamp(0., total)
+}
+
+/// The point at `distance` along the parts of a line, taken one after another
+fn interpolate_point(parts: &[LineString], distance: f64, measure: Measure) -> Option<Point> {
+    let mut travelled = 0.;
+    for segment in parts.iter().flat_map(|part| part.lines()) {
+        let length = measure.length(segment.start, segment.end);
+        if length > 0. && travelled + length >= distance {
+            let fraction = (distance - travelled) / length;
+            return Some(Point(measure.interpolate(
+                segment.start,
+                segment.end,
+                fraction,
+            )));
+        }
+        travelled += length;
+    }
+
+    parts
+        .iter()
+        .rev()
+        .find_map(|part| part.0.last())
+        .map(|coord| Point(*coord))
+}
+
+/// The distance along the parts of a line to the point of the line nearest to `point`
+///
+/// The nearest point is found in planar coordinates, also when measuring geodesic distances.
+fn locate_point(parts: &[LineString], point: Coord, measure: Measure) -> Option<f64> {
+    let mut best: Option<(f64, f64)> = None;
+    let mut travelled = 0.;
+    for part in parts {
+        if part.0.len() == 1 {
+            best = nearer(best, squared_distance(part.0[0], point), travelled);
+        }
+        for segment in part.lines() {
+            let delta = segment.end - segment.start;
+            let squared_length = delta.x * delta.x + delta.y * delta.y;
+            let t = if squared_length > 0. {
+                let offset = point - segment.start;
+                ((offset.x * delta.x + offset.y * delta.y) / squared_length).clamp(0., 1.)
+            } else {
+                0.
+            };
+            let nearest = segment.start + delta * t;
+            let along = travelled + measure.length(segment.start, nearest);
+            best = nearer(best, squared_distance(nearest, point), along);
+            travelled += measure.length(segment.start, segment.end);
+        }
+    }
+    best.map(|(_, distance)| distance)
+}
+
+/// The nearest of a previous candidate and a new one, as pairs of squared distance to the point
+/// and distance along the line
+fn nearer(best: Option<(f64, f64)>, squared: f64, along: f64) -> Option<(f64, f64)> {
+    match best {
+        Some((best_squared, _)) if best_squared <= squared => best,
+        _ => Some((squared, along)),
+    }
+}
+
+fn squared_distance(a: Coord, b: Coord) -> f64 {
+    (a.x - b.x) * (a.x - b.x) + (a.y - b.y) * (a.y - b.y)
+}
+
+/// The pieces of the parts of a line between two distances along it, reversed if `start` is
+/// after `end`. Pieces of fewer than two distinct coordinates are dropped.
+fn substring(parts: &[LineString], start: f64, end: f64, measure: Measure) -> Vec<LineString> {
+    let (from, to) = if start <= end {
+        (start, end)
+    } else {
+        (end, start)
+    };
+
+    let mut pieces = vec![];
+    let mut travelled = 0.;
+    for part in parts {
+        let mut coords = vec![];
+        for segment in part.lines() {
+            let length = measure.length(segment.start, segment.end);
+            let (segment_start, segment_end) = (travelled, travelled + length);
+            if segment_end >= from && segment_start <= to {
+                let point_at = |distance: f64| {
+                    if distance <= segment_start {
+                        segment.start
+                    } else if distance >= segment_end {
+                        segment.end
+                    } else {
+                        let fraction = (distance - segment_start) / length;
+                        measure.interpolate(segment.start, segment.end, fraction)
+                    }
+                };
+                if coords.is_empty() {
+                    coords.push(point_at(from));
+                }
+                coords.push(point_at(to));
+            }
+            travelled = segment_end;
+        }
+
+        coords.dedup();
+        if coords.len() >= 2 {
+            pieces.push(LineString::new(coords));
+        }
+    }
+
+    if start > end {
+        pieces.reverse();
+        for piece in pieces.iter_mut() {
+            piece.0.reverse();
+        }
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Measure;
+    use crate::geoseries::GeoSeries;
+    use crate::ops::length::GeodesicLengthMethod;
+    use crate::util::{from_geom_vec, geometries};
+    use geo::{line_string, point, Geometry, MultiLineString};
+
+    #[test]
+    fn interpolate_locate_and_substring() {
+        let series = from_geom_vec(&[
+            Geometry::LineString(line_string![(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.)]),
+            Geometry::MultiLineString(MultiLineString::new(vec![
+                line_string![(x: 0., y: 0.), (x: 1., y: 0.)],
+                line_string![(x: 5., y: 5.), (x: 5., y: 8.)],
+            ])),
+            Geometry::Point(point!(x: 0., y: 0.)),
+        ])
+        .unwrap();
+
+        let points = series
+            .line_interpolate_point(0.75, true, Measure::Euclidean)
+            .unwrap();
+        assert_eq!(
+            geometries(&points).unwrap(),
+            vec![
+                Some(Geometry::Point(point!(x: 10., y: 5.))),
+                Some(Geometry::Point(point!(x: 5., y: 7.))),
+                None,
+            ]
+        );
+
+        let other = from_geom_vec(&[Geometry::Point(point!(x: 12., y: 4.))]).unwrap();
+        let located = series
+            .line_locate_point(&other, false, Measure::Euclidean)
+            .unwrap();
+        let located: Vec<Option<f64>> = located.f64().unwrap().into_iter().collect();
+        assert_eq!(located, vec![Some(14.), Some(1.), None]);
+
+        let substring = series
+            .line_substring(-3., 2., false, Measure::Euclidean)
+            .unwrap();
+        assert_eq!(
+            geometries(&substring).unwrap(),
+            vec![
+                Some(Geometry::LineString(
+                    line_string![(x: 10., y: 7.), (x: 10., y: 0.), (x: 2., y: 0.)]
+                )),
+                // The piece of the first part is a single point, which is dropped
+                Some(Geometry::MultiLineString(MultiLineString::new(vec![
+                    line_string![(x: 5., y: 6.), (x: 5., y: 5.)]
+                ]))),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn geodesic_measure() {
+        // One degree of longitude along the equator
+        let series = from_geom_vec(&[Geometry::LineString(
+            line_string![(x: 0., y: 0.), (x: 1., y: 0.)],
+        )])
+        .unwrap();
+        let measure = Measure::Geodesic(GeodesicLengthMethod::Geodesic);
+
+        let points = series
+            .line_interpolate_point(55_000., false, measure)
+            .unwrap();
+        let x = points.x().unwrap().f64().unwrap().get(0).unwrap();
+        assert!((x - 55_000. / 111_319.49).abs() < 1e-6);
+
+        let other = from_geom_vec(&[Geometry::Point(point!(x: 0.5, y: 1.))]).unwrap();
+        let located = series.line_locate_point(&other, true, measure).unwrap();
+        let located = located.f64().unwrap().get(0).unwrap();
+        assert!((located - 0.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,285 @@
+use crate::error::Result;
+use geo::algorithm::convex_hull::ConvexHull;
+use geo::{coord, Coord, LineString, Polygon};
+use geoarrow::{GeometryArray, GeometryArrayTrait};
+use polars::export::arrow::array::{MutablePrimitiveArray, PrimitiveArray};
+use std::f64::consts::PI;
+
+/// Number of segments of the polygons approximating minimum bounding circles
+const CIRCLE_SEGMENTS: usize = 64;
+
+pub(crate) fn minimum_rotated_rectangle(array: GeometryArray) -> Result<GeometryArray> {
+    let output_geoms: Vec<Option<Polygon>> = convex_hulls(array)
+        .into_iter()
+        .map(|maybe_hull| maybe_hull.map(rotated_rectangle))
+        .collect();
+
+    Ok(GeometryArray::Polygon(output_geoms.into()))
+}
+
+pub(crate) fn minimum_bounding_circle(array: GeometryArray) -> Result<GeometryArray> {
+    let output_geoms: Vec<Option<Polygon>> = convex_hulls(array)
+        .into_iter()
+        .map(|maybe_hull| {
+            maybe_hull
+                .and_then(|hull| bounding_circle(&hull))
+                .map(|(center, radius)| circle_polygon(center, radius))
+        })
+        .collect();
+
+    Ok(GeometryArray::Polygon(output_geoms.into()))
+}
+
+pub(crate) fn minimum_bounding_radius(array: GeometryArray) -> Result<PrimitiveArray<f64>> {
+    let mut output_array = MutablePrimitiveArray::<f64>::with_capacity(array.len());
+    for maybe_hull in convex_hulls(array) {
+        output_array.push(
+            maybe_hull
+                .and_then(|hull| bounding_circle(&hull))
+                .map(|(_, radius)| radius),
+        );
+    }
+
+    Ok(output_array.into())
+}
+
+/// The convex hull of each geometry, which has the same minimum bounding shapes with fewer
+/// vertices
+fn convex_hulls(array: GeometryArray) -> Vec<Option<Polygon>> {
+    match array {
+        GeometryArray::WKB(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| geom.convex_hull()))
+            .collect(),
+        GeometryArray::Point(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| geom.convex_hull()))
+            .collect(),
+        GeometryArray::MultiPoint(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| geom.convex_hull()))
+            .collect(),
+        GeometryArray::LineString(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| geom.convex_hull()))
+            .collect(),
+        GeometryArray::MultiLineString(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| geom.convex_hull()))
+            .collect(),
+        GeometryArray::Polygon(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| geom.convex_hull()))
+            .collect(),
+        GeometryArray::MultiPolygon(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| geom.convex_hull()))
+            .collect(),
+    }
+}
+
+/// The rectangle of smallest area containing a convex hull, found with rotating calipers: one of
+/// its sides is collinear with an edge of the hull
+///
+/// Hulls without area, of points or collinear geometries, are returned as is.
+fn rotated_rectangle(hull: Polygon) -> Polygon {
+    let coords = &hull.exterior().0;
+    let mut best: Option<(f64, [Coord; 4])> = None;
+
+    for edge in coords.windows(2) {
+        let (origin, end) = (edge[0], edge[1]);
+        let length = (end.x - origin.x).hypot(end.y - origin.y);
+        if length == 0. {
+            continue;
+        }
+        let u = coord! { x: (end.x - origin.x) / length, y: (end.y - origin.y) / length };
+        let v = coord! { x: -u.y, y: u.x };
+
+        let (mut min_u, mut max_u) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut min_v, mut max_v) = (f64::INFINITY, f64::NEG_INFINITY);
+        for c in coords {
+            let (dx, dy) = (c.x - origin.x, c.y - origin.y);
+            let (pu, pv) = (dx * u.x + dy * u.y, dx * v.x + dy * v.y);
+            min_u = min_u.min(pu);
+            max_u = max_u.max(pu);
+            min_v = min_v.min(pv);
+            max_v = max_v.max(pv);
+        }
+
+        let area = (max_u - min_u) * (max_v - min_v);
+        let is_better = match best {
+            Some((best_area, _)) => area < best_area,
+            None => true,
+        };
+        if area > 0. && is_better {
+            let corner = |pu: f64, pv: f64| {
+                coord! {
+                    x: origin.x + pu * u.x + pv * v.x,
+                    y: origin.y + pu * u.y + pv * v.y,
+                }
+            };
+            best = Some((
+                area,
+                [
+                    corner(min_u, min_v),
+                    corner(max_u, min_v),
+                    corner(max_u, max_v),
+                    corner(min_u, max_v),
+                ],
+            ));
+        }
+    }
+
+    match best {
+        Some((_, corners)) => Polygon::new(LineString::new(corners.to_vec()), vec![]),
+        None => hull,
+    }
+}
+
+/// The center and radius of the smallest circle containing a convex hull, or `None` if the hull
+/// is empty
+///
+/// Uses Welzl's algorithm in its iterative form, which is expected linear time on the hull's
+/// vertices in random order. The vertices are shuffled with a fixed seed, so that the result
+/// doesn't vary between runs.
+fn bounding_circle(hull: &Polygon) -> Option<(Coord, f64)> {
+    let mut points = hull.exterior().0.clone();
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    for i in (1..points.len()).rev() {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        points.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+    let mut circle = (*points.first()?, 0.);
+
+    for (i, &a) in points.iter().enumerate() {
+        if contains(circle, a) {
+            continue;
+        }
+        circle = (a, 0.);
+        for (j, &b) in points[..i].iter().enumerate() {
+            if contains(circle, b) {
+                continue;
+            }
+            circle = diameter_circle(a, b);
+            for &c in &points[..j] {
+                if !contains(circle, c) {
+                    circle = circumcircle(a, b, c);
+                }
+            }
+        }
+    }
+
+    Some(circle)
+}
+
+fn contains((center, radius): (Coord, f64), point: Coord) -> bool {
+    let distance = (point.x - center.x).hypot(point.y - center.y);
+    distance <= radius + 1e-12 * radius.max(1.)
+}
+
+fn diameter_circle(a: Coord, b: Coord) -> (Coord, f64) {
+    let center = coord! { x: (a.x + b.x) / 2., y: (a.y + b.y) / 2. };
+    (center, (a.x - center.x).hypot(a.y - center.y))
+}
+
+/// The circle through three points, or the circle spanning the two farthest apart if they are
+/// collinear
+fn circumcircle(a: Coord, b: Coord, c: Coord) -> (Coord, f64) {
+    let (bx, by) = (b.x - a.x, b.y - a.y);
+    let (cx, cy) = (c.x - a.x, c.y - a.y);
+    let d = 2. * (bx * cy - by * cx);
+    if d == 0. {
+        return [
+            diameter_circle(a, b),
+            diameter_circle(a, c),
+            diameter_circle(b, c),
+        ]
+        .into_iter()
+        .fold(
+            (a, 0.),
+            |best, circle| {
+                if circle.1 > best.1 {
+                    circle
+                } else {
+                    best
+                }
+            },
+        );
+    }
+
+    let (b2, c2) = (bx * bx + by * by, cx * cx + cy * cy);
+    let ux = (cy * b2 - by * c2) / d;
+    let uy = (bx * c2 - cx * b2) / d;
+    (coord! { x: a.x + ux, y: a.y + uy }, ux.hypot(uy))
+}
+
+fn circle_polygon(center: Coord, radius: f64) -> Polygon {
+    let coords = (0..CIRCLE_SEGMENTS)
+        .map(|i| {
+            let angle = 2. * PI * i as f64 / CIRCLE_SEGMENTS as f64;
+            coord! {
+                x: center.x + radius * angle.cos(),
+                y: center.y + radius * angle.sin(),
+            }
+        })
+        .collect();
+    Polygon::new(LineString::new(coords), vec![])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{minimum_bounding_circle, minimum_bounding_radius, minimum_rotated_rectangle};
+    use crate::util::geometries_of_array;
+    use geo::prelude::Area;
+    use geo::{line_string, polygon, Geometry};
+    use geoarrow::{GeometryArray, WKBArray};
+
+    #[test]
+    fn rotated_rectangle() {
+        let diamond = polygon![
+            (x: 1., y: 0.),
+            (x: 2., y: 1.),
+            (x: 1., y: 2.),
+            (x: 0., y: 1.),
+        ];
+        let input: WKBArray = vec![Some(Geometry::Polygon(diamond)), None].into();
+        let result = minimum_rotated_rectangle(GeometryArray::WKB(input)).unwrap();
+        let result = geometries_of_array(result);
+
+        // The diamond is its own minimum rectangle, half the area of its envelope
+        assert!((result[0].as_ref().unwrap().unsigned_area() - 2.).abs() < 1e-12);
+        assert_eq!(result[1], None);
+    }
+
+    #[test]
+    fn bounding_circle() {
+        let input = || {
+            let array: WKBArray = vec![
+                Some(Geometry::Polygon(polygon![
+                    (x: 0., y: 0.),
+                    (x: 2., y: 0.),
+                    (x: 2., y: 2.),
+                    (x: 0., y: 2.),
+                ])),
+                // An obtuse triangle is spanned by its longest side
+                Some(Geometry::LineString(
+                    line_string![(x: 0., y: 0.), (x: 2., y: 1.), (x: 4., y: 0.)],
+                )),
+            ]
+            .into();
+            GeometryArray::WKB(array)
+        };
+        let radius = minimum_bounding_radius(input()).unwrap();
+        assert!((radius.value(0) - 2_f64.sqrt()).abs() < 1e-12);
+        assert!((radius.value(1) - 2.).abs() < 1e-12);
+
+        let circles = geometries_of_array(minimum_bounding_circle(input()).unwrap());
+        match &circles[1] {
+            Some(Geometry::Polygon(p)) => assert_eq!(p.exterior().0.len(), 65),
+            _ => panic!("Expected a polygon"),
+        }
+    }
+}
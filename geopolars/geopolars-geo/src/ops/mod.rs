@@ -1,16 +1,35 @@
 pub mod affine;
 pub mod area;
+pub mod bounds;
+#[cfg(not(feature = "proj"))]
+pub mod builtin_crs;
 pub mod centroid;
+pub mod clip;
+pub mod concave_hull;
 pub mod convex_hull;
+pub mod coordinates;
+pub mod count;
+pub(crate) mod delaunay;
+pub mod densify;
 pub mod distance;
 pub mod envelope;
 pub mod explode;
 pub mod exterior;
 pub mod geom_type;
+pub mod interior_point;
+pub mod intersects;
 pub mod is_empty;
 pub mod is_ring;
 pub mod length;
+pub mod linear_referencing;
+pub mod minimum_bounding;
+pub mod noding;
+pub mod normalize;
+pub mod parts;
 pub mod point;
+pub mod precision;
 #[cfg(feature = "proj")]
 pub mod proj;
 pub mod simplify;
+pub mod utm;
+pub mod voronoi;
@@ -0,0 +1,472 @@
+use crate::crs::with_crs_of;
+use crate::error::Result;
+use crate::extension::GeoArrowType;
+use crate::util::{
+    coord_key, geometries, geometry_array_from_geometries, geometry_series_from_arrays, CoordKey,
+};
+use geo::algorithm::line_intersection::{line_intersection, LineIntersection};
+use geo::algorithm::Contains;
+use geo::{Coord, Geometry, Line, LineString, MultiLineString, MultiPolygon, Point, Polygon};
+use polars::prelude::Series;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+pub(crate) fn line_merge(series: &Series) -> Result<Series> {
+    let merged = merge_lines(&input_lines(series)?);
+    aggregate(
+        series,
+        Geometry::MultiLineString(MultiLineString::new(merged)),
+        GeoArrowType::MultiLineString,
+    )
+}
+
+pub(crate) fn node(series: &Series) -> Result<Series> {
+    let noded = node_lines(&input_lines(series)?);
+    aggregate(
+        series,
+        Geometry::MultiLineString(MultiLineString::new(noded)),
+        GeoArrowType::MultiLineString,
+    )
+}
+
+pub(crate) fn polygonize(series: &Series) -> Result<Series> {
+    let polygons = polygonize_lines(&input_lines(series)?);
+    aggregate(
+        series,
+        Geometry::MultiPolygon(MultiPolygon::new(polygons)),
+        GeoArrowType::MultiPolygon,
+    )
+}
+
+/// A single row GeoSeries of the result of an aggregation, with the CRS of the input
+fn aggregate(series: &Series, geom: Geometry, geometry_type: GeoArrowType) -> Result<Series> {
+    let array = geometry_array_from_geometries(vec![Some(geom)], geometry_type)?;
+    with_crs_of(geometry_series_from_arrays(vec![array])?, series)
+}
+
+/// The lines and polygon rings of all geometries of a series, without repeated coordinates.
+/// Lines of fewer than two distinct coordinates are dropped.
+fn input_lines(series: &Series) -> Result<Vec<LineString>> {
+    let mut lines = vec![];
+    for geom in geometries(series)?.iter().flatten() {
+        linear_components(geom, &mut lines);
+    }
+    for line in lines.iter_mut() {
+        line.0.dedup();
+    }
+    lines.retain(|line| line.0.len() >= 2);
+    Ok(lines)
+}
+
+pub(crate) fn linear_components(geom: &Geometry, lines: &mut Vec<LineString>) {
+    match geom {
+        Geometry::Point(_) | Geometry::MultiPoint(_) => {}
+        Geometry::Line(g) => lines.push(LineString::new(vec![g.start, g.end])),
+        Geometry::LineString(g) => lines.push(g.clone()),
+        Geometry::MultiLineString(g) => lines.extend(g.0.iter().cloned()),
+        Geometry::Polygon(g) => polygon_rings(g, lines),
+        Geometry::MultiPolygon(g) => g.0.iter().for_each(|polygon| polygon_rings(polygon, lines)),
+        Geometry::Rect(g) => polygon_rings(&g.to_polygon(), lines),
+        Geometry::Triangle(g) => polygon_rings(&g.to_polygon(), lines),
+        Geometry::GeometryCollection(g) => {
+            g.0.iter().for_each(|geom| linear_components(geom, lines))
+        }
+    }
+}
+
+fn polygon_rings(polygon: &Polygon, lines: &mut Vec<LineString>) {
+    lines.push(polygon.exterior().clone());
+    lines.extend(polygon.interiors().iter().cloned());
+}
+
+/// The vertices of a line in the direction in which they compare smallest, so that a line and
+/// its reverse have the same key
+fn canonical_key(coords: &[Coord]) -> Vec<CoordKey> {
+    let keys: Vec<CoordKey> = coords.iter().map(coord_key).collect();
+    let reversed_keys: Vec<CoordKey> = keys.iter().rev().copied().collect();
+    keys.min(reversed_keys)
+}
+
+/// Merge lines meeting end to end into longer lines, like the GEOS `LineMerger`
+///
+/// Lines are joined at the nodes where exactly two line ends meet, reversing lines as needed. A
+/// merged line keeps the direction of most of its lines. Cycles of lines become closed lines.
+fn merge_lines(lines: &[LineString]) -> Vec<LineString> {
+    let ends = |line: &LineString| (coord_key(&line.0[0]), coord_key(&line.0[line.0.len() - 1]));
+    let mut incident: HashMap<CoordKey, Vec<usize>> = HashMap::new();
+    for (i, line) in lines.iter().enumerate() {
+        let (start, end) = ends(line);
+        incident.entry(start).or_default().push(i);
+        incident.entry(end).or_default().push(i);
+    }
+
+    let mut visited = vec![false; lines.len()];
+    let mut merged = vec![];
+    // Merged lines start at the nodes where they can't be extended...
+    for (i, line) in lines.iter().enumerate() {
+        let (start, end) = ends(line);
+        for node in [start, end] {
+            if !visited[i] && incident[&node].len() != 2 {
+                merged.push(walk(lines, &incident, &mut visited, i, node));
+            }
+        }
+    }
+    // ...and the lines left over form cycles
+    for (i, line) in lines.iter().enumerate() {
+        if !visited[i] {
+            merged.push(walk(lines, &incident, &mut visited, i, ends(line).0));
+        }
+    }
+    merged
+}
+
+/// Join lines starting with line `first` at `node`, for as long as the far end of the last line
+/// joined meets exactly one other line that isn't joined yet
+fn walk(
+    lines: &[LineString],
+    incident: &HashMap<CoordKey, Vec<usize>>,
+    visited: &mut [bool],
+    first: usize,
+    node: CoordKey,
+) -> LineString {
+    let mut coords: Vec<Coord> = vec![];
+    let (mut line, mut node) = (first, node);
+    let (mut joined, mut reversed) = (0, 0);
+    loop {
+        visited[line] = true;
+        joined += 1;
+        let mut part = lines[line].0.clone();
+        if coord_key(&part[0]) != node {
+            part.reverse();
+            reversed += 1;
+        }
+        node = coord_key(&part[part.len() - 1]);
+        if coords.is_empty() {
+            coords = part;
+        } else {
+            coords.extend_from_slice(&part[1..]);
+        }
+
+        let next = match incident[&node].as_slice() {
+            [a, b] => [*a, *b].into_iter().find(|other| !visited[*other]),
+            _ => None,
+        };
+        match next {
+            Some(next) => line = next,
+            None => break,
+        }
+    }
+
+    if 2 * reversed > joined {
+        coords.reverse();
+    }
+    LineString::new(coords)
+}
+
+/// Split lines at every point where they intersect themselves or each other, like the GEOS
+/// `node` operation. Pieces that are duplicates of another piece, in either direction, are
+/// dropped.
+pub(crate) fn node_lines(lines: &[LineString]) -> Vec<LineString> {
+    let min_x = |segment: &Line| segment.start.x.min(segment.end.x);
+
+    // Segments by the index of their line and their index in the line, sorted by their minimum
+    // x so that only segments with overlapping x ranges are compared
+    let mut segments: Vec<(usize, usize, Line)> = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            line.lines()
+                .enumerate()
+                .map(move |(j, segment)| (i, j, segment))
+        })
+        .collect();
+    segments.sort_by(|a, b| {
+        min_x(&a.2)
+            .partial_cmp(&min_x(&b.2))
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut splits: HashMap<(usize, usize), Vec<Coord>> = HashMap::new();
+    for (k, &(line_a, index_a, a)) in segments.iter().enumerate() {
+        let max_x = a.start.x.max(a.end.x);
+        for &(line_b, index_b, b) in &segments[k + 1..] {
+            if min_x(&b) > max_x {
+                break;
+            }
+            let points = match line_intersection(a, b) {
+                None => continue,
+                // Consecutive segments of a line only meet at their shared vertex, unless the
+                // line doubles back on itself
+                Some(LineIntersection::SinglePoint { .. })
+                    if line_a == line_b && are_consecutive(&lines[line_a], index_a, index_b) =>
+                {
+                    continue
+                }
+                Some(LineIntersection::SinglePoint { intersection, .. }) => vec![intersection],
+                Some(LineIntersection::Collinear { intersection }) => {
+                    vec![intersection.start, intersection.end]
+                }
+            };
+            for point in points {
+                splits.entry((line_a, index_a)).or_default().push(point);
+                splits.entry((line_b, index_b)).or_default().push(point);
+            }
+        }
+    }
+
+    let mut pieces = vec![];
+    let mut seen = HashSet::new();
+    let mut push_piece = |coords: Vec<Coord>| {
+        if coords.len() >= 2 && seen.insert(canonical_key(&coords)) {
+            pieces.push(LineString::new(coords));
+        }
+    };
+    for (i, line) in lines.iter().enumerate() {
+        let mut piece = vec![line.0[0]];
+        for (j, segment) in line.lines().enumerate() {
+            let mut points = splits.remove(&(i, j)).unwrap_or_default();
+            let along = |point: &Coord| {
+                let (offset, delta) = (*point - segment.start, segment.end - segment.start);
+                offset.x * delta.x + offset.y * delta.y
+            };
+            points.sort_by(|p, q| along(p).partial_cmp(&along(q)).unwrap_or(Ordering::Equal));
+
+            for point in points {
+                if piece[piece.len() - 1] != point {
+                    piece.push(point);
+                }
+                push_piece(std::mem::replace(&mut piece, vec![point]));
+            }
+            if piece[piece.len() - 1] != segment.end {
+                piece.push(segment.end);
+            }
+        }
+        push_piece(piece);
+    }
+    pieces
+}
+
+fn are_consecutive(line: &LineString, i: usize, j: usize) -> bool {
+    let last = line.0.len() - 2;
+    let (i, j) = (i.min(j), i.max(j));
+    j == i + 1 || (line.is_closed() && i == 0 && j == last)
+}
+
+/// Build the polygons enclosed by noded lines, like the GEOS `polygonize` operation
+///
+/// The lines form a planar graph whose faces are traced by always taking the next edge
+/// clockwise at each node. Cut edges, including dangling lines, are dropped as they don't
+/// bound a face. Faces are traced counter-clockwise, while the outer boundary of each connected
+/// part of the graph is traced clockwise and becomes a hole of the smallest face containing it.
+pub(crate) fn polygonize_lines(lines: &[LineString]) -> Vec<Polygon> {
+    let mut seen = HashSet::new();
+    let edges: Vec<&LineString> = lines
+        .iter()
+        .filter(|line| seen.insert(canonical_key(&line.0)))
+        .collect();
+
+    let mut removed = vec![false; edges.len()];
+    let rings = loop {
+        let rings = trace_rings(&edges, &removed);
+        let mut has_cut_edges = false;
+        for ring in &rings {
+            let half_edges: HashSet<usize> = ring.iter().copied().collect();
+            for half_edge in ring {
+                if half_edges.contains(&(half_edge ^ 1)) {
+                    removed[half_edge / 2] = true;
+                    has_cut_edges = true;
+                }
+            }
+        }
+        if !has_cut_edges {
+            break rings;
+        }
+    };
+
+    let mut shells = vec![];
+    let mut holes = vec![];
+    for ring in rings {
+        let ring = ring_line_string(&edges, &ring);
+        let area = signed_area(&ring.0);
+        match area.partial_cmp(&0.) {
+            Some(Ordering::Greater) => shells.push((area, Polygon::new(ring, vec![]))),
+            Some(Ordering::Less) => holes.push((-area, ring)),
+            _ => {}
+        }
+    }
+
+    let mut interiors: Vec<Vec<LineString>> = vec![vec![]; shells.len()];
+    for (hole_area, hole) in holes {
+        let point = Point(hole.0[0]);
+        let container = shells
+            .iter()
+            .enumerate()
+            .filter(|(_, (area, shell))| *area > hole_area && shell.contains(&point))
+            .min_by(|(_, (a, _)), (_, (b, _))| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map(|(i, _)| i);
+        if let Some(i) = container {
+            interiors[i].push(hole);
+        }
+    }
+
+    shells
+        .into_iter()
+        .zip(interiors)
+        .map(|((_, shell), interiors)| Polygon::new(shell.exterior().clone(), interiors))
+        .collect()
+}
+
+/// The first two coordinates of a half-edge: half-edge `2 * i` runs along edge `i` and
+/// half-edge `2 * i + 1` runs back along it
+fn half_edge_start(edges: &[&LineString], half_edge: usize) -> (Coord, Coord) {
+    let coords = &edges[half_edge / 2].0;
+    match half_edge % 2 {
+        0 => (coords[0], coords[1]),
+        _ => (coords[coords.len() - 1], coords[coords.len() - 2]),
+    }
+}
+
+/// The rings of half-edges bounding the faces of the graph of the edges that aren't removed
+fn trace_rings(edges: &[&LineString], removed: &[bool]) -> Vec<Vec<usize>> {
+    let half_edges = (0..2 * edges.len()).filter(|half_edge| !removed[half_edge / 2]);
+
+    // The half-edges leaving each node, sorted counter-clockwise
+    let mut outgoing: HashMap<CoordKey, Vec<(f64, usize)>> = HashMap::new();
+    for half_edge in half_edges.clone() {
+        let (origin, next) = half_edge_start(edges, half_edge);
+        let angle = (next.y - origin.y).atan2(next.x - origin.x);
+        outgoing
+            .entry(coord_key(&origin))
+            .or_default()
+            .push((angle, half_edge));
+    }
+    for node in outgoing.values_mut() {
+        node.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+    }
+
+    // The face to the left of a half-edge continues along the half-edge leaving its end next
+    // clockwise from the way back
+    let next = |half_edge: usize| {
+        let twin = half_edge ^ 1;
+        let node = &outgoing[&coord_key(&half_edge_start(edges, twin).0)];
+        let position = node.iter().position(|(_, h)| *h == twin).unwrap();
+        node[(position + node.len() - 1) % node.len()].1
+    };
+
+    let mut visited = vec![false; 2 * edges.len()];
+    let mut rings = vec![];
+    for half_edge in half_edges {
+        let mut ring = vec![];
+        let mut current = half_edge;
+        while !visited[current] {
+            visited[current] = true;
+            ring.push(current);
+            current = next(current);
+        }
+        if !ring.is_empty() {
+            rings.push(ring);
+        }
+    }
+    rings
+}
+
+fn ring_line_string(edges: &[&LineString], ring: &[usize]) -> LineString {
+    let mut coords = vec![];
+    for half_edge in ring {
+        let edge = &edges[half_edge / 2].0;
+        match half_edge % 2 {
+            0 => coords.extend_from_slice(&edge[..edge.len() - 1]),
+            _ => coords.extend(edge[1..].iter().rev()),
+        }
+    }
+    coords.push(coords[0]);
+    LineString::new(coords)
+}
+
+/// The signed area of a closed ring, positive if it is counter-clockwise
+fn signed_area(coords: &[Coord]) -> f64 {
+    coords
+        .windows(2)
+        .map(|pair| pair[0].x * pair[1].y - pair[1].x * pair[0].y)
+        .sum::<f64>()
+        / 2.
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoseries::GeoSeries;
+    use crate::util::{from_geom_vec, geometries};
+    use geo::prelude::{Area, EuclideanLength};
+    use geo::{line_string, polygon, Geometry};
+
+    #[test]
+    fn merge_lines() {
+        let series = from_geom_vec(&[
+            Geometry::LineString(line_string![(x: 0., y: 0.), (x: 1., y: 0.)]),
+            Geometry::LineString(line_string![(x: 5., y: 5.), (x: 6., y: 6.)]),
+            Geometry::LineString(line_string![(x: 2., y: 1.), (x: 2., y: 0.)]),
+            Geometry::LineString(line_string![(x: 1., y: 0.), (x: 2., y: 0.)]),
+        ])
+        .unwrap();
+
+        let merged = geometries(&series.line_merge().unwrap()).unwrap();
+        let lines = match &merged[..] {
+            [Some(Geometry::MultiLineString(lines))] => lines,
+            _ => panic!("Expected a single MultiLineString"),
+        };
+        assert_eq!(
+            lines.0,
+            vec![
+                line_string![(x: 0., y: 0.), (x: 1., y: 0.), (x: 2., y: 0.), (x: 2., y: 1.)],
+                line_string![(x: 5., y: 5.), (x: 6., y: 6.)],
+            ]
+        );
+    }
+
+    #[test]
+    fn node_and_polygonize() {
+        let series = from_geom_vec(&[
+            Geometry::Polygon(polygon![
+                (x: 0., y: 0.),
+                (x: 2., y: 0.),
+                (x: 2., y: 2.),
+                (x: 0., y: 2.),
+            ]),
+            // A diagonal splitting the square, dangling past its corner
+            Geometry::LineString(line_string![(x: 0., y: 0.), (x: 3., y: 3.)]),
+            // A hole in the lower triangle
+            Geometry::LineString(line_string![
+                (x: 1.5, y: 0.2),
+                (x: 1.8, y: 0.2),
+                (x: 1.8, y: 0.5),
+                (x: 1.5, y: 0.5),
+                (x: 1.5, y: 0.2),
+            ]),
+        ])
+        .unwrap();
+
+        let noded = series.node().unwrap();
+        let lines = match &geometries(&noded).unwrap()[..] {
+            [Some(Geometry::MultiLineString(lines))] => lines.clone(),
+            _ => panic!("Expected a single MultiLineString"),
+        };
+        // The square's ring and the diagonal are split at the far corner
+        assert_eq!(lines.0.len(), 5);
+        assert!((lines.euclidean_length() - 8. - 3. * 2_f64.sqrt() - 1.2).abs() < 1e-12);
+
+        let polygons = match &geometries(&noded.polygonize().unwrap()).unwrap()[..] {
+            [Some(Geometry::MultiPolygon(polygons))] => polygons.clone(),
+            _ => panic!("Expected a single MultiPolygon"),
+        };
+        assert_eq!(polygons.0.len(), 3);
+        assert_eq!(
+            polygons
+                .0
+                .iter()
+                .map(|polygon| polygon.interiors().len())
+                .sum::<usize>(),
+            1
+        );
+        assert!((polygons.unsigned_area() - 4.).abs() < 1e-12);
+    }
+}
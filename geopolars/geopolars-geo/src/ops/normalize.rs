@@ -0,0 +1,580 @@
+use crate::error::Result;
+use crate::extension::{chunk_extension, chunk_geoarrow_type, extension_array, GeoArrowType};
+use crate::util::{
+    coord_array, coord_buffers, geometries_of_array, geometry_array,
+    geometry_array_from_geometries, map_coord_lists, series_like, unexpected_coord_type,
+};
+use geo::{Coord, CoordsIter, Geometry, GeometryCollection, LineString, Point, Polygon};
+use geoarrow::GeometryArrayTrait;
+use polars::error::ErrString;
+use polars::export::arrow::array::{Array, ListArray, StructArray};
+use polars::export::arrow::bitmap::Bitmap;
+use polars::export::arrow::offset::{Offset, Offsets};
+use polars::prelude::{PolarsError, Series};
+use std::cmp::Ordering;
+
+/// What an innermost list of coordinates of a geometry holds
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CoordList {
+    Points,
+    Line,
+    Exterior,
+    Interior,
+}
+
+pub(crate) fn orient(series: &Series, exterior_ccw: bool) -> Result<Series> {
+    map_lists(series, &|coords, kind| match kind {
+        CoordList::Exterior => orient_ring(coords, exterior_ccw),
+        CoordList::Interior => orient_ring(coords, !exterior_ccw),
+        _ => coords,
+    })
+}
+
+pub(crate) fn reverse(series: &Series) -> Result<Series> {
+    map_lists(series, &|mut coords, kind| {
+        // The points of a MultiPoint have no direction
+        if kind != CoordList::Points {
+            coords.reverse();
+        }
+        coords
+    })
+}
+
+pub(crate) fn remove_repeated_points(series: &Series, tolerance: f64) -> Result<Series> {
+    if tolerance.is_nan() || tolerance < 0. {
+        return Err(PolarsError::ComputeError(ErrString::from(
+            "The tolerance must not be negative",
+        ))
+        .into());
+    }
+    map_lists(series, &|coords, kind| {
+        remove_repeated(coords, kind, tolerance)
+    })
+}
+
+pub(crate) fn normalize(series: &Series) -> Result<Series> {
+    let output_chunks = series
+        .chunks()
+        .iter()
+        .map(|chunk| match chunk_geoarrow_type(&**chunk)? {
+            GeoArrowType::WKB => map_wkb(&**chunk, |geom| {
+                sort_parts(map_geometry_lists(geom, &normalize_list))
+            }),
+            geometry_type => {
+                let output = map_coord_lists(&**chunk, &mut |coords: Vec<Coord>, first: bool| {
+                    normalize_list(coords, list_kind(geometry_type, first))
+                })?;
+                // Lists of a single part are already ordered; otherwise the parts are reordered
+                // by moving whole lists of coordinates
+                match geometry_type {
+                    GeoArrowType::Polygon => sort_children(&*output, true),
+                    GeoArrowType::MultiLineString => sort_children(&*output, false),
+                    GeoArrowType::MultiPolygon => sort_multi_polygon_children(&*output),
+                    _ => Ok(output),
+                }
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    series_like(series, output_chunks)
+}
+
+/// Apply `f` to every innermost list of coordinates of a geometry Series, working on the
+/// coordinate buffers of native chunks directly
+fn map_lists(series: &Series, f: &dyn Fn(Vec<Coord>, CoordList) -> Vec<Coord>) -> Result<Series> {
+    let output_chunks = series
+        .chunks()
+        .iter()
+        .map(|chunk| match chunk_geoarrow_type(&**chunk)? {
+            GeoArrowType::WKB => map_wkb(&**chunk, |geom| map_geometry_lists(geom, f)),
+            geometry_type => map_coord_lists(&**chunk, &mut |coords: Vec<Coord>, first: bool| {
+                f(coords, list_kind(geometry_type, first))
+            }),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    series_like(series, output_chunks)
+}
+
+fn list_kind(geometry_type: GeoArrowType, first: bool) -> CoordList {
+    match geometry_type {
+        GeoArrowType::Point | GeoArrowType::MultiPoint => CoordList::Points,
+        GeoArrowType::Polygon | GeoArrowType::MultiPolygon if first => CoordList::Exterior,
+        GeoArrowType::Polygon | GeoArrowType::MultiPolygon => CoordList::Interior,
+        _ => CoordList::Line,
+    }
+}
+
+/// Apply `f` to each geometry of a WKB chunk, keeping its extension information
+fn map_wkb(chunk: &dyn Array, f: impl Fn(Geometry) -> Geometry) -> Result<Box<dyn Array>> {
+    let extension =
+        chunk_extension(chunk).map(|(_, metadata)| metadata.map(|metadata| metadata.to_string()));
+    let output_geoms = geometries_of_array(geometry_array(chunk)?)
+        .into_iter()
+        .map(|maybe_g| maybe_g.map(&f))
+        .collect();
+    let output = geometry_array_from_geometries(output_geoms, GeoArrowType::WKB)?.into_arrow();
+    Ok(match extension {
+        Some(metadata) => extension_array(&*output, GeoArrowType::WKB, metadata)?,
+        None => output,
+    })
+}
+
+fn map_geometry_lists(geom: Geometry, f: &dyn Fn(Vec<Coord>, CoordList) -> Vec<Coord>) -> Geometry {
+    let map_polygon = |polygon: Polygon| {
+        let (exterior, interiors) = polygon.into_inner();
+        Polygon::new(
+            LineString::new(f(exterior.0, CoordList::Exterior)),
+            interiors
+                .into_iter()
+                .map(|ring| LineString::new(f(ring.0, CoordList::Interior)))
+                .collect(),
+        )
+    };
+
+    match geom {
+        Geometry::Point(g) => Geometry::Point(g),
+        Geometry::MultiPoint(g) => Geometry::MultiPoint(
+            f(g.0.iter().map(|point| point.0).collect(), CoordList::Points)
+                .into_iter()
+                .map(Point)
+                .collect(),
+        ),
+        Geometry::Line(g) => {
+            Geometry::LineString(LineString::new(f(vec![g.start, g.end], CoordList::Line)))
+        }
+        Geometry::LineString(g) => Geometry::LineString(LineString::new(f(g.0, CoordList::Line))),
+        Geometry::MultiLineString(g) => Geometry::MultiLineString(
+            g.into_iter()
+                .map(|line| LineString::new(f(line.0, CoordList::Line)))
+                .collect(),
+        ),
+        Geometry::Polygon(g) => Geometry::Polygon(map_polygon(g)),
+        Geometry::MultiPolygon(g) => {
+            Geometry::MultiPolygon(g.into_iter().map(map_polygon).collect())
+        }
+        Geometry::Rect(g) => Geometry::Polygon(map_polygon(g.to_polygon())),
+        Geometry::Triangle(g) => Geometry::Polygon(map_polygon(g.to_polygon())),
+        Geometry::GeometryCollection(g) => Geometry::GeometryCollection(GeometryCollection(
+            g.into_iter()
+                .map(|geom| map_geometry_lists(geom, f))
+                .collect(),
+        )),
+    }
+}
+
+/// Twice the signed area of a closed ring, positive for counter-clockwise rings
+fn signed_area(coords: &[Coord]) -> f64 {
+    coords
+        .windows(2)
+        .map(|pair| pair[0].x * pair[1].y - pair[1].x * pair[0].y)
+        .sum()
+}
+
+fn orient_ring(mut coords: Vec<Coord>, ccw: bool) -> Vec<Coord> {
+    let area = signed_area(&coords);
+    if (area > 0. && !ccw) || (area < 0. && ccw) {
+        coords.reverse();
+    }
+    coords
+}
+
+/// Remove each coordinate within `tolerance` of the last one kept, keeping the last coordinate of
+/// lines and rings so that rings stay closed. Lists that would collapse are left as they are.
+fn remove_repeated(coords: Vec<Coord>, kind: CoordList, tolerance: f64) -> Vec<Coord> {
+    let mut kept: Vec<Coord> = Vec::with_capacity(coords.len());
+    for coord in &coords {
+        match kept.last() {
+            Some(last) if (last.x - coord.x).hypot(last.y - coord.y) <= tolerance => {}
+            _ => kept.push(*coord),
+        }
+    }
+
+    let min_len = match kind {
+        CoordList::Points => return kept,
+        CoordList::Line => 2,
+        CoordList::Exterior | CoordList::Interior => 4,
+    };
+    if let Some(end) = coords.last() {
+        if kept.last() != Some(end) {
+            if kept.len() > 1 {
+                kept.pop();
+            }
+            kept.push(*end);
+        }
+    }
+    if kept.len() < min_len {
+        coords
+    } else {
+        kept
+    }
+}
+
+/// Put a list of coordinates in the canonical form of GEOS: exterior rings are clockwise and
+/// interior rings counter-clockwise, both starting at their smallest coordinate, lines run in the
+/// direction with the smaller coordinates first, and points are sorted in descending order
+fn normalize_list(mut coords: Vec<Coord>, kind: CoordList) -> Vec<Coord> {
+    match kind {
+        CoordList::Points => {
+            coords.sort_by(|a, b| cmp_coord(b, a));
+            coords
+        }
+        CoordList::Line => {
+            let reversed: Vec<Coord> = coords.iter().rev().copied().collect();
+            if cmp_coords(&reversed, &coords) == Ordering::Less {
+                reversed
+            } else {
+                coords
+            }
+        }
+        CoordList::Exterior | CoordList::Interior => {
+            if coords.len() < 4 || coords.first() != coords.last() {
+                return coords;
+            }
+            let mut coords = orient_ring(coords, kind == CoordList::Interior);
+            coords.pop();
+            let start = (0..coords.len())
+                .min_by(|a, b| cmp_coord(&coords[*a], &coords[*b]))
+                .unwrap_or(0);
+            coords.rotate_left(start);
+            coords.push(coords[0]);
+            coords
+        }
+    }
+}
+
+fn cmp_coord(a: &Coord, b: &Coord) -> Ordering {
+    a.x.partial_cmp(&b.x)
+        .unwrap_or(Ordering::Equal)
+        .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+}
+
+fn cmp_coords(a: &[Coord], b: &[Coord]) -> Ordering {
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| cmp_coord(a, b))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+/// Sort the interior rings of polygons and the parts of multi-part geometries in descending
+/// order of their coordinates, as GEOS does
+fn sort_parts(geom: Geometry) -> Geometry {
+    let sort_interiors = |polygon: Polygon| {
+        let (exterior, mut interiors) = polygon.into_inner();
+        interiors.sort_by(|a, b| cmp_coords(&b.0, &a.0));
+        Polygon::new(exterior, interiors)
+    };
+
+    match geom {
+        Geometry::MultiLineString(mut g) => {
+            g.0.sort_by(|a, b| cmp_coords(&b.0, &a.0));
+            Geometry::MultiLineString(g)
+        }
+        Geometry::Polygon(g) => Geometry::Polygon(sort_interiors(g)),
+        Geometry::MultiPolygon(mut g) => {
+            let mut keyed: Vec<(Vec<Coord>, Polygon)> =
+                g.0.into_iter()
+                    .map(|polygon| {
+                        let polygon = sort_interiors(polygon);
+                        (polygon.coords_iter().collect(), polygon)
+                    })
+                    .collect();
+            keyed.sort_by(|a, b| cmp_coords(&b.0, &a.0));
+            g.0 = keyed.into_iter().map(|(_, polygon)| polygon).collect();
+            Geometry::MultiPolygon(g)
+        }
+        Geometry::GeometryCollection(g) => Geometry::GeometryCollection(GeometryCollection(
+            g.into_iter().map(sort_parts).collect(),
+        )),
+        geom => geom,
+    }
+}
+
+/// Sort the children of each list of a native list array in descending order of their
+/// coordinates, like [`sort_parts`], keeping the first child in place if `keep_first`
+fn sort_children(array: &dyn Array, keep_first: bool) -> Result<Box<dyn Array>> {
+    let any = array.as_any();
+    if let Some(arr) = any.downcast_ref::<ListArray<i64>>() {
+        sort_list_children(arr, keep_first)
+    } else if let Some(arr) = any.downcast_ref::<ListArray<i32>>() {
+        sort_list_children(arr, keep_first)
+    } else {
+        Err(unexpected_coord_type(array))
+    }
+}
+
+fn sort_list_children<O: Offset>(arr: &ListArray<O>, keep_first: bool) -> Result<Box<dyn Array>> {
+    let offsets = arr.offsets().buffer();
+    let values = arr.values().as_ref();
+    let mut indices = Vec::with_capacity(values.len());
+    for window in offsets.windows(2) {
+        let (start, end) = (window[0].to_usize(), window[1].to_usize());
+        let sorted_start = if keep_first {
+            (start + 1).min(end)
+        } else {
+            start
+        };
+        indices.extend(start..sorted_start);
+
+        let mut keyed: Vec<(Vec<Coord>, usize)> = (sorted_start..end)
+            .map(|child| {
+                let mut coords = vec![];
+                flat_coords(values, child..child + 1, &mut coords)?;
+                Ok((coords, child))
+            })
+            .collect::<Result<_>>()?;
+        keyed.sort_by(|a, b| cmp_coords(&b.0, &a.0));
+        indices.extend(keyed.into_iter().map(|(_, child)| child));
+    }
+
+    let lengths = offsets
+        .windows(2)
+        .map(|window| window[1].to_usize() - window[0].to_usize());
+    Ok(ListArray::<O>::new(
+        arr.data_type().clone(),
+        offsets_from_lengths(lengths)?.into(),
+        take(values, &indices)?,
+        arr.validity().cloned(),
+    )
+    .boxed())
+}
+
+/// Sort the rings of each polygon of a native MultiPolygon array, then its polygons
+fn sort_multi_polygon_children(array: &dyn Array) -> Result<Box<dyn Array>> {
+    let any = array.as_any();
+    if let Some(arr) = any.downcast_ref::<ListArray<i64>>() {
+        sort_list_multi_polygon_children(arr)
+    } else if let Some(arr) = any.downcast_ref::<ListArray<i32>>() {
+        sort_list_multi_polygon_children(arr)
+    } else {
+        Err(unexpected_coord_type(array))
+    }
+}
+
+fn sort_list_multi_polygon_children<O: Offset>(arr: &ListArray<O>) -> Result<Box<dyn Array>> {
+    let polygons = sort_children(arr.values().as_ref(), true)?;
+    let arr = ListArray::<O>::new(
+        arr.data_type().clone(),
+        arr.offsets().clone(),
+        polygons,
+        arr.validity().cloned(),
+    );
+    sort_list_children(&arr, false)
+}
+
+/// The elements at `indices` of a native list or coordinate array
+fn take(array: &dyn Array, indices: &[usize]) -> Result<Box<dyn Array>> {
+    let any = array.as_any();
+    if let Some(arr) = any.downcast_ref::<StructArray>() {
+        let (x, y) = coord_buffers(arr)?;
+        let coords = indices
+            .iter()
+            .map(|i| Coord { x: x[*i], y: y[*i] })
+            .collect();
+        Ok(coord_array(arr, coords))
+    } else if let Some(arr) = any.downcast_ref::<ListArray<i64>>() {
+        take_list(arr, indices)
+    } else if let Some(arr) = any.downcast_ref::<ListArray<i32>>() {
+        take_list(arr, indices)
+    } else {
+        Err(unexpected_coord_type(array))
+    }
+}
+
+fn take_list<O: Offset>(arr: &ListArray<O>, indices: &[usize]) -> Result<Box<dyn Array>> {
+    let offsets = arr.offsets().buffer();
+    let ranges = indices
+        .iter()
+        .map(|i| offsets[*i].to_usize()..offsets[*i + 1].to_usize());
+    let child_indices: Vec<usize> = ranges.clone().flatten().collect();
+    let validity = arr.validity().map(|validity| {
+        indices
+            .iter()
+            .map(|i| validity.get_bit(*i))
+            .collect::<Bitmap>()
+    });
+    Ok(ListArray::<O>::new(
+        arr.data_type().clone(),
+        offsets_from_lengths(ranges.map(|range| range.len()))?.into(),
+        take(arr.values().as_ref(), &child_indices)?,
+        validity,
+    )
+    .boxed())
+}
+
+fn offsets_from_lengths<O: Offset>(lengths: impl Iterator<Item = usize>) -> Result<Offsets<O>> {
+    let mut offsets = Offsets::<O>::new();
+    for length in lengths {
+        offsets.try_push_usize(length).map_err(PolarsError::from)?;
+    }
+    Ok(offsets)
+}
+
+/// Collect the coordinates of the elements in `rows` of a native list or coordinate array
+fn flat_coords(
+    array: &dyn Array,
+    rows: std::ops::Range<usize>,
+    coords: &mut Vec<Coord>,
+) -> Result<()> {
+    let any = array.as_any();
+    if let Some(arr) = any.downcast_ref::<StructArray>() {
+        let (x, y) = coord_buffers(arr)?;
+        coords.extend(rows.map(|i| Coord { x: x[i], y: y[i] }));
+        Ok(())
+    } else if let Some(arr) = any.downcast_ref::<ListArray<i64>>() {
+        flat_list_coords(arr, rows, coords)
+    } else if let Some(arr) = any.downcast_ref::<ListArray<i32>>() {
+        flat_list_coords(arr, rows, coords)
+    } else {
+        Err(unexpected_coord_type(array))
+    }
+}
+
+fn flat_list_coords<O: Offset>(
+    arr: &ListArray<O>,
+    rows: std::ops::Range<usize>,
+    coords: &mut Vec<Coord>,
+) -> Result<()> {
+    let offsets = arr.offsets().buffer();
+    let rows = offsets[rows.start].to_usize()..offsets[rows.end].to_usize();
+    flat_coords(arr.values().as_ref(), rows, coords)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::extension::GeoArrowType;
+    use crate::geoseries::GeoSeries;
+    use crate::util::{
+        from_geom_vec, geometries, geometry_array_from_geometries, geometry_series_from_arrays,
+    };
+    use geo::{line_string, polygon, Geometry, MultiLineString, MultiPoint, Polygon};
+    use polars::prelude::Series;
+
+    fn native(geoms: Vec<Geometry>, geometry_type: GeoArrowType) -> Series {
+        let geoms = geoms.into_iter().map(Some).collect();
+        let array = geometry_array_from_geometries(geoms, geometry_type).unwrap();
+        geometry_series_from_arrays(vec![array]).unwrap()
+    }
+
+    fn square_with_hole() -> Polygon {
+        // Both rings are clockwise
+        polygon!(
+            exterior: [(x: 0., y: 0.), (x: 0., y: 4.), (x: 4., y: 4.), (x: 4., y: 0.)],
+            interiors: [[(x: 1., y: 1.), (x: 1., y: 2.), (x: 2., y: 2.), (x: 2., y: 1.)]],
+        )
+    }
+
+    #[test]
+    fn orient_and_reverse() {
+        let expected = Some(Geometry::Polygon(polygon!(
+            exterior: [(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 4.)],
+            interiors: [[(x: 1., y: 1.), (x: 1., y: 2.), (x: 2., y: 2.), (x: 2., y: 1.)]],
+        )));
+        let polygons = vec![Geometry::Polygon(square_with_hole())];
+        for series in [
+            from_geom_vec(&polygons).unwrap(),
+            native(polygons.clone(), GeoArrowType::Polygon),
+        ] {
+            assert_eq!(
+                geometries(&series.orient(true).unwrap()).unwrap(),
+                vec![expected.clone()]
+            );
+            // Reversing twice gives back the input
+            // Called as a function, as `Series` has an inherent method of the same name
+            let reversed = GeoSeries::reverse(&GeoSeries::reverse(&series).unwrap()).unwrap();
+            assert_eq!(geometries(&reversed).unwrap(), geometries(&series).unwrap());
+        }
+
+        let line = native(
+            vec![Geometry::LineString(
+                line_string![(x: 0., y: 0.), (x: 1., y: 2.)],
+            )],
+            GeoArrowType::LineString,
+        );
+        assert_eq!(
+            geometries(&GeoSeries::reverse(&line).unwrap()).unwrap(),
+            vec![Some(Geometry::LineString(
+                line_string![(x: 1., y: 2.), (x: 0., y: 0.)]
+            ))]
+        );
+    }
+
+    #[test]
+    fn normalize() {
+        let geoms = vec![
+            Geometry::MultiLineString(MultiLineString::new(vec![
+                line_string![(x: 1., y: 1.), (x: 0., y: 0.)],
+                line_string![(x: 2., y: 2.), (x: 3., y: 3.)],
+            ])),
+            Geometry::MultiLineString(MultiLineString::new(vec![
+                line_string![(x: 3., y: 3.), (x: 2., y: 2.)],
+                line_string![(x: 0., y: 0.), (x: 1., y: 1.)],
+            ])),
+        ];
+        let expected = Some(Geometry::MultiLineString(MultiLineString::new(vec![
+            line_string![(x: 2., y: 2.), (x: 3., y: 3.)],
+            line_string![(x: 0., y: 0.), (x: 1., y: 1.)],
+        ])));
+        for series in [
+            from_geom_vec(&geoms).unwrap(),
+            native(geoms.clone(), GeoArrowType::MultiLineString),
+        ] {
+            let normalized = geometries(&series.normalize().unwrap()).unwrap();
+            assert_eq!(normalized, vec![expected.clone(), expected.clone()]);
+        }
+
+        // Rings start at their smallest coordinate, with a clockwise exterior
+        let polygon = native(
+            vec![Geometry::Polygon(polygon![
+                (x: 4., y: 4.),
+                (x: 0., y: 4.),
+                (x: 0., y: 0.),
+                (x: 4., y: 0.),
+            ])],
+            GeoArrowType::Polygon,
+        );
+        assert_eq!(
+            geometries(&polygon.normalize().unwrap()).unwrap(),
+            vec![Some(Geometry::Polygon(polygon![
+                (x: 0., y: 0.),
+                (x: 0., y: 4.),
+                (x: 4., y: 4.),
+                (x: 4., y: 0.),
+            ]))]
+        );
+    }
+
+    #[test]
+    fn remove_repeated_points() {
+        let geoms = vec![
+            Geometry::LineString(line_string![
+                (x: 0., y: 0.),
+                (x: 0., y: 0.),
+                (x: 1., y: 0.),
+                (x: 1.05, y: 0.),
+                (x: 2., y: 0.),
+                (x: 2.05, y: 0.),
+            ]),
+            Geometry::MultiPoint(MultiPoint::from(vec![(0., 0.), (0., 0.), (1., 1.)])),
+        ];
+        let series = from_geom_vec(&geoms).unwrap();
+
+        let result = geometries(&series.remove_repeated_points(0.1).unwrap()).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                Some(Geometry::LineString(line_string![
+                    (x: 0., y: 0.),
+                    (x: 1., y: 0.),
+                    (x: 2.05, y: 0.),
+                ])),
+                Some(Geometry::MultiPoint(MultiPoint::from(vec![
+                    (0., 0.),
+                    (1., 1.)
+                ]))),
+            ]
+        );
+        assert!(series.remove_repeated_points(-1.).is_err());
+    }
+}
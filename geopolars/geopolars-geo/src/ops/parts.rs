@@ -0,0 +1,234 @@
+use crate::crs::with_crs_of;
+use crate::error::Result;
+use crate::extension::GeoArrowType;
+use crate::util::{
+    geometries_of_array, geometry_array, geometry_array_from_geometries,
+    geometry_series_from_arrays,
+};
+use geo::{Coord, Geometry, LineString, MultiLineString, MultiPoint, Point, Polygon};
+use polars::prelude::Series;
+use std::iter::once;
+
+pub(crate) fn interiors(series: &Series) -> Result<Series> {
+    map_geometries(
+        series,
+        |geometry_type| match geometry_type {
+            GeoArrowType::WKB => GeoArrowType::WKB,
+            _ => GeoArrowType::MultiLineString,
+        },
+        |geom| match geom {
+            Geometry::Polygon(g) => {
+                let (_, interiors) = g.into_inner();
+                Some(Geometry::MultiLineString(MultiLineString::new(interiors)))
+            }
+            _ => None,
+        },
+    )
+}
+
+pub(crate) fn get_geometry(series: &Series, n: i64) -> Result<Series> {
+    map_geometries(
+        series,
+        |geometry_type| match geometry_type {
+            GeoArrowType::MultiPoint => GeoArrowType::Point,
+            GeoArrowType::MultiLineString => GeoArrowType::LineString,
+            GeoArrowType::MultiPolygon => GeoArrowType::Polygon,
+            geometry_type => geometry_type,
+        },
+        |geom| match geom {
+            Geometry::MultiPoint(g) => nth(g.0, n).map(Geometry::Point),
+            Geometry::MultiLineString(g) => nth(g.0, n).map(Geometry::LineString),
+            Geometry::MultiPolygon(g) => nth(g.0, n).map(Geometry::Polygon),
+            Geometry::GeometryCollection(g) => nth(g.0, n),
+            geom => nth(vec![geom], n),
+        },
+    )
+}
+
+pub(crate) fn get_point(series: &Series, n: i64) -> Result<Series> {
+    map_geometries(
+        series,
+        |_| GeoArrowType::Point,
+        |geom| match geom {
+            Geometry::LineString(g) => nth(g.0, n).map(|c| Geometry::Point(Point(c))),
+            Geometry::Line(g) => nth(vec![g.start, g.end], n).map(|c| Geometry::Point(Point(c))),
+            _ => None,
+        },
+    )
+}
+
+pub(crate) fn boundary(series: &Series) -> Result<Series> {
+    map_geometries(
+        series,
+        |geometry_type| match geometry_type {
+            GeoArrowType::Polygon | GeoArrowType::MultiPolygon => GeoArrowType::MultiLineString,
+            GeoArrowType::WKB => GeoArrowType::WKB,
+            _ => GeoArrowType::MultiPoint,
+        },
+        |geom| match geom {
+            Geometry::Polygon(g) => Some(polygons_boundary(vec![g])),
+            Geometry::MultiPolygon(g) => Some(polygons_boundary(g.0)),
+            Geometry::Rect(g) => Some(polygons_boundary(vec![g.to_polygon()])),
+            Geometry::Triangle(g) => Some(polygons_boundary(vec![g.to_polygon()])),
+            Geometry::LineString(g) => Some(lines_boundary(vec![g])),
+            Geometry::MultiLineString(g) => Some(lines_boundary(g.0)),
+            Geometry::Line(g) => Some(lines_boundary(vec![LineString::new(vec![g.start, g.end])])),
+            Geometry::Point(_) | Geometry::MultiPoint(_) | Geometry::GeometryCollection(_) => None,
+        },
+    )
+}
+
+/// Apply `f` to each geometry of a Series, collecting the outputs of each chunk into an array of
+/// the GeoArrow type given by `output_type` for the type of the chunk
+fn map_geometries<F>(
+    series: &Series,
+    output_type: fn(GeoArrowType) -> GeoArrowType,
+    f: F,
+) -> Result<Series>
+where
+    F: Fn(Geometry<f64>) -> Option<Geometry<f64>>,
+{
+    let output_arrays = series
+        .chunks()
+        .iter()
+        .map(|chunk| {
+            let array = geometry_array(&**chunk)?;
+            let geometry_type = output_type(GeoArrowType::from(&array));
+            let output_geoms = geometries_of_array(array)
+                .into_iter()
+                .map(|maybe_g| maybe_g.and_then(&f))
+                .collect();
+            geometry_array_from_geometries(output_geoms, geometry_type)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    with_crs_of(geometry_series_from_arrays(output_arrays)?, series)
+}
+
+/// The `n`th item, counting from the end for negative `n`
+fn nth<T>(items: Vec<T>, n: i64) -> Option<T> {
+    let index = if n < 0 { items.len() as i64 + n } else { n };
+    let index = usize::try_from(index).ok()?;
+    items.into_iter().nth(index)
+}
+
+/// The rings of polygons, skipping empty ones
+fn polygons_boundary(polygons: Vec<Polygon>) -> Geometry {
+    let rings = polygons
+        .into_iter()
+        .flat_map(|polygon| {
+            let (exterior, interiors) = polygon.into_inner();
+            once(exterior).chain(interiors)
+        })
+        .filter(|ring| !ring.0.is_empty())
+        .collect();
+    Geometry::MultiLineString(MultiLineString::new(rings))
+}
+
+/// The endpoints of lines that end an odd number of them (the "mod 2" rule), so that closed lines
+/// and endpoints shared by two lines are not part of the boundary
+fn lines_boundary(lines: Vec<LineString>) -> Geometry {
+    let mut endpoints: Vec<(Coord, usize)> = vec![];
+    for line in &lines {
+        if let (Some(first), Some(last)) = (line.0.first(), line.0.last()) {
+            for coord in [*first, *last] {
+                match endpoints.iter_mut().find(|(c, _)| *c == coord) {
+                    Some((_, count)) => *count += 1,
+                    None => endpoints.push((coord, 1)),
+                }
+            }
+        }
+    }
+
+    let points = endpoints
+        .into_iter()
+        .filter(|(_, count)| count % 2 == 1)
+        .map(|(coord, _)| Point(coord))
+        .collect();
+    Geometry::MultiPoint(MultiPoint::new(points))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoseries::GeoSeries;
+    use crate::util::{from_geom_vec, geometries};
+    use geo::{line_string, point, polygon, Geometry, LineString, MultiLineString, MultiPoint};
+    use geoarrow::{GeometryArray, GeometryArrayTrait, MultiPointArray};
+    use polars::prelude::Series;
+
+    #[test]
+    fn parts_of_wkb() {
+        let ring = line_string![(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.), (x: 1., y: 1.)];
+        let series = from_geom_vec(&[
+            Geometry::LineString(line_string![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.)]),
+            Geometry::Polygon(polygon!(
+                exterior: [(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.), (x: 0., y: 10.)],
+                interiors: [[(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.)]],
+            )),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            geometries(&series.start_point().unwrap()).unwrap(),
+            vec![Some(Geometry::Point(point!(x: 0., y: 0.))), None]
+        );
+        assert_eq!(
+            geometries(&series.get_point(-2).unwrap()).unwrap(),
+            vec![Some(Geometry::Point(point!(x: 1., y: 0.))), None]
+        );
+        assert_eq!(
+            geometries(&series.interiors().unwrap()).unwrap(),
+            vec![
+                None,
+                Some(Geometry::MultiLineString(MultiLineString::new(vec![
+                    ring.clone()
+                ])))
+            ]
+        );
+
+        let boundary = geometries(&series.boundary().unwrap()).unwrap();
+        assert_eq!(
+            boundary[0],
+            Some(Geometry::MultiPoint(MultiPoint::new(vec![
+                point!(x: 0., y: 0.),
+                point!(x: 1., y: 1.),
+            ])))
+        );
+        match &boundary[1] {
+            Some(Geometry::MultiLineString(g)) => assert_eq!(g.0[1], ring),
+            _ => panic!("Expected a MultiLineString"),
+        }
+    }
+
+    #[test]
+    fn get_geometry_native() {
+        let geoms: Vec<MultiPoint> = vec![
+            vec![point!(x: 0., y: 1.), point!(x: 2., y: -1.)].into(),
+            vec![point!(x: -5., y: 5.)].into(),
+        ];
+        let array: MultiPointArray = geoms.into();
+        let series =
+            Series::try_from(("geometry", GeometryArray::MultiPoint(array).into_arrow())).unwrap();
+
+        // Parts of a multi-part type are of the single-part type
+        let last = series.get_geometry(-1).unwrap();
+        assert_eq!(last.len(), 2);
+        assert_eq!(last.x().unwrap().f64().unwrap().get(0), Some(2.));
+        assert_eq!(last.x().unwrap().f64().unwrap().get(1), Some(-5.));
+
+        let second = series.get_geometry(1).unwrap();
+        assert_eq!(second.y().unwrap().f64().unwrap().get(1), None);
+
+        // The boundary of a line is empty when it is closed
+        let closed = from_geom_vec(&[Geometry::LineString(LineString::from(vec![
+            (0., 0.),
+            (1., 0.),
+            (0., 0.),
+        ]))])
+        .unwrap();
+        assert_eq!(
+            geometries(&closed.boundary().unwrap()).unwrap(),
+            vec![Some(Geometry::MultiPoint(MultiPoint::new(vec![])))]
+        );
+    }
+}
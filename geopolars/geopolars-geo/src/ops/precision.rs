@@ -0,0 +1,664 @@
+use crate::crs::with_crs_of;
+use crate::error::Result;
+use crate::extension::{geoarrow_type, GeoArrowType};
+use crate::ops::interior_point::scanline_interior_point;
+use crate::ops::noding::{node_lines, polygonize_lines};
+use crate::util::{
+    coord_key, geometries, geometry_array_from_geometries, geometry_series_from_arrays, CoordKey,
+};
+use geo::algorithm::{Contains, Intersects};
+use geo::{
+    coord, Coord, CoordsIter, Geometry, GeometryCollection, LineString, MultiPolygon, Point,
+    Polygon, Rect,
+};
+use polars::error::ErrString;
+use polars::prelude::{PolarsError, Series};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::iter::once;
+
+pub(crate) fn set_precision(series: &Series, grid_size: f64) -> Result<Series> {
+    if grid_size.is_nan() || grid_size < 0. {
+        return Err(PolarsError::ComputeError(ErrString::from(
+            "The grid size must not be negative",
+        ))
+        .into());
+    }
+
+    let round = |c: Coord| round_coord(c, grid_size);
+    let transform = Transform {
+        point: &round,
+        line: &|line| {
+            let mut coords: Vec<Coord> = line.coords().map(|c| round(*c)).collect();
+            coords.dedup();
+            (coords.len() >= 2).then(|| LineString::new(coords))
+        },
+        ring: &|ring| {
+            let coords = ring.coords().map(|c| round(*c)).collect();
+            valid_ring(remove_spikes(coords))
+        },
+    };
+
+    let mut output_geoms: Vec<Option<Geometry>> = geometries(series)?
+        .into_iter()
+        .map(|maybe_g| maybe_g.map(|geom| make_valid(transform.apply(geom), grid_size)))
+        .collect();
+
+    // Polygons split by making them valid need a MultiPolygon array
+    let mut geometry_type = geoarrow_type(series)?;
+    if geometry_type == GeoArrowType::Polygon
+        && output_geoms
+            .iter()
+            .any(|geom| matches!(geom, Some(Geometry::MultiPolygon(_))))
+    {
+        geometry_type = GeoArrowType::MultiPolygon;
+        for geom in output_geoms.iter_mut().flatten() {
+            if let Geometry::Polygon(polygon) = geom {
+                let polygons = if polygon.exterior().0.is_empty() {
+                    vec![]
+                } else {
+                    vec![polygon.clone()]
+                };
+                *geom = Geometry::MultiPolygon(MultiPolygon::new(polygons));
+            }
+        }
+    }
+
+    let array = geometry_array_from_geometries(output_geoms, geometry_type)?;
+    with_crs_of(geometry_series_from_arrays(vec![array])?, series)
+}
+
+pub(crate) fn snap(series: &Series, other: &Series, tolerance: f64) -> Result<Series> {
+    if tolerance.is_nan() || tolerance < 0. {
+        return Err(PolarsError::ComputeError(ErrString::from(
+            "The snapping tolerance must not be negative",
+        ))
+        .into());
+    }
+
+    let geoms = geometries(series)?;
+    let references = geometries(other)?;
+    let snap_pair = |geom: &Option<Geometry>, reference: &Option<Geometry>| match (geom, reference)
+    {
+        (Some(geom), Some(reference)) => Some(snap_geometry(geom.clone(), reference, tolerance)),
+        _ => None,
+    };
+
+    let output_geoms = match references.len() {
+        // A single geometry, e.g. from a literal, is snapped to by every row
+        1 => geoms
+            .iter()
+            .map(|geom| snap_pair(geom, &references[0]))
+            .collect(),
+        len if len == geoms.len() => geoms
+            .iter()
+            .zip(references.iter())
+            .map(|(geom, reference)| snap_pair(geom, reference))
+            .collect(),
+        len => {
+            return Err(PolarsError::ShapeMisMatch(ErrString::from(format!(
+                "Cannot snap a GeoSeries of length {} to one of length {}",
+                geoms.len(),
+                len
+            )))
+            .into())
+        }
+    };
+
+    let array = geometry_array_from_geometries(output_geoms, geoarrow_type(series)?)?;
+    with_crs_of(geometry_series_from_arrays(vec![array])?, series)
+}
+
+/// Round a value to the nearest multiple of the grid size, leaving it as is for a grid size of 0
+fn round_to_grid(value: f64, grid_size: f64) -> f64 {
+    if grid_size == 0. {
+        return value;
+    }
+    // Grid sizes like 0.1 aren't exact, so divide by their inverse when that is a whole number
+    let scale = 1. / grid_size;
+    if scale > 1. && (scale - scale.round()).abs() < 1e-9 {
+        (value * scale.round()).round() / scale.round()
+    } else {
+        (value / grid_size).round() * grid_size
+    }
+}
+
+fn round_coord(c: Coord, grid_size: f64) -> Coord {
+    coord! { x: round_to_grid(c.x, grid_size), y: round_to_grid(c.y, grid_size) }
+}
+
+/// How the coordinates, lines and rings of geometries are transformed, where a line or ring that
+/// collapses becomes `None`
+struct Transform<'a> {
+    point: &'a dyn Fn(Coord) -> Coord,
+    line: &'a dyn Fn(&LineString) -> Option<LineString>,
+    ring: &'a dyn Fn(&LineString) -> Option<LineString>,
+}
+
+impl<'a> Transform<'a> {
+    /// Apply the transform to a geometry. Lines and polygons that collapse become empty, and
+    /// collapsed parts of multi-part geometries and holes are dropped.
+    fn apply(&self, geom: Geometry) -> Geometry {
+        let empty_polygon = || Polygon::new(LineString::new(vec![]), vec![]);
+        match geom {
+            Geometry::Point(g) => Geometry::Point((self.point)(g.0).into()),
+            Geometry::MultiPoint(g) => {
+                Geometry::MultiPoint(g.into_iter().map(|point| (self.point)(point.0)).collect())
+            }
+            Geometry::Line(g) => Geometry::LineString(
+                (self.line)(&LineString::new(vec![g.start, g.end]))
+                    .unwrap_or_else(|| LineString::new(vec![])),
+            ),
+            Geometry::LineString(g) => {
+                Geometry::LineString((self.line)(&g).unwrap_or_else(|| LineString::new(vec![])))
+            }
+            Geometry::MultiLineString(mut g) => {
+                g.0 = g.0.iter().filter_map(|line| (self.line)(line)).collect();
+                Geometry::MultiLineString(g)
+            }
+            Geometry::Polygon(g) => {
+                Geometry::Polygon(self.polygon(&g).unwrap_or_else(empty_polygon))
+            }
+            Geometry::MultiPolygon(mut g) => {
+                g.0 =
+                    g.0.iter()
+                        .filter_map(|polygon| self.polygon(polygon))
+                        .collect();
+                Geometry::MultiPolygon(g)
+            }
+            Geometry::Rect(g) => {
+                Geometry::Polygon(self.polygon(&g.to_polygon()).unwrap_or_else(empty_polygon))
+            }
+            Geometry::Triangle(g) => {
+                Geometry::Polygon(self.polygon(&g.to_polygon()).unwrap_or_else(empty_polygon))
+            }
+            Geometry::GeometryCollection(g) => Geometry::GeometryCollection(GeometryCollection(
+                g.into_iter().map(|geom| self.apply(geom)).collect(),
+            )),
+        }
+    }
+
+    fn polygon(&self, polygon: &Polygon) -> Option<Polygon> {
+        let exterior = (self.ring)(polygon.exterior())?;
+        let interiors = polygon
+            .interiors()
+            .iter()
+            .filter_map(|ring| (self.ring)(ring))
+            .collect();
+        Some(Polygon::new(exterior, interiors))
+    }
+}
+
+/// Remove repeated vertices and spikes, where a ring runs to a vertex and straight back, from
+/// the coordinates of a closed ring
+fn remove_spikes(coords: Vec<Coord>) -> Vec<Coord> {
+    let mut open: Vec<Coord> = Vec::with_capacity(coords.len());
+    for coord in coords {
+        open.push(coord);
+        loop {
+            let n = open.len();
+            if n >= 2 && open[n - 1] == open[n - 2] {
+                open.pop();
+            } else if n >= 3 && open[n - 1] == open[n - 3] {
+                open.truncate(n - 2);
+            } else {
+                break;
+            }
+        }
+    }
+
+    // The same, across the start of the ring
+    loop {
+        let n = open.len();
+        if n >= 2 && open[0] == open[n - 1] {
+            open.pop();
+        } else if n >= 3 && open[1] == open[n - 1] {
+            open.remove(0);
+        } else if n >= 3 && open[0] == open[n - 2] {
+            open.pop();
+        } else {
+            break;
+        }
+    }
+
+    if let Some(first) = open.first().copied() {
+        open.push(first);
+    }
+    open
+}
+
+/// A closed ring of the coordinates, or `None` if it has collapsed to a line or a point
+fn valid_ring(coords: Vec<Coord>) -> Option<LineString> {
+    let area: f64 = coords
+        .windows(2)
+        .map(|pair| pair[0].x * pair[1].y - pair[1].x * pair[0].y)
+        .sum();
+    (coords.len() >= 4 && area != 0.).then(|| LineString::new(coords))
+}
+
+/// Rebuild the polygons of a geometry whose rings cross or touch, or that overlap another part
+/// of a MultiPolygon, as valid polygons, like the GEOS `setPrecision`
+fn make_valid(geom: Geometry, grid_size: f64) -> Geometry {
+    match geom {
+        Geometry::Polygon(g) => {
+            let mut polygons = valid_polygons(vec![g], grid_size);
+            match polygons.len() {
+                0 => Geometry::Polygon(Polygon::new(LineString::new(vec![]), vec![])),
+                1 => Geometry::Polygon(polygons.remove(0)),
+                _ => Geometry::MultiPolygon(MultiPolygon::new(polygons)),
+            }
+        }
+        Geometry::MultiPolygon(g) => {
+            Geometry::MultiPolygon(MultiPolygon::new(valid_polygons(g.0, grid_size)))
+        }
+        Geometry::GeometryCollection(g) => Geometry::GeometryCollection(GeometryCollection(
+            g.into_iter()
+                .map(|geom| make_valid(geom, grid_size))
+                .collect(),
+        )),
+        geom => geom,
+    }
+}
+
+/// Valid polygons covering the area inside the exterior and outside the holes of any of the
+/// polygons, where the inside of a ring crossing itself alternates at each crossing
+///
+/// Polygons that are already valid are returned as they are. Otherwise the rings are noded,
+/// snap rounded to the grid so that the vertices added where they cross are on the grid too,
+/// and the faces they enclose that are inside the polygons are merged.
+fn valid_polygons(polygons: Vec<Polygon>, grid_size: f64) -> Vec<Polygon> {
+    let polygons: Vec<Polygon> = polygons
+        .into_iter()
+        .filter(|polygon| !polygon.exterior().0.is_empty())
+        .collect();
+    let rings: Vec<LineString> = polygons
+        .iter()
+        .flat_map(|polygon| once(polygon.exterior()).chain(polygon.interiors()))
+        .cloned()
+        .collect();
+    let noded = node_lines(&rings);
+    if noded.len() == rings.len() && noded.iter().all(LineString::is_closed) && is_nested(&polygons)
+    {
+        return polygons;
+    }
+
+    let rings = if grid_size > 0. {
+        snap_round(&rings, &noded, grid_size)
+    } else {
+        rings
+    };
+    let mut rings = rings.into_iter();
+    let polygons: Vec<Polygon> = polygons
+        .iter()
+        .filter_map(|polygon| {
+            let exterior = rings.next()?;
+            let interiors = rings.by_ref().take(polygon.interiors().len()).collect();
+            Some(Polygon::new(exterior, interiors))
+        })
+        .collect();
+    let is_inside = |face: &Polygon| {
+        scanline_interior_point(face).map_or(false, |(point, _)| {
+            polygons.iter().any(|polygon| {
+                is_inside_ring(polygon.exterior(), point.0)
+                    && !polygon
+                        .interiors()
+                        .iter()
+                        .any(|ring| is_inside_ring(ring, point.0))
+            })
+        })
+    };
+
+    let rings: Vec<LineString> = polygons
+        .iter()
+        .flat_map(|polygon| once(polygon.exterior()).chain(polygon.interiors()))
+        .cloned()
+        .collect();
+    let faces: Vec<Polygon> = polygonize_lines(&segments(node_lines(&rings).iter()))
+        .into_iter()
+        .filter(|face| is_inside(face))
+        .collect();
+
+    // Merge the faces by dropping the edges between them, and polygonizing again
+    let mut edges: HashMap<[CoordKey; 2], (usize, LineString)> = HashMap::new();
+    let face_rings = faces
+        .iter()
+        .flat_map(|face| once(face.exterior()).chain(face.interiors()));
+    for segment in segments(face_rings) {
+        let mut key = [coord_key(&segment.0[0]), coord_key(&segment.0[1])];
+        key.sort_unstable();
+        edges.entry(key).or_insert((0, segment)).0 += 1;
+    }
+    let boundary: Vec<LineString> = edges
+        .into_values()
+        .filter(|(count, _)| *count == 1)
+        .map(|(_, segment)| segment)
+        .collect();
+    polygonize_lines(&boundary)
+        .into_iter()
+        .filter(|face| is_inside(face))
+        .collect()
+}
+
+/// Whether polygons whose rings don't cross or touch are valid, with their holes inside their
+/// exterior and outside each other, and no polygon inside another
+fn is_nested(polygons: &[Polygon]) -> bool {
+    let first_point = |ring: &LineString| Point(ring.0[0]);
+    let holes_nested = polygons.iter().all(|polygon| {
+        let exterior = Polygon::new(polygon.exterior().clone(), vec![]);
+        polygon.interiors().iter().enumerate().all(|(i, hole)| {
+            exterior.contains(&first_point(hole))
+                && polygon.interiors().iter().enumerate().all(|(j, other)| {
+                    i == j || !Polygon::new(other.clone(), vec![]).contains(&first_point(hole))
+                })
+        })
+    });
+    holes_nested
+        && polygons.iter().enumerate().all(|(i, polygon)| {
+            polygons
+                .iter()
+                .enumerate()
+                .all(|(j, other)| i == j || !other.contains(&first_point(polygon.exterior())))
+        })
+}
+
+/// Snap round rings with vertices on the grid, like the GEOS `SnapRoundingNoder`
+///
+/// Every vertex of the noded rings, including the points where they cross, is rounded to the
+/// centre of a hot pixel, a grid cell. Each segment passing through a hot pixel gets its centre
+/// as a vertex, after which segments only meet at their vertices.
+fn snap_round(rings: &[LineString], noded: &[LineString], grid_size: f64) -> Vec<LineString> {
+    let mut seen = HashSet::new();
+    let mut hot_pixels: Vec<Coord> = noded
+        .iter()
+        .flat_map(|line| line.0.iter())
+        .map(|c| round_coord(*c, grid_size))
+        .filter(|c| seen.insert(coord_key(c)))
+        .collect();
+    hot_pixels.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap_or(Ordering::Equal));
+
+    let half = grid_size / 2.;
+    rings
+        .iter()
+        .map(|ring| {
+            let mut coords = vec![ring.0[0]];
+            for segment in ring.lines() {
+                let min_x = segment.start.x.min(segment.end.x) - half;
+                let max_x = segment.start.x.max(segment.end.x) + half;
+                let first = hot_pixels.partition_point(|c| c.x < min_x);
+                let along = |c: &Coord| {
+                    let (offset, delta) = (*c - segment.start, segment.end - segment.start);
+                    offset.x * delta.x + offset.y * delta.y
+                };
+                let mut inserted: Vec<(f64, Coord)> = hot_pixels[first..]
+                    .iter()
+                    .take_while(|c| c.x <= max_x)
+                    .filter(|c| **c != segment.start && **c != segment.end)
+                    .filter(|c| {
+                        let pixel = Rect::new(
+                            coord! { x: c.x - half, y: c.y - half },
+                            coord! { x: c.x + half, y: c.y + half },
+                        );
+                        segment.intersects(&pixel)
+                    })
+                    .map(|c| (along(c), *c))
+                    .collect();
+                inserted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+                coords.extend(inserted.into_iter().map(|(_, c)| c));
+                coords.push(segment.end);
+            }
+            coords.dedup();
+            LineString::new(coords)
+        })
+        .collect()
+}
+
+/// The distinct segments of lines, as lines of two coordinates
+fn segments<'a>(lines: impl Iterator<Item = &'a LineString>) -> Vec<LineString> {
+    let mut seen = HashSet::new();
+    lines
+        .flat_map(|line| line.lines())
+        .filter(|segment| segment.start != segment.end)
+        .filter(|segment| {
+            let mut key = [coord_key(&segment.start), coord_key(&segment.end)];
+            key.sort_unstable();
+            seen.insert(key)
+        })
+        .map(|segment| LineString::new(vec![segment.start, segment.end]))
+        .collect()
+}
+
+/// Whether a coordinate is inside a ring by the even-odd rule, counting the crossings of a ray
+/// from it to the right
+fn is_inside_ring(ring: &LineString, c: Coord) -> bool {
+    ring.lines()
+        .filter(|line| (line.start.y > c.y) != (line.end.y > c.y))
+        .filter(|line| c.x < line.start.x + (c.y - line.start.y) * line.dx() / line.dy())
+        .count()
+        % 2
+        == 1
+}
+
+/// Snap the vertices of a geometry to the vertices of a reference geometry within `tolerance`,
+/// like the GEOS `GeometrySnapper`
+///
+/// Each vertex moves to the nearest reference vertex within the tolerance. Reference vertices
+/// within the tolerance of a segment that doesn't already have them as a vertex are then
+/// inserted into it, so that edges shared up to the tolerance end up with the same vertices.
+fn snap_geometry(geom: Geometry, reference: &Geometry, tolerance: f64) -> Geometry {
+    let targets: Vec<Coord> = reference.coords_iter().collect();
+    let snap_coord = |c: Coord| {
+        targets
+            .iter()
+            .map(|target| (distance(c, *target), *target))
+            .filter(|(d, _)| *d <= tolerance)
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal))
+            .map_or(c, |(_, target)| target)
+    };
+    let snap_line = |line: &LineString| {
+        let snapped: Vec<Coord> = line.coords().map(|c| snap_coord(*c)).collect();
+        let vertices: HashSet<_> = snapped.iter().map(coord_key).collect();
+
+        let mut coords = Vec::with_capacity(snapped.len());
+        for pair in snapped.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            coords.push(start);
+            let mut inserted: Vec<(f64, Coord)> = targets
+                .iter()
+                .filter(|target| !vertices.contains(&coord_key(target)))
+                .filter_map(|target| {
+                    let (fraction, nearest) = project(*target, start, end)?;
+                    (distance(nearest, *target) <= tolerance).then(|| (fraction, *target))
+                })
+                .collect();
+            inserted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+            coords.extend(inserted.into_iter().map(|(_, target)| target));
+        }
+        coords.extend(snapped.last());
+        coords.dedup();
+        coords
+    };
+
+    let transform = Transform {
+        point: &snap_coord,
+        line: &|line| {
+            let coords = snap_line(line);
+            (coords.len() >= 2).then(|| LineString::new(coords))
+        },
+        ring: &|ring| valid_ring(remove_spikes(snap_line(ring))),
+    };
+    transform.apply(geom)
+}
+
+fn distance(a: Coord, b: Coord) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+/// The fraction along the segment from `start` to `end` of the point of it nearest to `c`, and
+/// that point, or `None` if it is one of the ends of the segment
+fn project(c: Coord, start: Coord, end: Coord) -> Option<(f64, Coord)> {
+    let delta = end - start;
+    let squared_length = delta.x * delta.x + delta.y * delta.y;
+    if squared_length == 0. {
+        return None;
+    }
+    let offset = c - start;
+    let fraction = (offset.x * delta.x + offset.y * delta.y) / squared_length;
+    (fraction > 0. && fraction < 1.).then(|| (fraction, start + delta * fraction))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::extension::{geoarrow_type, GeoArrowType};
+    use crate::geoseries::GeoSeries;
+    use crate::util::{from_geom_vec, geometries};
+    use geo::prelude::Area;
+    use geo::{coord, line_string, polygon, Geometry, Intersects, Line, LineString, Polygon};
+
+    #[test]
+    fn set_precision() {
+        let series = from_geom_vec(&[
+            Geometry::LineString(line_string![
+                (x: 0.30000001, y: 0.),
+                (x: 0.29999999, y: 0.),
+                (x: 1.04, y: 2.),
+            ]),
+            // Noise around a square, including a spike
+            Geometry::Polygon(polygon![
+                (x: 0., y: 0.),
+                (x: 1.0000001, y: 0.),
+                (x: 1., y: 0.0000001),
+                (x: 1., y: 1.),
+                (x: 1., y: 2.),
+                (x: 1., y: 1.),
+                (x: 0., y: 1.),
+            ]),
+            // A sliver that collapses
+            Geometry::Polygon(polygon![
+                (x: 0., y: 0.),
+                (x: 1., y: 0.),
+                (x: 1., y: 0.001),
+                (x: 0., y: 0.001),
+            ]),
+        ])
+        .unwrap();
+
+        let result = geometries(&series.set_precision(0.1).unwrap()).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                Some(Geometry::LineString(
+                    line_string![(x: 0.3, y: 0.), (x: 1., y: 2.)]
+                )),
+                Some(Geometry::Polygon(polygon![
+                    (x: 0., y: 0.),
+                    (x: 1., y: 0.),
+                    (x: 1., y: 1.),
+                    (x: 0., y: 1.),
+                ])),
+                Some(Geometry::Polygon(Polygon::new(
+                    LineString::new(vec![]),
+                    vec![]
+                ))),
+            ]
+        );
+
+        assert!(series.set_precision(-1.).is_err());
+    }
+
+    #[test]
+    fn set_precision_valid() {
+        let series = from_geom_vec(&[
+            // A bow-tie crossing at (1.2, 1.2)
+            Geometry::Polygon(polygon![
+                (x: 0., y: 0.),
+                (x: 2., y: 2.),
+                (x: 2., y: 0.),
+                (x: 0., y: 3.),
+            ]),
+            // (3, 1.4) lies above the bottom edge, but rounds to below it
+            Geometry::Polygon(polygon![
+                (x: 0., y: 0.),
+                (x: 10., y: 3.6),
+                (x: 10., y: 10.),
+                (x: 3., y: 1.4),
+            ]),
+        ])
+        .unwrap();
+
+        let result = series.set_precision(1.).unwrap();
+        assert_eq!(geoarrow_type(&result).unwrap(), GeoArrowType::MultiPolygon);
+        let result = geometries(&result).unwrap();
+
+        let polygons = |geom: &Option<Geometry>| match geom {
+            Some(Geometry::MultiPolygon(g)) => g.0.clone(),
+            _ => panic!("Expected a MultiPolygon"),
+        };
+        let bow_tie = polygons(&result[0]);
+        assert_eq!(bow_tie.len(), 2);
+        let mut areas: Vec<f64> = bow_tie
+            .iter()
+            .map(|polygon| polygon.unsigned_area())
+            .collect();
+        areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(areas, vec![1., 1.5]);
+        // The crossing is snapped to (1, 1), a vertex of both parts
+        assert!(bow_tie
+            .iter()
+            .all(|polygon| polygon.exterior().0.contains(&coord! { x: 1., y: 1. })));
+
+        // The spike from (0, 0) to (3, 1) is dropped, leaving the triangle above it
+        let quadrilateral = polygons(&result[1]);
+        assert_eq!(quadrilateral.len(), 1);
+        assert_eq!(quadrilateral[0].unsigned_area(), 21.);
+
+        for polygon in bow_tie.iter().chain(&quadrilateral) {
+            assert!(polygon.interiors().is_empty());
+            assert!(is_simple(polygon.exterior()));
+            assert!(polygon
+                .exterior()
+                .coords()
+                .all(|c| c.x.fract() == 0. && c.y.fract() == 0.));
+        }
+    }
+
+    /// Whether no two segments of a ring meet other than consecutive ones at their shared vertex
+    fn is_simple(ring: &LineString) -> bool {
+        let segments: Vec<Line> = ring.lines().collect();
+        let n = segments.len();
+        (0..n).all(|i| {
+            (i + 2..n).all(|j| (i == 0 && j == n - 1) || !segments[i].intersects(&segments[j]))
+        })
+    }
+
+    #[test]
+    fn snap() {
+        let series = from_geom_vec(&[Geometry::Polygon(polygon![
+            (x: 0., y: 0.),
+            (x: 1., y: 0.),
+            (x: 1., y: 1.),
+            (x: 0., y: 1.),
+        ])])
+        .unwrap();
+        // A neighbour across a sliver gap, with an extra vertex along the shared edge
+        let other = from_geom_vec(&[Geometry::Polygon(polygon![
+            (x: 1.001, y: 0.),
+            (x: 2., y: 0.),
+            (x: 2., y: 1.),
+            (x: 1.001, y: 1.),
+            (x: 1.001, y: 0.5),
+        ])])
+        .unwrap();
+
+        let result = geometries(&series.snap(&other, 0.01).unwrap()).unwrap();
+        assert_eq!(
+            result,
+            vec![Some(Geometry::Polygon(polygon![
+                (x: 0., y: 0.),
+                (x: 1.001, y: 0.),
+                (x: 1.001, y: 0.5),
+                (x: 1.001, y: 1.),
+                (x: 0., y: 1.),
+            ]))]
+        );
+    }
+}
@@ -1,20 +1,86 @@
-use crate::error::Result;
-use crate::util::from_geom_vec;
-use crate::util::iter_geom;
-use geo::Geometry;
-use polars::prelude::Series;
-use proj::Transform;
+use crate::crs::{crs, set_crs};
+use crate::error::{GeopolarsError, Result};
+use crate::extension::{chunk_geoarrow_type, GeoArrowType};
+use crate::util::{map_coords, map_wkb, series_like};
+use geo::Coord;
+use polars::error::ErrString;
+use polars::prelude::{PolarsError, Series};
+use proj::{Proj, ProjBuilder, Transform};
+use proj_sys::{
+    proj_as_proj_string, proj_context_create, proj_context_destroy, proj_context_errno,
+    proj_context_errno_string, proj_context_set_enable_network, proj_context_set_search_paths,
+    proj_coordoperation_get_accuracy, proj_coordoperation_get_grid_used,
+    proj_coordoperation_get_grid_used_count, proj_coordoperation_has_ballpark_transformation,
+    proj_coordoperation_is_instantiable, proj_create, proj_create_operation_factory_context,
+    proj_create_operations, proj_destroy, proj_get_name, proj_list_destroy, proj_list_get,
+    proj_list_get_count, proj_normalize_for_visualization, proj_operation_factory_context_destroy,
+    proj_operation_factory_context_set_area_of_interest,
+    proj_operation_factory_context_set_grid_availability_use,
+    proj_operation_factory_context_set_spatial_criterion, PJ, PJ_CONTEXT,
+    PJ_PROJ_STRING_TYPE_PJ_PROJ_5,
+    PROJ_GRID_AVAILABILITY_USE_PROJ_GRID_AVAILABILITY_DISCARD_OPERATION_IF_MISSING_GRID,
+    PROJ_SPATIAL_CRITERION_PROJ_SPATIAL_CRITERION_PARTIAL_INTERSECTION,
+};
+use rayon::prelude::*;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
 use std::path::PathBuf;
-
-use proj::ProjBuilder;
+use std::ptr;
 
 /// Options to be passed to ProjBuilder
-/// We use a custom ProjOptions struct instead of accepting ProjBuilder so that we can more easily
-/// use multithreading in the future.
-#[derive(Default, Clone)]
+/// We use a custom ProjOptions struct instead of accepting ProjBuilder because a Proj can't be
+/// shared across threads: reprojection creates one Proj per worker thread from these options.
+///
+/// Grid files for datum shifts are only looked up locally, in `search_paths` and PROJ's own data
+/// directory: network access to the PROJ CDN is disabled while selecting a transformation, and
+/// transformations whose grids are not available locally are never selected.
+#[derive(Clone)]
 pub struct ProjOptions {
     /// Search paths to set through PROJ
     pub search_paths: Option<Vec<PathBuf>>,
+
+    /// Area of interest as `[west, south, east, north]` in degrees. When several transformations
+    /// between two CRSs exist, the most relevant one valid over this area is selected.
+    pub area_of_interest: Option<[f64; 4]>,
+
+    /// Required accuracy of the transformation in metres. Transformations with a worse or
+    /// unknown accuracy are rejected.
+    pub accuracy: Option<f64>,
+
+    /// Whether ballpark transformations, which ignore datum differences, may be used. Defaults to
+    /// `true`, matching PROJ.
+    pub allow_ballpark: bool,
+
+    /// Short names of the grid files, e.g. `ch_swisstopo_CHENyx06_ETRS.tif`, that the
+    /// transformation may use. Transformations using any other grid are rejected, so an empty
+    /// list only allows transformations without grids. Defaults to `None`, allowing any grid
+    /// available locally.
+    pub grids: Option<Vec<String>>,
+}
+
+impl Default for ProjOptions {
+    fn default() -> Self {
+        Self {
+            search_paths: None,
+            area_of_interest: None,
+            accuracy: None,
+            allow_ballpark: true,
+            grids: None,
+        }
+    }
+}
+
+/// Description of the transformation PROJ selected between two CRSs
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransformationInfo {
+    /// The PROJ pipeline string of the transformation
+    pub definition: String,
+
+    /// Human readable name of the transformation
+    pub description: Option<String>,
+
+    /// Accuracy of the transformation in metres, if known
+    pub accuracy: Option<f64>,
 }
 
 impl ProjOptions {
@@ -28,43 +94,484 @@ impl ProjOptions {
         }
         Ok(builder)
     }
+
+    /// Select the transformation from `from` to `to`
+    ///
+    /// The candidate operations are enumerated by PROJ, most relevant first, discarding those
+    /// whose grids are missing. The first candidate that meets the required accuracy, is not a
+    /// ballpark transformation if those are disallowed, and only uses the selected grids is
+    /// returned.
+    pub fn select_transformation(&self, from: &str, to: &str) -> Result<TransformationInfo> {
+        let ctx = Context::new(self.search_paths.as_deref())?;
+        let source = ctx.create_crs(from)?;
+        let target = ctx.create_crs(to)?;
+
+        let candidates = ctx.create_operations(&source, &target, self.area_of_interest);
+        let mut reasons = vec![];
+        for operation in &candidates {
+            let info = operation.info();
+            match self.rejection(operation, &info) {
+                Some(reason) => reasons.push(format!("{}: {}", info.name, reason)),
+                None => match operation.normalized_definition() {
+                    Some(definition) => {
+                        return Ok(TransformationInfo {
+                            definition,
+                            description: Some(info.name),
+                            accuracy: info.accuracy,
+                        })
+                    }
+                    None => reasons.push(format!(
+                        "{}: can't be expressed as a PROJ pipeline",
+                        info.name
+                    )),
+                },
+            }
+        }
+
+        Err(GeopolarsError::UnsuitableTransformation {
+            from: from.to_string(),
+            to: to.to_string(),
+            reason: if reasons.is_empty() {
+                "PROJ found no transformation with the available grids".to_string()
+            } else {
+                reasons.join("; ")
+            },
+        })
+    }
+
+    /// Create the transformation from `from` to `to` selected by `select_transformation`
+    pub fn create_transformation(&self, from: &str, to: &str) -> Result<Proj> {
+        let info = self.select_transformation(from, to)?;
+        Ok(self.to_proj_builder()?.proj(&info.definition)?)
+    }
+
+    /// The reason `operation` is unsuitable, if any
+    fn rejection(&self, operation: &Operation, info: &OperationInfo) -> Option<String> {
+        if !operation.is_instantiable() {
+            return Some("can't be instantiated".to_string());
+        }
+        if !self.allow_ballpark && operation.is_ballpark() {
+            return Some("ballpark transformation".to_string());
+        }
+        if let Some(required) = self.accuracy {
+            match info.accuracy {
+                Some(accuracy) if accuracy <= required => {}
+                Some(accuracy) => {
+                    return Some(format!("accuracy of {accuracy} m, {required} m required"))
+                }
+                None => return Some(format!("unknown accuracy, {required} m required")),
+            }
+        }
+        if let Some(grids) = &self.grids {
+            if let Some(grid) = info.grids.iter().find(|grid| !grids.contains(grid)) {
+                return Some(format!("uses grid {grid}, which is not selected"));
+            }
+        }
+        None
+    }
+}
+
+/// A PROJ context to enumerate candidate operations in, with network access disabled
+struct Context(*mut PJ_CONTEXT);
+
+impl Context {
+    fn new(search_paths: Option<&[PathBuf]>) -> Result<Self> {
+        // SAFETY: the context is owned by the returned value, which destroys it on drop
+        let ctx = Self(unsafe { proj_context_create() });
+        unsafe { proj_context_set_enable_network(ctx.0, 0) };
+
+        if let Some(search_paths) = search_paths {
+            let paths = search_paths
+                .iter()
+                .map(|path| c_string(&path.to_string_lossy()))
+                .collect::<Result<Vec<_>>>()?;
+            let pointers: Vec<*const c_char> = paths.iter().map(|path| path.as_ptr()).collect();
+            // SAFETY: PROJ copies the paths, which outlive the call
+            unsafe {
+                proj_context_set_search_paths(ctx.0, pointers.len() as c_int, pointers.as_ptr())
+            };
+        }
+        Ok(ctx)
+    }
+
+    fn create_crs(&self, definition: &str) -> Result<Object> {
+        let c_definition = c_string(definition)?;
+        // SAFETY: the definition outlives the call, and the object is destroyed on drop
+        let ptr = unsafe { proj_create(self.0, c_definition.as_ptr()) };
+        if ptr.is_null() {
+            return Err(PolarsError::ComputeError(ErrString::from(format!(
+                "Invalid CRS {}: {}",
+                definition,
+                self.last_error()
+            )))
+            .into());
+        }
+        Ok(Object { ctx: self, ptr })
+    }
+
+    /// The candidate operations from `source` to `target`, most relevant first
+    fn create_operations(
+        &self,
+        source: &Object,
+        target: &Object,
+        area_of_interest: Option<[f64; 4]>,
+    ) -> Vec<Operation> {
+        // SAFETY: every object created here is destroyed before returning, except for the
+        // operations, which are destroyed on drop
+        unsafe {
+            let factory = proj_create_operation_factory_context(self.0, ptr::null());
+            if let Some([west, south, east, north]) = area_of_interest {
+                proj_operation_factory_context_set_area_of_interest(
+                    self.0, factory, west, south, east, north,
+                );
+            }
+            proj_operation_factory_context_set_spatial_criterion(
+                self.0,
+                factory,
+                PROJ_SPATIAL_CRITERION_PROJ_SPATIAL_CRITERION_PARTIAL_INTERSECTION,
+            );
+            proj_operation_factory_context_set_grid_availability_use(
+                self.0,
+                factory,
+                PROJ_GRID_AVAILABILITY_USE_PROJ_GRID_AVAILABILITY_DISCARD_OPERATION_IF_MISSING_GRID,
+            );
+
+            let list = proj_create_operations(self.0, source.ptr, target.ptr, factory);
+            let operations = if list.is_null() {
+                vec![]
+            } else {
+                (0..proj_list_get_count(list))
+                    .map(|index| Object {
+                        ctx: self,
+                        ptr: proj_list_get(self.0, list, index),
+                    })
+                    .filter(|operation| !operation.ptr.is_null())
+                    .map(Operation)
+                    .collect()
+            };
+
+            proj_list_destroy(list);
+            proj_operation_factory_context_destroy(factory);
+            operations
+        }
+    }
+
+    /// The message of the last error in the context
+    fn last_error(&self) -> String {
+        // SAFETY: PROJ returns a static or context owned string, which is copied
+        unsafe {
+            let message = proj_context_errno_string(self.0, proj_context_errno(self.0));
+            if message.is_null() {
+                "unknown PROJ error".to_string()
+            } else {
+                CStr::from_ptr(message).to_string_lossy().into_owned()
+            }
+        }
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        // SAFETY: all objects of the context borrow it, so they were destroyed before
+        unsafe { proj_context_destroy(self.0) }
+    }
+}
+
+/// A PROJ object created in a [`Context`], destroyed on drop
+struct Object<'a> {
+    ctx: &'a Context,
+    ptr: *mut PJ,
+}
+
+impl Drop for Object<'_> {
+    fn drop(&mut self) {
+        // SAFETY: the object is owned by this value and its context is still alive
+        unsafe { proj_destroy(self.ptr) };
+    }
+}
+
+/// A candidate operation between two CRSs
+struct Operation<'a>(Object<'a>);
+
+/// What [`ProjOptions`] filters candidate operations on
+struct OperationInfo {
+    name: String,
+    accuracy: Option<f64>,
+    /// The short names of the grid files the operation uses
+    grids: Vec<String>,
+}
+
+impl Operation<'_> {
+    fn ctx(&self) -> *mut PJ_CONTEXT {
+        self.0.ctx.0
+    }
+
+    fn info(&self) -> OperationInfo {
+        // SAFETY: the strings returned by PROJ are owned by the operation, and copied
+        unsafe {
+            let name = proj_get_name(self.0.ptr);
+            let name = if name.is_null() {
+                "unnamed operation".to_string()
+            } else {
+                CStr::from_ptr(name).to_string_lossy().into_owned()
+            };
+
+            // PROJ reports -1 for an unknown accuracy
+            let accuracy = proj_coordoperation_get_accuracy(self.ctx(), self.0.ptr);
+
+            let grids = (0..proj_coordoperation_get_grid_used_count(self.ctx(), self.0.ptr))
+                .filter_map(|index| {
+                    let mut short_name = ptr::null();
+                    let mut full_name = ptr::null();
+                    let mut package_name = ptr::null();
+                    let mut url = ptr::null();
+                    let mut direct_download = 0;
+                    let mut open_license = 0;
+                    let mut available = 0;
+                    let found = proj_coordoperation_get_grid_used(
+                        self.ctx(),
+                        self.0.ptr,
+                        index,
+                        &mut short_name,
+                        &mut full_name,
+                        &mut package_name,
+                        &mut url,
+                        &mut direct_download,
+                        &mut open_license,
+                        &mut available,
+                    );
+                    if found == 0 || short_name.is_null() {
+                        None
+                    } else {
+                        Some(CStr::from_ptr(short_name).to_string_lossy().into_owned())
+                    }
+                })
+                .collect();
+
+            OperationInfo {
+                name,
+                accuracy: if accuracy >= 0. { Some(accuracy) } else { None },
+                grids,
+            }
+        }
+    }
+
+    fn is_instantiable(&self) -> bool {
+        // SAFETY: the operation and its context are alive
+        unsafe { proj_coordoperation_is_instantiable(self.ctx(), self.0.ptr) != 0 }
+    }
+
+    fn is_ballpark(&self) -> bool {
+        // SAFETY: the operation and its context are alive
+        unsafe { proj_coordoperation_has_ballpark_transformation(self.ctx(), self.0.ptr) != 0 }
+    }
+
+    /// The PROJ pipeline of the operation, taking and returning coordinates in x/y (longitude,
+    /// latitude) order like `Proj::new_known_crs`
+    fn normalized_definition(&self) -> Option<String> {
+        // SAFETY: the normalized operation is destroyed on drop, after its string is copied
+        unsafe {
+            let normalized = Object {
+                ctx: self.0.ctx,
+                ptr: proj_normalize_for_visualization(self.ctx(), self.0.ptr),
+            };
+            if normalized.ptr.is_null() {
+                return None;
+            }
+            let definition = proj_as_proj_string(
+                self.ctx(),
+                normalized.ptr,
+                PJ_PROJ_STRING_TYPE_PJ_PROJ_5,
+                ptr::null(),
+            );
+            if definition.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(definition).to_string_lossy().into_owned())
+            }
+        }
+    }
+}
+
+fn c_string(value: &str) -> Result<CString> {
+    CString::new(value).map_err(|_| {
+        PolarsError::ComputeError(ErrString::from(format!(
+            "{value:?} contains a nul byte and can't be passed to PROJ"
+        )))
+        .into()
+    })
 }
 
-pub(crate) fn to_crs(series: &Series, from: &str, to: &str) -> Result<Series> {
-    to_crs_with_options(series, from, to, ProjOptions::default())
+pub(crate) fn to_crs(series: &Series, to: &str) -> Result<Series> {
+    to_crs_with_options(series, to, ProjOptions::default())
 }
 
 pub(crate) fn to_crs_with_options(
     series: &Series,
-    from: &str,
     to: &str,
     proj_options: ProjOptions,
 ) -> Result<Series> {
-    to_crs_with_options_wkb(series, from, to, proj_options)
+    to_crs_with_info(series, to, proj_options).map(|(output, _)| output)
 }
 
-fn to_crs_with_options_wkb(
+pub(crate) fn to_crs_with_info(
     series: &Series,
-    from: &str,
     to: &str,
     proj_options: ProjOptions,
-) -> Result<Series> {
-    let proj = proj_options
-        .to_proj_builder()?
-        .proj_known_crs(from, to, None)?;
-
-    // Specify literal Result<> to propagate error from within closure
-    // https://stackoverflow.com/a/26370894
-    let output_vec: Result<Vec<Geometry>> = iter_geom(series)
-        .map(|mut geom| {
-            // geom.tranform modifies `geom` in place.
-            // Note that this doesn't modify the _original series_ because iter_geom makes a
-            // copy
-            // https://docs.rs/proj/latest/proj/#integration-with-geo-types
-            geom.transform(&proj)?;
-            Ok(geom)
+) -> Result<(Series, TransformationInfo)> {
+    let from = crs(series)?.ok_or(GeopolarsError::MissingCrs)?;
+    let (output, info) = transform(series, &from, to, &proj_options)?;
+    Ok((set_crs(&output, Some(to))?, info))
+}
+
+/// Number of coordinates transformed by one task of a native geometry array
+const COORDS_PER_TASK: usize = 16_384;
+
+/// Transform every chunk of a geometry Series from `from` to `to`
+///
+/// Rows are processed in parallel, with one Proj per worker thread. The coordinates of native
+/// GeoArrow arrays are copied out of their buffers and transformed in bulk without building
+/// geometries; WKB geometries are decoded, transformed and re-encoded.
+fn transform(
+    series: &Series,
+    from: &str,
+    to: &str,
+    proj_options: &ProjOptions,
+) -> Result<(Series, TransformationInfo)> {
+    // Select and create the transformation once on the calling thread so that an invalid CRS or
+    // rejected transformation is reported before any work is spread over the worker threads
+    let info = proj_options.select_transformation(from, to)?;
+    let new_proj =
+        || -> Result<Proj> { Ok(proj_options.to_proj_builder()?.proj(&info.definition)?) };
+    new_proj()?;
+
+    let output_chunks = series
+        .chunks()
+        .iter()
+        .map(|chunk| match chunk_geoarrow_type(&**chunk)? {
+            GeoArrowType::WKB => map_wkb(&**chunk, new_proj, |proj, mut geom| {
+                // geom.tranform modifies `geom` in place; it's a copy decoded from the WKB
+                // https://docs.rs/proj/latest/proj/#integration-with-geo-types
+                geom.transform(worker_proj(proj)?)?;
+                Ok(geom)
+            }),
+            _ => map_coords(&**chunk, &mut |coords: &mut [Coord<f64>]| {
+                coords.par_chunks_mut(COORDS_PER_TASK).try_for_each_init(
+                    new_proj,
+                    |proj, coords| {
+                        worker_proj(proj)?.convert_array(coords)?;
+                        Ok(())
+                    },
+                )
+            }),
         })
-        .collect();
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((series_like(series, output_chunks)?, info))
+}
+
+/// The Proj of a worker thread, as created by the `init` closure of a rayon `*_init` method
+///
+/// A failed creation is kept in the worker's state, so its error is reported as a copy.
+fn worker_proj(proj: &Result<Proj>) -> Result<&Proj> {
+    proj.as_ref()
+        .map_err(|err| PolarsError::ComputeError(ErrString::from(err.to_string())).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProjOptions;
+    use crate::error::GeopolarsError;
+    use crate::geoseries::GeoSeries;
+    use crate::util::from_geom_vec;
+    use geo::{line_string, point, Geometry, LineString};
+    use geoarrow::{GeometryArray, GeometryArrayTrait, LineStringArray, PointArray};
+    use polars::prelude::{DataType, Series};
+
+    #[test]
+    fn to_crs_wkb() {
+        let series = from_geom_vec(&[Geometry::Point(point!(x: 1., y: 0.))])
+            .unwrap()
+            .set_crs("EPSG:4326")
+            .unwrap();
+
+        let output = series.to_crs("EPSG:3857").unwrap();
+        assert_eq!(output.crs().unwrap(), Some("EPSG:3857".to_string()));
 
-    from_geom_vec(&output_vec?)
+        let geom = crate::util::iter_geom(&output).next().unwrap();
+        let point = geo::Point::try_from(geom).unwrap();
+        assert!((point.x() - 111_319.49).abs() < 0.01);
+        assert!(point.y().abs() < 1e-6);
+    }
+
+    #[test]
+    fn to_crs_native() {
+        let input_geom: LineString = line_string![(x: 0., y: 0.), (x: 1., y: 0.)];
+        let input_array: LineStringArray = vec![input_geom].into();
+        let arrow_array = GeometryArray::LineString(input_array).into_arrow();
+        let series = Series::try_from(("geometry", arrow_array))
+            .unwrap()
+            .set_crs("EPSG:4326")
+            .unwrap();
+
+        let output = series.to_crs("EPSG:3857").unwrap();
+        assert_eq!(output.crs().unwrap(), Some("EPSG:3857".to_string()));
+
+        let x = output.centroid().unwrap().x().unwrap();
+        assert!((x.f64().unwrap().get(0).unwrap() - 55_659.75).abs() < 0.01);
+    }
+
+    #[test]
+    fn to_crs_points() {
+        let input_array: PointArray = vec![point!(x: 1., y: 0.)].into();
+        let arrow_array = GeometryArray::Point(input_array).into_arrow();
+        let series = Series::try_from(("geometry", arrow_array))
+            .unwrap()
+            .set_crs("EPSG:4326")
+            .unwrap();
+
+        let output = series.to_crs("EPSG:3857").unwrap();
+        assert!(matches!(output.dtype(), DataType::Struct(_)));
+        assert_eq!(output.crs().unwrap(), Some("EPSG:3857".to_string()));
+        let x = output.x().unwrap();
+        assert!((x.f64().unwrap().get(0).unwrap() - 111_319.49).abs() < 0.01);
+    }
+
+    #[test]
+    fn transformation_info() {
+        let series = from_geom_vec(&[Geometry::Point(point!(x: 1., y: 0.))])
+            .unwrap()
+            .set_crs("EPSG:4326")
+            .unwrap();
+
+        let (_, info) = series
+            .to_crs_with_info("EPSG:3857", ProjOptions::default())
+            .unwrap();
+        assert!(info.definition.contains("merc"));
+    }
+
+    #[test]
+    fn select_transformation() {
+        // NAD27 to WGS 84 has candidates using Helmert parameters, NADCON grids and a ballpark
+        let options = ProjOptions {
+            allow_ballpark: false,
+            grids: Some(vec![]),
+            ..Default::default()
+        };
+        let info = options
+            .select_transformation("EPSG:4267", "EPSG:4326")
+            .unwrap();
+        assert!(!info.definition.contains("grids="));
+        assert!(!info.description.unwrap().contains("Ballpark"));
+
+        // None of them is accurate to a centimetre
+        let options = ProjOptions {
+            accuracy: Some(0.01),
+            ..Default::default()
+        };
+        assert!(matches!(
+            options.select_transformation("EPSG:4267", "EPSG:4326"),
+            Err(GeopolarsError::UnsuitableTransformation { .. })
+        ));
+    }
 }
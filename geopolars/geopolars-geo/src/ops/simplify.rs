@@ -1,14 +1,62 @@
+use crate::crs::with_crs_of;
 use crate::error::Result;
+use crate::extension::GeoArrowType;
+use crate::util::{
+    coord_key, geometries_of_array, geometry_array, geometry_array_from_geometries,
+    geometry_series_from_arrays, CoordKey,
+};
 use geo::algorithm::simplify::Simplify;
-use geo::{Geometry, LineString, MultiLineString, MultiPolygon, Polygon};
+use geo::algorithm::{SimplifyVw, SimplifyVwPreserve};
+use geo::{
+    Coord, Geometry, GeometryCollection, LineString, MultiLineString, MultiPolygon, Polygon,
+};
 use geoarrow::GeometryArray;
+use polars::prelude::Series;
+use std::collections::HashMap;
 
-pub(crate) fn simplify(array: GeometryArray, tolerance: &f64) -> Result<GeometryArray> {
+/// The algorithm used to simplify geometries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimplifyMethod {
+    /// Ramer-Douglas-Peucker: removes the vertices closer than the tolerance to the line between
+    /// the vertices kept around them
+    Rdp,
+    /// Visvalingam-Whyatt: repeatedly removes the vertex forming the triangle of smallest area
+    /// with its neighbours, until all such triangles are larger than the tolerance
+    Vw,
+    /// Visvalingam-Whyatt, skipping the removals that would make a geometry self-intersect or
+    /// make its rings intersect each other
+    VwPreserve,
+    /// Ramer-Douglas-Peucker applied to the boundaries shared by the polygons of the Series, so
+    /// that each shared boundary is simplified identically for all polygons it bounds
+    Coverage,
+}
+
+/// Geometries that can be simplified with each per-geometry [`SimplifyMethod`]
+trait SimplifyBy: Simplify<f64> + SimplifyVw<f64> + SimplifyVwPreserve<f64> + Sized {
+    fn simplify_by(&self, tolerance: &f64, method: SimplifyMethod) -> Self {
+        match method {
+            SimplifyMethod::Rdp | SimplifyMethod::Coverage => self.simplify(tolerance),
+            SimplifyMethod::Vw => self.simplify_vw(tolerance),
+            SimplifyMethod::VwPreserve => self.simplify_vw_preserve(tolerance),
+        }
+    }
+}
+
+impl SimplifyBy for LineString {}
+impl SimplifyBy for MultiLineString {}
+impl SimplifyBy for Polygon {}
+impl SimplifyBy for MultiPolygon {}
+
+pub(crate) fn simplify(
+    array: GeometryArray,
+    tolerance: &f64,
+    method: SimplifyMethod,
+) -> Result<GeometryArray> {
     match array {
         GeometryArray::WKB(arr) => {
             let output_geoms: Vec<Option<Geometry>> = arr
                 .iter_geo()
-                .map(|maybe_g| maybe_g.map(|geom| simplify_geometry(geom, tolerance)))
+                .map(|maybe_g| maybe_g.map(|geom| simplify_geometry(geom, tolerance, method)))
                 .collect();
 
             Ok(GeometryArray::WKB(output_geoms.into()))
@@ -18,7 +66,7 @@ pub(crate) fn simplify(array: GeometryArray, tolerance: &f64) -> Result<Geometry
         GeometryArray::LineString(arr) => {
             let output_geoms: Vec<Option<LineString>> = arr
                 .iter_geo()
-                .map(|maybe_g| maybe_g.map(|geom| geom.simplify(tolerance)))
+                .map(|maybe_g| maybe_g.map(|geom| geom.simplify_by(tolerance, method)))
                 .collect();
 
             Ok(GeometryArray::LineString(output_geoms.into()))
@@ -26,7 +74,7 @@ pub(crate) fn simplify(array: GeometryArray, tolerance: &f64) -> Result<Geometry
         GeometryArray::MultiLineString(arr) => {
             let output_geoms: Vec<Option<MultiLineString>> = arr
                 .iter_geo()
-                .map(|maybe_g| maybe_g.map(|geom| geom.simplify(tolerance)))
+                .map(|maybe_g| maybe_g.map(|geom| geom.simplify_by(tolerance, method)))
                 .collect();
 
             Ok(GeometryArray::MultiLineString(output_geoms.into()))
@@ -34,7 +82,7 @@ pub(crate) fn simplify(array: GeometryArray, tolerance: &f64) -> Result<Geometry
         GeometryArray::Polygon(arr) => {
             let output_geoms: Vec<Option<Polygon>> = arr
                 .iter_geo()
-                .map(|maybe_g| maybe_g.map(|geom| geom.simplify(tolerance)))
+                .map(|maybe_g| maybe_g.map(|geom| geom.simplify_by(tolerance, method)))
                 .collect();
 
             Ok(GeometryArray::Polygon(output_geoms.into()))
@@ -42,7 +90,7 @@ pub(crate) fn simplify(array: GeometryArray, tolerance: &f64) -> Result<Geometry
         GeometryArray::MultiPolygon(arr) => {
             let output_geoms: Vec<Option<MultiPolygon>> = arr
                 .iter_geo()
-                .map(|maybe_g| maybe_g.map(|geom| geom.simplify(tolerance)))
+                .map(|maybe_g| maybe_g.map(|geom| geom.simplify_by(tolerance, method)))
                 .collect();
 
             Ok(GeometryArray::MultiPolygon(output_geoms.into()))
@@ -50,23 +98,224 @@ pub(crate) fn simplify(array: GeometryArray, tolerance: &f64) -> Result<Geometry
     }
 }
 
-fn simplify_geometry(geom: Geometry, tolerance: &f64) -> Geometry {
+fn simplify_geometry(geom: Geometry, tolerance: &f64, method: SimplifyMethod) -> Geometry {
     match geom {
         Geometry::Point(g) => Geometry::Point(g),
         Geometry::MultiPoint(g) => Geometry::MultiPoint(g),
-        Geometry::LineString(g) => Geometry::LineString(g.simplify(tolerance)),
-        Geometry::MultiLineString(g) => Geometry::MultiLineString(g.simplify(tolerance)),
-        Geometry::Polygon(g) => Geometry::Polygon(g.simplify(tolerance)),
-        Geometry::MultiPolygon(g) => Geometry::MultiPolygon(g.simplify(tolerance)),
-        _ => unimplemented!(),
+        Geometry::LineString(g) => Geometry::LineString(g.simplify_by(tolerance, method)),
+        Geometry::MultiLineString(g) => Geometry::MultiLineString(g.simplify_by(tolerance, method)),
+        Geometry::Polygon(g) => Geometry::Polygon(g.simplify_by(tolerance, method)),
+        Geometry::MultiPolygon(g) => Geometry::MultiPolygon(g.simplify_by(tolerance, method)),
+        // A line has no vertices between its endpoints to remove
+        Geometry::Line(g) => Geometry::Line(g),
+        Geometry::Rect(g) => Geometry::Polygon(g.to_polygon().simplify_by(tolerance, method)),
+        Geometry::Triangle(g) => Geometry::Polygon(g.to_polygon().simplify_by(tolerance, method)),
+        Geometry::GeometryCollection(g) => Geometry::GeometryCollection(GeometryCollection(
+            g.0.into_iter()
+                .map(|geom| simplify_geometry(geom, tolerance, method))
+                .collect(),
+        )),
     }
 }
 
+/// Simplify the polygons of a Series as a coverage, see [`SimplifyMethod::Coverage`]
+///
+/// The rings of all polygons are split into chains at their nodes, the vertices where more or
+/// fewer than two distinct edges meet. A boundary shared by two polygons is then the same chain
+/// in both, so simplifying each distinct chain once and reassembling the rings keeps neighbours
+/// gap-free. Other geometry types are simplified on their own with Ramer-Douglas-Peucker.
+pub(crate) fn simplify_coverage(series: &Series, tolerance: f64) -> Result<Series> {
+    let chunks: Vec<(GeoArrowType, Vec<Option<Geometry>>)> = series
+        .chunks()
+        .iter()
+        .map(|chunk| {
+            let array = geometry_array(&**chunk)?;
+            Ok((GeoArrowType::from(&array), geometries_of_array(array)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut coverage = Coverage::new(tolerance);
+    for (_, geoms) in &chunks {
+        for geom in geoms.iter().flatten() {
+            coverage.add_geometry(geom);
+        }
+    }
+
+    let output_arrays = chunks
+        .into_iter()
+        .map(|(geometry_type, geoms)| {
+            let output_geoms = geoms
+                .into_iter()
+                .map(|maybe_g| maybe_g.map(|geom| coverage.simplify_geometry(geom)))
+                .collect();
+            geometry_array_from_geometries(output_geoms, geometry_type)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    with_crs_of(geometry_series_from_arrays(output_arrays)?, series)
+}
+
+struct Coverage {
+    tolerance: f64,
+    /// The distinct vertices adjacent to each vertex of the rings of the coverage
+    neighbours: HashMap<CoordKey, Vec<CoordKey>>,
+    /// The simplified chains, by the vertices of the chain in canonical direction
+    chains: HashMap<Vec<CoordKey>, Vec<Coord>>,
+}
+
+impl Coverage {
+    fn new(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            neighbours: HashMap::new(),
+            chains: HashMap::new(),
+        }
+    }
+
+    fn add_geometry(&mut self, geom: &Geometry) {
+        match geom {
+            Geometry::Polygon(g) => self.add_polygon(g),
+            Geometry::MultiPolygon(g) => g.0.iter().for_each(|polygon| self.add_polygon(polygon)),
+            Geometry::GeometryCollection(g) => g.0.iter().for_each(|geom| self.add_geometry(geom)),
+            _ => {}
+        }
+    }
+
+    fn add_polygon(&mut self, polygon: &Polygon) {
+        for ring in std::iter::once(polygon.exterior()).chain(polygon.interiors()) {
+            let vertices = ring_vertices(ring);
+            let n = vertices.len();
+            for (i, vertex) in vertices.iter().enumerate() {
+                let key = coord_key(vertex);
+                for other in [&vertices[(i + n - 1) % n], &vertices[(i + 1) % n]] {
+                    let other = coord_key(other);
+                    let neighbours = self.neighbours.entry(key).or_default();
+                    if !neighbours.contains(&other) {
+                        neighbours.push(other);
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_node(&self, coord: &Coord) -> bool {
+        !matches!(
+            self.neighbours.get(&coord_key(coord)),
+            Some(neighbours) if neighbours.len() == 2
+        )
+    }
+
+    fn simplify_geometry(&mut self, geom: Geometry) -> Geometry {
+        match geom {
+            Geometry::Polygon(g) => Geometry::Polygon(self.simplify_polygon(g)),
+            Geometry::MultiPolygon(g) => Geometry::MultiPolygon(MultiPolygon::new(
+                g.0.into_iter()
+                    .map(|polygon| self.simplify_polygon(polygon))
+                    .collect(),
+            )),
+            Geometry::GeometryCollection(g) => Geometry::GeometryCollection(GeometryCollection(
+                g.0.into_iter()
+                    .map(|geom| self.simplify_geometry(geom))
+                    .collect(),
+            )),
+            geom => simplify_geometry(geom, &self.tolerance, SimplifyMethod::Rdp),
+        }
+    }
+
+    fn simplify_polygon(&mut self, polygon: Polygon) -> Polygon {
+        let (exterior, interiors) = polygon.into_inner();
+        Polygon::new(
+            self.simplify_ring(exterior),
+            interiors
+                .into_iter()
+                .map(|ring| self.simplify_ring(ring))
+                .collect(),
+        )
+    }
+
+    fn simplify_ring(&mut self, ring: LineString) -> LineString {
+        let mut vertices = ring_vertices(&ring);
+        if vertices.len() < 3 {
+            return ring;
+        }
+
+        // Start the ring at a node. A ring without nodes is not shared in part, so any vertex
+        // would do, but a ring shared in full must start at the same vertex in both polygons.
+        let start = vertices
+            .iter()
+            .position(|coord| self.is_node(coord))
+            .unwrap_or_else(|| {
+                (0..vertices.len())
+                    .min_by_key(|i| coord_key(&vertices[*i]))
+                    .unwrap()
+            });
+        vertices.rotate_left(start);
+        vertices.push(vertices[0]);
+
+        let mut output = vec![vertices[0]];
+        let mut chain_start = 0;
+        for (i, vertex) in vertices.iter().enumerate().skip(1) {
+            if i == vertices.len() - 1 || self.is_node(vertex) {
+                let chain = self.simplify_chain(&vertices[chain_start..=i]);
+                output.extend_from_slice(&chain[1..]);
+                chain_start = i;
+            }
+        }
+
+        // Keep rings that would collapse, so that their neighbours still match them
+        if output.len() < 4 {
+            return ring;
+        }
+        LineString::new(output)
+    }
+
+    /// Simplify a chain of vertices between two nodes, or look up its simplified form if it was
+    /// already simplified for a neighbouring polygon, possibly in the other direction
+    fn simplify_chain(&mut self, chain: &[Coord]) -> Vec<Coord> {
+        let keys: Vec<CoordKey> = chain.iter().map(coord_key).collect();
+        let reversed_keys: Vec<CoordKey> = keys.iter().rev().copied().collect();
+        let is_reversed = reversed_keys < keys;
+
+        let tolerance = self.tolerance;
+        let key = if is_reversed { reversed_keys } else { keys };
+        let simplified = self.chains.entry(key).or_insert_with(|| {
+            let mut coords = chain.to_vec();
+            if is_reversed {
+                coords.reverse();
+            }
+            LineString::new(coords).simplify(&tolerance).0
+        });
+
+        let mut output = simplified.clone();
+        if is_reversed {
+            output.reverse();
+        }
+        output
+    }
+}
+
+/// The vertices of a ring without its closing vertex and without repeated consecutive vertices
+fn ring_vertices(ring: &LineString) -> Vec<Coord> {
+    let mut vertices = ring.0.clone();
+    vertices.dedup();
+    if vertices.len() > 1 && vertices.first() == vertices.last() {
+        vertices.pop();
+    }
+    vertices
+}
+
 #[cfg(test)]
 mod tests {
-    use super::simplify;
-    use geo::{line_string, polygon, Geometry};
-    use geoarrow::{GeometryArray, GeometryArrayTrait, LineStringArray, PolygonArray};
+    use super::{simplify, SimplifyMethod};
+    use crate::geoseries::GeoSeries;
+    use crate::util::geometries;
+    use geo::prelude::Area;
+    use geo::{
+        coord, line_string, point, polygon, CoordsIter, Geometry, GeometryCollection, Rect,
+        Triangle,
+    };
+    use geoarrow::{GeometryArray, GeometryArrayTrait, LineStringArray, PolygonArray, WKBArray};
+    use polars::prelude::Series;
 
     #[test]
     fn rdp_test() {
@@ -78,7 +327,12 @@ mod tests {
             (x: 27.8, y: 0.1 ),
         ];
         let input_array: LineStringArray = vec![input_geom].into();
-        let result_array = simplify(GeometryArray::LineString(input_array), &1.0).unwrap();
+        let result_array = simplify(
+            GeometryArray::LineString(input_array),
+            &1.0,
+            SimplifyMethod::Rdp,
+        )
+        .unwrap();
 
         let expected = line_string![
             ( x: 0.0, y: 0.0 ),
@@ -93,6 +347,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn coverage() {
+        // Two squares sharing a wiggly edge from (2, 0) to (2, 4)
+        let left = polygon![
+            (x: 0., y: 0.),
+            (x: 2., y: 0.),
+            (x: 2.1, y: 1.),
+            (x: 1.9, y: 2.),
+            (x: 2.1, y: 3.),
+            (x: 2., y: 4.),
+            (x: 0., y: 4.),
+        ];
+        let right = polygon![
+            (x: 2., y: 0.),
+            (x: 4., y: 0.),
+            (x: 4., y: 4.),
+            (x: 2., y: 4.),
+            (x: 2.1, y: 3.),
+            (x: 1.9, y: 2.),
+            (x: 2.1, y: 1.),
+        ];
+        let input_array: PolygonArray = vec![left, right].into();
+        let series =
+            Series::try_from(("geometry", GeometryArray::Polygon(input_array).into_arrow()))
+                .unwrap();
+
+        let result = series.simplify_with(0.5, SimplifyMethod::Coverage).unwrap();
+        let result = geometries(&result).unwrap();
+
+        // The shared edge is straightened identically on both sides, leaving no gap or overlap
+        assert_eq!(result[0].as_ref().unwrap().unsigned_area(), 8.);
+        assert_eq!(result[1].as_ref().unwrap().unsigned_area(), 8.);
+        assert_eq!(result[0].as_ref().unwrap().coords_count(), 5);
+    }
+
     #[test]
     fn polygon() {
         let input_geom = polygon![
@@ -104,7 +393,12 @@ mod tests {
             (x: 0., y: 0.),
         ];
         let input_array: PolygonArray = vec![input_geom].into();
-        let result_array = simplify(GeometryArray::Polygon(input_array), &2.0).unwrap();
+        let result_array = simplify(
+            GeometryArray::Polygon(input_array),
+            &2.0,
+            SimplifyMethod::Rdp,
+        )
+        .unwrap();
 
         let expected = polygon![
             (x: 0., y: 0.),
@@ -119,4 +413,67 @@ mod tests {
             result_array.get_as_geo(0).unwrap()
         );
     }
+
+    #[test]
+    fn mixed_geometries() {
+        let wiggly = line_string![
+            (x: 0., y: 0.),
+            (x: 1., y: 0.1),
+            (x: 2., y: 0.),
+        ];
+        let collection = GeometryCollection(vec![
+            Geometry::Point(point!(x: 5., y: 5.)),
+            Geometry::LineString(wiggly),
+        ]);
+        let rect = Rect::new(coord! { x: 0., y: 0. }, coord! { x: 1., y: 1. });
+        let triangle = Triangle::new(
+            coord! { x: 0., y: 0. },
+            coord! { x: 1., y: 0. },
+            coord! { x: 0., y: 1. },
+        );
+        let input_array: WKBArray = vec![
+            Some(Geometry::GeometryCollection(collection)),
+            Some(Geometry::Rect(rect)),
+            Some(Geometry::Triangle(triangle)),
+        ]
+        .into();
+
+        let result_array =
+            simplify(GeometryArray::WKB(input_array), &0.5, SimplifyMethod::Rdp).unwrap();
+
+        let expected = GeometryCollection(vec![
+            Geometry::Point(point!(x: 5., y: 5.)),
+            Geometry::LineString(line_string![(x: 0., y: 0.), (x: 2., y: 0.)]),
+        ]);
+        assert_eq!(
+            Geometry::GeometryCollection(expected),
+            result_array.get_as_geo(0).unwrap()
+        );
+        assert_eq!(
+            Geometry::Polygon(rect.to_polygon()),
+            result_array.get_as_geo(1).unwrap()
+        );
+        assert_eq!(
+            Geometry::Polygon(triangle.to_polygon()),
+            result_array.get_as_geo(2).unwrap()
+        );
+    }
+
+    #[test]
+    fn default_method() {
+        let input_geom = line_string![
+            (x: 0., y: 0.),
+            (x: 1., y: 0.1),
+            (x: 2., y: 0.),
+        ];
+        let input_array: LineStringArray = vec![input_geom].into();
+        let series = Series::try_from((
+            "geometry",
+            GeometryArray::LineString(input_array).into_arrow(),
+        ))
+        .unwrap();
+
+        let result = geometries(&series.simplify(0.5).unwrap()).unwrap();
+        assert_eq!(result[0].as_ref().unwrap().coords_count(), 2);
+    }
 }
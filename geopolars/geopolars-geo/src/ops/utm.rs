@@ -0,0 +1,184 @@
+use crate::crs::crs;
+use crate::error::{GeopolarsError, Result};
+use crate::ops::bounds::{chunk_bounds, total_bounds};
+use polars::error::ErrString;
+use polars::prelude::{PolarsError, Series};
+
+pub(crate) fn estimate_utm_crs(series: &Series) -> Result<String> {
+    if let Some(crs) = crs(series)? {
+        if !matches!(crs.as_str(), "EPSG:4326" | "OGC:CRS84") {
+            return Err(GeopolarsError::NotWgs84(crs));
+        }
+    }
+
+    let bounds = total_bounds(series)?.ok_or_else(|| {
+        PolarsError::NoData(ErrString::from(
+            "Cannot estimate a UTM CRS for a GeoSeries without geometries",
+        ))
+    })?;
+
+    let mut ranges = vec![];
+    for chunk in series.chunks() {
+        ranges.extend(
+            chunk_bounds(&**chunk)?
+                .into_iter()
+                .flatten()
+                .map(|rect| (rect.min().x, rect.max().x)),
+        );
+    }
+    Ok(format!(
+        "EPSG:{}",
+        utm_epsg_code(center_lon(ranges), bounds.center().y)
+    ))
+}
+
+/// The centre of the shortest arc of longitudes covering all `(min, max)` longitude ranges
+///
+/// Unlike the centre of the total bounds, this keeps geometries on either side of the
+/// antimeridian together, e.g. the centre of 179° and -179° is 180°, not 0°. A single geometry
+/// crossing the antimeridian can't be told apart from one spanning nearly all longitudes by its
+/// bounds, so it's still centred as the latter.
+fn center_lon(ranges: Vec<(f64, f64)>) -> f64 {
+    // Wrap the ranges into [-180, 180], splitting those crossing the antimeridian
+    let mut arcs = vec![];
+    for (min, max) in ranges {
+        if max - min >= 360. {
+            return 0.;
+        }
+        let start = (min + 180.).rem_euclid(360.) - 180.;
+        let end = start + (max - min);
+        if end > 180. {
+            arcs.push((start, 180.));
+            arcs.push((-180., end - 360.));
+        } else {
+            arcs.push((start, end));
+        }
+    }
+    arcs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    // The covering arc starts at the end of the widest gap between arcs
+    let first_start = arcs[0].0;
+    let mut end = arcs[0].1;
+    let mut widest_gap = (end, end);
+    for &(start, arc_end) in &arcs[1..] {
+        if start - end > widest_gap.1 - widest_gap.0 {
+            widest_gap = (end, start);
+        }
+        end = end.max(arc_end);
+    }
+    if first_start + 360. - end > widest_gap.1 - widest_gap.0 {
+        widest_gap = (end, first_start + 360.);
+    }
+
+    let covered = 360. - (widest_gap.1 - widest_gap.0);
+    let center = widest_gap.1 + covered / 2.;
+    if center > 180. {
+        center - 360.
+    } else {
+        center
+    }
+}
+
+/// The EPSG code of the UTM zone, or UPS area near the poles, containing a WGS 84 coordinate
+fn utm_epsg_code(lon: f64, lat: f64) -> u32 {
+    // Universal Polar Stereographic beyond the latitude limits of UTM
+    if lat >= 84. {
+        return 32661;
+    }
+    if lat < -80. {
+        return 32761;
+    }
+
+    // Normalize to [-180, 180) so that longitudes past the antimeridian land in the right zone
+    let lon = (lon + 180.).rem_euclid(360.) - 180.;
+    let mut zone = ((lon + 180.) / 6.).floor() as u32 + 1;
+
+    // Zone 32V is widened to cover all of southwestern Norway
+    if (56.0..64.).contains(&lat) && (3.0..12.).contains(&lon) {
+        zone = 32;
+    }
+
+    // Around Svalbard the zones 32X, 34X and 36X are unused and their neighbours widened
+    if (72.0..84.).contains(&lat) {
+        zone = match lon {
+            lon if (0.0..9.).contains(&lon) => 31,
+            lon if (9.0..21.).contains(&lon) => 33,
+            lon if (21.0..33.).contains(&lon) => 35,
+            lon if (33.0..42.).contains(&lon) => 37,
+            _ => zone,
+        };
+    }
+
+    if lat >= 0. {
+        32600 + zone
+    } else {
+        32700 + zone
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{center_lon, utm_epsg_code};
+    use crate::error::GeopolarsError;
+    use crate::geoseries::GeoSeries;
+    use crate::util::from_geom_vec;
+    use geo::{point, Geometry};
+
+    #[test]
+    fn zones() {
+        // New York City
+        assert_eq!(utm_epsg_code(-74.006, 40.7128), 32618);
+        // Sydney
+        assert_eq!(utm_epsg_code(151.2093, -33.8688), 32756);
+        // The antimeridian belongs to zone 1 like -180, and longitudes past it wrap around
+        assert_eq!(utm_epsg_code(180., 10.), 32601);
+        assert_eq!(utm_epsg_code(179.9, 10.), 32660);
+        assert_eq!(utm_epsg_code(-180.1, 10.), 32660);
+        // Bergen lies in the widened zone 32V
+        assert_eq!(utm_epsg_code(5.3221, 60.3913), 32632);
+        // Longyearbyen, Svalbard
+        assert_eq!(utm_epsg_code(15.6356, 78.2232), 32633);
+        // Poles
+        assert_eq!(utm_epsg_code(0., 85.), 32661);
+        assert_eq!(utm_epsg_code(0., -85.), 32761);
+    }
+
+    #[test]
+    fn estimate_utm_crs() {
+        let series = from_geom_vec(&[
+            Geometry::Point(point!(x: 8.5, y: 47.3)),
+            Geometry::Point(point!(x: 8.6, y: 47.4)),
+        ])
+        .unwrap()
+        .set_crs("EPSG:4326")
+        .unwrap();
+        assert_eq!(series.estimate_utm_crs().unwrap(), "EPSG:32632");
+
+        let projected = series.set_crs("EPSG:3857").unwrap();
+        assert!(matches!(
+            projected.estimate_utm_crs(),
+            Err(GeopolarsError::NotWgs84(_))
+        ));
+    }
+
+    #[test]
+    fn estimate_utm_crs_antimeridian() {
+        // Fiji, on both sides of the antimeridian
+        let series = from_geom_vec(&[
+            Geometry::Point(point!(x: 179.0, y: -17.)),
+            Geometry::Point(point!(x: -179.8, y: -17.5)),
+        ])
+        .unwrap()
+        .set_crs("EPSG:4326")
+        .unwrap();
+        assert_eq!(series.estimate_utm_crs().unwrap(), "EPSG:32760");
+    }
+
+    #[test]
+    fn center_lons() {
+        assert_eq!(center_lon(vec![(8., 9.), (10., 12.)]), 10.);
+        assert_eq!(center_lon(vec![(179., 179.), (-179., -179.)]), 180.);
+        assert_eq!(center_lon(vec![(170., 190.), (-175., -170.)]), 180.);
+        assert_eq!(center_lon(vec![(-180., 180.)]), 0.);
+    }
+}
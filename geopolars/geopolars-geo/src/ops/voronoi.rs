@@ -0,0 +1,232 @@
+use crate::crs::with_crs_of;
+use crate::error::Result;
+use crate::extension::GeoArrowType;
+use crate::ops::delaunay::{edges, triangulate};
+use crate::util::{
+    coord_key, geometries, geometry_array_from_geometries, geometry_series_from_arrays,
+};
+use geo::{coord, Coord, CoordsIter, Geometry, LineString, Polygon};
+use polars::prelude::{DataFrame, NamedFrom, Series};
+use std::collections::HashSet;
+
+pub(crate) fn delaunay_triangles(series: &Series) -> Result<DataFrame> {
+    let (sites, rows) = sites(series)?;
+    let triangles = triangulate(&sites);
+
+    let polygons = triangles
+        .iter()
+        .map(|t| {
+            let ring = vec![sites[t[0]], sites[t[1]], sites[t[2]]];
+            Some(Geometry::Polygon(Polygon::new(
+                LineString::new(ring),
+                vec![],
+            )))
+        })
+        .collect();
+    let vertex = |i: usize| {
+        let vertex_rows: Vec<u32> = triangles.iter().map(|t| rows[t[i]]).collect();
+        Series::new(&format!("vertex_{}", i), vertex_rows)
+    };
+
+    Ok(DataFrame::new(vec![
+        vertex(0),
+        vertex(1),
+        vertex(2),
+        polygon_column(series, polygons)?,
+    ])?)
+}
+
+pub(crate) fn voronoi_polygons(series: &Series, extent: Option<[f64; 4]>) -> Result<DataFrame> {
+    let (sites, rows) = sites(series)?;
+    let [minx, miny, maxx, maxy] = match extent {
+        Some(extent) => extent,
+        None => expanded_envelope(&sites),
+    };
+
+    // The cell of a site only borders the cells of its neighbours in the Delaunay triangulation.
+    // Collinear sites have no triangulation, and are all compared with each other instead.
+    let triangles = triangulate(&sites);
+    let mut neighbours: Vec<Vec<usize>> = vec![vec![]; sites.len()];
+    if triangles.is_empty() {
+        for (i, site_neighbours) in neighbours.iter_mut().enumerate() {
+            site_neighbours.extend((0..sites.len()).filter(|j| *j != i));
+        }
+    } else {
+        for (a, b) in triangles.iter().flat_map(edges) {
+            neighbours[a].push(b);
+            neighbours[b].push(a);
+        }
+        for site_neighbours in neighbours.iter_mut() {
+            site_neighbours.sort_unstable();
+            site_neighbours.dedup();
+        }
+    }
+
+    let mut index = vec![];
+    let mut polygons = vec![];
+    for (i, site) in sites.iter().enumerate() {
+        let mut cell = vec![
+            coord! { x: minx, y: miny },
+            coord! { x: maxx, y: miny },
+            coord! { x: maxx, y: maxy },
+            coord! { x: minx, y: maxy },
+        ];
+        for j in &neighbours[i] {
+            cell = clip_to_site(cell, *site, sites[*j]);
+        }
+        // Sites outside of the extent may have no cell within it
+        if cell.len() >= 3 {
+            index.push(rows[i]);
+            polygons.push(Some(Geometry::Polygon(Polygon::new(
+                LineString::new(cell),
+                vec![],
+            ))));
+        }
+    }
+
+    Ok(DataFrame::new(vec![
+        Series::new("index", index),
+        polygon_column(series, polygons)?,
+    ])?)
+}
+
+/// The distinct vertices of the geometries of a series, with the row of the first geometry each
+/// vertex belongs to
+fn sites(series: &Series) -> Result<(Vec<Coord>, Vec<u32>)> {
+    let mut seen = HashSet::new();
+    let (mut sites, mut rows) = (vec![], vec![]);
+    for (row, geom) in geometries(series)?.iter().enumerate() {
+        for coord in geom.iter().flat_map(|geom| geom.coords_iter()) {
+            if seen.insert(coord_key(&coord)) {
+                sites.push(coord);
+                rows.push(row as u32);
+            }
+        }
+    }
+    Ok((sites, rows))
+}
+
+/// The envelope of the sites expanded by its width or height, whichever is larger, on every side
+fn expanded_envelope(sites: &[Coord]) -> [f64; 4] {
+    let (mut minx, mut miny) = (f64::INFINITY, f64::INFINITY);
+    let (mut maxx, mut maxy) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for site in sites {
+        minx = minx.min(site.x);
+        miny = miny.min(site.y);
+        maxx = maxx.max(site.x);
+        maxy = maxy.max(site.y);
+    }
+    let size = (maxx - minx).max(maxy - miny);
+    // A single site has an envelope without size
+    let margin = if size > 0. { size } else { 1. };
+    [minx - margin, miny - margin, maxx + margin, maxy + margin]
+}
+
+/// Clip a convex cell to the half-plane of points closer to `site` than to `other`, with the
+/// Sutherland-Hodgman algorithm
+fn clip_to_site(cell: Vec<Coord>, site: Coord, other: Coord) -> Vec<Coord> {
+    if cell.is_empty() {
+        return cell;
+    }
+    let normal = other - site;
+    let middle = (site + other) / 2.;
+    let side = |c: Coord| (c.x - middle.x) * normal.x + (c.y - middle.y) * normal.y;
+    let crossing =
+        |a: Coord, b: Coord, side_a: f64, side_b: f64| a + (b - a) * (side_a / (side_a - side_b));
+
+    let mut clipped = Vec::with_capacity(cell.len() + 1);
+    for (k, &current) in cell.iter().enumerate() {
+        let previous = cell[(k + cell.len() - 1) % cell.len()];
+        let (side_current, side_previous) = (side(current), side(previous));
+        if side_current <= 0. {
+            if side_previous > 0. {
+                clipped.push(crossing(previous, current, side_previous, side_current));
+            }
+            clipped.push(current);
+        } else if side_previous <= 0. {
+            clipped.push(crossing(previous, current, side_previous, side_current));
+        }
+    }
+
+    clipped.dedup();
+    if clipped.len() > 1 && clipped.first() == clipped.last() {
+        clipped.pop();
+    }
+    clipped
+}
+
+/// The `geometry` column of polygons, with the CRS of the input
+fn polygon_column(series: &Series, polygons: Vec<Option<Geometry>>) -> Result<Series> {
+    let array = geometry_array_from_geometries(polygons, GeoArrowType::Polygon)?;
+    with_crs_of(geometry_series_from_arrays(vec![array])?, series)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoseries::GeoSeries;
+    use crate::util::{from_geom_vec, geometries};
+    use geo::prelude::Area;
+    use geo::{point, Geometry};
+    use polars::prelude::Series;
+
+    fn square_with_center() -> Series {
+        from_geom_vec(&[
+            Geometry::Point(point!(x: 0., y: 0.)),
+            Geometry::Point(point!(x: 2., y: 0.)),
+            Geometry::Point(point!(x: 0., y: 2.)),
+            Geometry::Point(point!(x: 2., y: 2.)),
+            // Duplicate points only have a cell for the first row
+            Geometry::Point(point!(x: 0., y: 0.)),
+            Geometry::Point(point!(x: 1., y: 1.)),
+        ])
+        .unwrap()
+    }
+
+    fn areas(series: &Series) -> Vec<f64> {
+        geometries(series)
+            .unwrap()
+            .iter()
+            .map(|geom| geom.as_ref().unwrap().unsigned_area())
+            .collect()
+    }
+
+    #[test]
+    fn voronoi_cells() {
+        let series = square_with_center();
+        let df = series.voronoi_polygons(Some([0., 0., 2., 2.])).unwrap();
+
+        let index: Vec<Option<u32>> = df
+            .column("index")
+            .unwrap()
+            .u32()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(index, vec![Some(0), Some(1), Some(2), Some(3), Some(5)]);
+        let expected = [0.5, 0.5, 0.5, 0.5, 2.];
+        for (area, expected) in areas(df.column("geometry").unwrap()).iter().zip(expected) {
+            assert!((area - expected).abs() < 1e-12);
+        }
+
+        // Without an extent, the cells cover the envelope of the points expanded by its size
+        let df = series.voronoi_polygons(None).unwrap();
+        let total: f64 = areas(df.column("geometry").unwrap()).iter().sum();
+        assert!((total - 36.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn delaunay() {
+        let df = square_with_center().delaunay_triangles().unwrap();
+        assert_eq!(df.height(), 4);
+
+        let total: f64 = areas(df.column("geometry").unwrap()).iter().sum();
+        assert!((total - 4.).abs() < 1e-12);
+        for row in 0..4 {
+            let vertices: Vec<u32> = ["vertex_0", "vertex_1", "vertex_2"]
+                .iter()
+                .map(|name| df.column(name).unwrap().u32().unwrap().get(row).unwrap())
+                .collect();
+            assert!(vertices.contains(&5));
+        }
+    }
+}
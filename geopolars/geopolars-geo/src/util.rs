@@ -1,11 +1,23 @@
-use crate::error::Result;
-use geo::Geometry;
+use crate::error::{inner_type_name, GeopolarsError, Result};
+use crate::extension::{
+    chunk_extension, chunk_geoarrow_type, extension_array, point_series, storage_array,
+    with_geoarrow_extension, GeoArrowType,
+};
+use geo::{Coord, Geometry};
+use geoarrow::{GeometryArray, GeometryArrayTrait};
 use geozero::{wkb::Wkb, ToGeo};
 use geozero::{CoordDimensions, ToWkb};
 use polars::error::ErrString;
-use polars::export::arrow::array::{Array, BinaryArray, MutableBinaryArray};
+use polars::export::arrow::array::{
+    Array, BinaryArray, ListArray, MutableBinaryArray, PrimitiveArray, StructArray,
+};
 use polars::export::arrow::compute::concatenate::concatenate;
-use polars::prelude::{PolarsError, Series};
+use polars::export::arrow::offset::{Offset, Offsets};
+use polars::prelude::{BinaryChunked, DataFrame, DataType, ListChunked, PolarsError, Series};
+use polars::series::IntoSeries;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::any::type_name;
 use std::convert::Into;
 
 pub fn from_geom_vec(geoms: &[Geometry<f64>]) -> Result<Series> {
@@ -23,6 +35,25 @@ pub fn from_geom_vec(geoms: &[Geometry<f64>]) -> Result<Series> {
     Ok(series)
 }
 
+/// Rebuild geometries of `geometry_type` from a DataFrame of coordinates, the inverse of
+/// `GeoSeries::get_coordinates` on `source`
+///
+/// The DataFrame must have the columns `index`, `x` and `y`, and may have the columns `part` and
+/// `ring`. Coordinates are grouped by row index, then part, then ring, keeping their order within
+/// a ring; without `part` and `ring` columns all coordinates of a row are taken to be in the same
+/// part and ring.
+///
+/// The output has the length and CRS of `source`. Rows that are null in `source` are null, and
+/// other rows without coordinates are empty geometries, except for points, which can't be empty
+/// and are null.
+pub fn from_coordinates(
+    df: &DataFrame,
+    geometry_type: GeoArrowType,
+    source: &Series,
+) -> Result<Series> {
+    crate::ops::coordinates::from_coordinates(df, geometry_type, source)
+}
+
 /// Helper function to iterate over geometries from polars Series
 pub(crate) fn iter_geom(series: &Series) -> impl Iterator<Item = Geometry<f64>> + '_ {
     let chunks = series.binary().expect("series was not a list type");
@@ -51,3 +82,386 @@ pub(crate) fn series_from_any_chunks(chunks: Vec<Box<dyn Array>>) -> Result<Seri
     let output = concatenate(refs.as_slice()).unwrap();
     Ok(Series::try_from(("geometry", output))?)
 }
+
+/// Helper function to create a geoarrow GeometryArray from an Arrow chunk of a geometry Series,
+/// using the chunk's GeoArrow extension type to tell multi-part from single-part geometries
+pub fn geometry_array(chunk: &dyn Array) -> Result<GeometryArray> {
+    let is_multi = chunk_geoarrow_type(chunk)?.is_multi();
+    Ok(GeometryArray::from_arrow(&*storage_array(chunk)?, is_multi))
+}
+
+/// Helper function to collect the geometries of a geometry Series of any GeoArrow type, with
+/// `None` for null rows
+pub(crate) fn geometries(series: &Series) -> Result<Vec<Option<Geometry<f64>>>> {
+    let mut geoms = Vec::with_capacity(series.len());
+    for chunk in series.chunks() {
+        geoms.extend(geometries_of_array(geometry_array(&**chunk)?));
+    }
+    Ok(geoms)
+}
+
+/// Helper function to collect the geometries of a GeometryArray, with `None` for null rows
+pub(crate) fn geometries_of_array(array: GeometryArray) -> Vec<Option<Geometry<f64>>> {
+    match array {
+        GeometryArray::WKB(arr) => arr.iter_geo().collect(),
+        GeometryArray::Point(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(Geometry::from))
+            .collect(),
+        GeometryArray::LineString(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(Geometry::from))
+            .collect(),
+        GeometryArray::Polygon(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(Geometry::from))
+            .collect(),
+        GeometryArray::MultiPoint(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(Geometry::from))
+            .collect(),
+        GeometryArray::MultiLineString(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(Geometry::from))
+            .collect(),
+        GeometryArray::MultiPolygon(arr) => arr
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(Geometry::from))
+            .collect(),
+    }
+}
+
+/// Helper function to build a GeometryArray of `geometry_type` from geometries, with `None` for
+/// null rows. Every geometry must be of that type, unless the type is WKB.
+pub(crate) fn geometry_array_from_geometries(
+    geoms: Vec<Option<Geometry<f64>>>,
+    geometry_type: GeoArrowType,
+) -> Result<GeometryArray> {
+    Ok(match geometry_type {
+        GeoArrowType::Point => GeometryArray::Point(typed_geometries(geoms)?.into()),
+        GeoArrowType::LineString => GeometryArray::LineString(typed_geometries(geoms)?.into()),
+        GeoArrowType::Polygon => GeometryArray::Polygon(typed_geometries(geoms)?.into()),
+        GeoArrowType::MultiPoint => GeometryArray::MultiPoint(typed_geometries(geoms)?.into()),
+        GeoArrowType::MultiLineString => {
+            GeometryArray::MultiLineString(typed_geometries(geoms)?.into())
+        }
+        GeoArrowType::MultiPolygon => GeometryArray::MultiPolygon(typed_geometries(geoms)?.into()),
+        GeoArrowType::WKB => GeometryArray::WKB(geoms.into()),
+    })
+}
+
+fn typed_geometries<T>(geoms: Vec<Option<Geometry<f64>>>) -> Result<Vec<Option<T>>>
+where
+    T: TryFrom<Geometry<f64>>,
+{
+    geoms
+        .into_iter()
+        .map(|maybe_g| {
+            maybe_g
+                .map(|geom| {
+                    let found = inner_type_name(&geom);
+                    T::try_from(geom).map_err(|_| GeopolarsError::MismatchedGeometry {
+                        expected: type_name::<T>(),
+                        found,
+                    })
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+/// Create a geometry Series from the output arrays of an operation, tagged with the GeoArrow
+/// type of the output so that multi-part geometries are recognized downstream
+pub(crate) fn geometry_series_from_arrays(arrays: Vec<GeometryArray>) -> Result<Series> {
+    let geoarrow_type = arrays
+        .first()
+        .map(GeoArrowType::from)
+        .unwrap_or(GeoArrowType::WKB);
+    let chunks = arrays.into_iter().map(|arr| arr.into_arrow()).collect();
+    let series = series_from_any_chunks(chunks)?;
+    with_geoarrow_extension(&series, geoarrow_type, None)
+}
+
+/// A coordinate usable as a hash map key, equal for coordinates with identical values
+pub(crate) type CoordKey = (u64, u64);
+
+pub(crate) fn coord_key(coord: &Coord) -> CoordKey {
+    (coord.x.to_bits(), coord.y.to_bits())
+}
+
+/// Apply `f` to the coordinates of a chunk of native (non-WKB) geometries
+///
+/// The coordinates of every geometry type are stored in an inner struct array of x and y values,
+/// nested in zero to three levels of lists. `f` receives all coordinates of the chunk as one
+/// slice and may modify them in place; the list offsets and validity of the chunk, including any
+/// extension information, are kept as is.
+pub(crate) fn map_coords<F>(chunk: &dyn Array, f: &mut F) -> Result<Box<dyn Array>>
+where
+    F: FnMut(&mut [Coord<f64>]) -> Result<()>,
+{
+    let any = chunk.as_any();
+    if let Some(arr) = any.downcast_ref::<ListArray<i64>>() {
+        let values = map_coords(arr.values().as_ref(), f)?;
+        Ok(ListArray::<i64>::new(
+            arr.data_type().clone(),
+            arr.offsets().clone(),
+            values,
+            arr.validity().cloned(),
+        )
+        .boxed())
+    } else if let Some(arr) = any.downcast_ref::<ListArray<i32>>() {
+        let values = map_coords(arr.values().as_ref(), f)?;
+        Ok(ListArray::<i32>::new(
+            arr.data_type().clone(),
+            arr.offsets().clone(),
+            values,
+            arr.validity().cloned(),
+        )
+        .boxed())
+    } else if let Some(arr) = any.downcast_ref::<StructArray>() {
+        let (x, y) = match (
+            arr.values()[0]
+                .as_any()
+                .downcast_ref::<PrimitiveArray<f64>>(),
+            arr.values()[1]
+                .as_any()
+                .downcast_ref::<PrimitiveArray<f64>>(),
+        ) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return Err(unexpected_coord_type(chunk)),
+        };
+
+        let mut coords: Vec<Coord<f64>> = x
+            .values()
+            .iter()
+            .zip(y.values().iter())
+            .map(|(x, y)| Coord { x: *x, y: *y })
+            .collect();
+        f(&mut coords)?;
+        let (x_values, y_values): (Vec<f64>, Vec<f64>) =
+            coords.into_iter().map(|c| (c.x, c.y)).unzip();
+
+        let x = PrimitiveArray::new(
+            x.data_type().clone(),
+            x_values.into(),
+            x.validity().cloned(),
+        );
+        let y = PrimitiveArray::new(
+            y.data_type().clone(),
+            y_values.into(),
+            y.validity().cloned(),
+        );
+        Ok(StructArray::new(
+            arr.data_type().clone(),
+            vec![x.boxed(), y.boxed()],
+            arr.validity().cloned(),
+        )
+        .boxed())
+    } else {
+        Err(unexpected_coord_type(chunk))
+    }
+}
+
+/// The rows of a WKB storage array, stored as `Binary` or `LargeBinary`
+pub(crate) fn wkb_rows(array: &dyn Array) -> Result<Vec<Option<&[u8]>>> {
+    if let Some(arr) = array.as_any().downcast_ref::<BinaryArray<i64>>() {
+        Ok(arr.iter().collect())
+    } else if let Some(arr) = array.as_any().downcast_ref::<BinaryArray<i32>>() {
+        Ok(arr.iter().collect())
+    } else {
+        Err(unexpected_coord_type(array))
+    }
+}
+
+/// Apply `f` to each geometry of a WKB chunk, keeping the extension information of the chunk
+///
+/// Rows are mapped on multiple threads with the `parallel` feature. Like rayon's `map_init`,
+/// `init` creates a state that `f` receives for every row mapped on the same thread.
+pub(crate) fn map_wkb<T, I, F>(chunk: &dyn Array, init: I, f: F) -> Result<Box<dyn Array>>
+where
+    I: Fn() -> T + Sync + Send,
+    F: Fn(&mut T, Geometry<f64>) -> Result<Geometry<f64>> + Sync + Send,
+{
+    let storage = storage_array(chunk)?;
+    let rows = wkb_rows(&*storage)?;
+    let map_row = |state: &mut T, row: &Option<&[u8]>| {
+        row.map(|wkb| -> Result<Vec<u8>> {
+            let geom = f(state, Wkb(wkb.to_vec()).to_geo()?)?;
+            Ok(geom.to_wkb(CoordDimensions::xy())?)
+        })
+        .transpose()
+    };
+
+    #[cfg(feature = "parallel")]
+    let output = rows
+        .par_iter()
+        .map_init(init, map_row)
+        .collect::<Result<Vec<Option<Vec<u8>>>>>()?;
+    #[cfg(not(feature = "parallel"))]
+    let output = {
+        let mut state = init();
+        rows.iter()
+            .map(|row| map_row(&mut state, row))
+            .collect::<Result<Vec<Option<Vec<u8>>>>>()?
+    };
+
+    let output = BinaryArray::<i64>::from(output).boxed();
+    Ok(match chunk_extension(chunk) {
+        Some((_, metadata)) => extension_array(
+            &*output,
+            GeoArrowType::WKB,
+            metadata.map(|metadata| metadata.to_string()),
+        )?,
+        None => output,
+    })
+}
+
+pub(crate) fn unexpected_coord_type(array: &dyn Array) -> GeopolarsError {
+    GeopolarsError::UnexpectedArrowType(format!("{:?}", array.data_type()))
+}
+
+/// The offsets of the coordinates of each row of a chunk of native (non-WKB) geometries into the
+/// coordinate slice that `map_coords` passes on, so that row `i` has the coordinates
+/// `offsets[i]..offsets[i + 1]`
+pub(crate) fn row_coord_offsets(chunk: &dyn Array) -> Vec<usize> {
+    let mut offsets: Vec<usize> = (0..=chunk.len()).collect();
+    let mut array = chunk;
+    loop {
+        let any = array.as_any();
+        if let Some(arr) = any.downcast_ref::<ListArray<i64>>() {
+            let buffer = arr.offsets().buffer();
+            offsets = offsets.iter().map(|i| buffer[*i] as usize).collect();
+            array = arr.values().as_ref();
+        } else if let Some(arr) = any.downcast_ref::<ListArray<i32>>() {
+            let buffer = arr.offsets().buffer();
+            offsets = offsets.iter().map(|i| buffer[*i] as usize).collect();
+            array = arr.values().as_ref();
+        } else {
+            return offsets;
+        }
+    }
+}
+
+/// Apply `f` to each innermost list of coordinates of a chunk of native (non-WKB) geometries,
+/// i.e. to each LineString, ring of a Polygon, or the points of a MultiPoint
+///
+/// `f` receives the coordinates of the list and whether it is the first list of its parent, which
+/// tells the exterior ring of a polygon from its interiors, and returns the new coordinates of the
+/// list, which may have a different length. The number of lists and the validity of the chunk,
+/// including any extension information, are kept as is. Point chunks have no lists and are
+/// returned unchanged.
+pub(crate) fn map_coord_lists<F>(chunk: &dyn Array, f: &mut F) -> Result<Box<dyn Array>>
+where
+    F: FnMut(Vec<Coord<f64>>, bool) -> Vec<Coord<f64>>,
+{
+    map_coord_lists_inner(chunk, None, f)
+}
+
+fn map_coord_lists_inner<F>(
+    array: &dyn Array,
+    first: Option<&[bool]>,
+    f: &mut F,
+) -> Result<Box<dyn Array>>
+where
+    F: FnMut(Vec<Coord<f64>>, bool) -> Vec<Coord<f64>>,
+{
+    let any = array.as_any();
+    if let Some(arr) = any.downcast_ref::<ListArray<i64>>() {
+        map_list(arr, first, f)
+    } else if let Some(arr) = any.downcast_ref::<ListArray<i32>>() {
+        map_list(arr, first, f)
+    } else if any.downcast_ref::<StructArray>().is_some() {
+        Ok(array.to_boxed())
+    } else {
+        Err(unexpected_coord_type(array))
+    }
+}
+
+fn map_list<O: Offset, F>(
+    arr: &ListArray<O>,
+    first: Option<&[bool]>,
+    f: &mut F,
+) -> Result<Box<dyn Array>>
+where
+    F: FnMut(Vec<Coord<f64>>, bool) -> Vec<Coord<f64>>,
+{
+    let offsets = arr.offsets().buffer();
+    let coord_arr = match arr.values().as_any().downcast_ref::<StructArray>() {
+        Some(coord_arr) => coord_arr,
+        None => {
+            // Mark the first child of every list before descending into the children
+            let mut child_first = vec![false; arr.values().len()];
+            for window in offsets.windows(2) {
+                if window[0] < window[1] {
+                    child_first[window[0].to_usize()] = true;
+                }
+            }
+            let values = map_coord_lists_inner(arr.values().as_ref(), Some(&child_first), f)?;
+            return Ok(ListArray::<O>::new(
+                arr.data_type().clone(),
+                arr.offsets().clone(),
+                values,
+                arr.validity().cloned(),
+            )
+            .boxed());
+        }
+    };
+
+    let (x, y) = coord_buffers(coord_arr)?;
+    let mut coords = Vec::with_capacity(x.len());
+    let mut new_offsets = Vec::with_capacity(offsets.len());
+    new_offsets.push(O::default());
+    for (i, window) in offsets.windows(2).enumerate() {
+        let list = (window[0].to_usize()..window[1].to_usize())
+            .map(|j| Coord { x: x[j], y: y[j] })
+            .collect();
+        let is_first = match first {
+            Some(first) => first[i],
+            None => true,
+        };
+        coords.extend(f(list, is_first));
+        new_offsets.push(O::from_usize(coords.len()).ok_or_else(|| {
+            PolarsError::ComputeError(ErrString::from("The list offsets overflow"))
+        })?);
+    }
+
+    let offsets = Offsets::try_from(new_offsets).map_err(PolarsError::from)?;
+    Ok(ListArray::<O>::new(
+        arr.data_type().clone(),
+        offsets.into(),
+        coord_array(coord_arr, coords),
+        arr.validity().cloned(),
+    )
+    .boxed())
+}
+
+/// The x and y buffers of a native coordinate array
+pub(crate) fn coord_buffers(arr: &StructArray) -> Result<(&[f64], &[f64])> {
+    let buffer = |i: usize| {
+        arr.values()[i]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<f64>>()
+            .map(|values| values.values().as_slice())
+            .ok_or_else(|| unexpected_coord_type(arr))
+    };
+    Ok((buffer(0)?, buffer(1)?))
+}
+
+/// A native coordinate array of the same data type as `like` holding `coords`
+pub(crate) fn coord_array(like: &StructArray, coords: Vec<Coord<f64>>) -> Box<dyn Array> {
+    let (x_values, y_values): (Vec<f64>, Vec<f64>) = coords.into_iter().map(|c| (c.x, c.y)).unzip();
+    let x = PrimitiveArray::new(like.values()[0].data_type().clone(), x_values.into(), None);
+    let y = PrimitiveArray::new(like.values()[1].data_type().clone(), y_values.into(), None);
+    StructArray::new(like.data_type().clone(), vec![x.boxed(), y.boxed()], None).boxed()
+}
+
+/// Create a Series with the name and geometry layout of `series` from chunks produced by an
+/// operation that maps each of its chunks, keeping any extension information on the chunks
+pub(crate) fn series_like(series: &Series, chunks: Vec<Box<dyn Array>>) -> Result<Series> {
+    match series.dtype() {
+        DataType::Binary => Ok(BinaryChunked::from_chunks(series.name(), chunks).into_series()),
+        DataType::List(_) => Ok(ListChunked::from_chunks(series.name(), chunks).into_series()),
+        DataType::Struct(_) => point_series(series.name(), chunks),
+        _ => Ok(Series::try_from((series.name(), chunks))?),
+    }
+}
@@ -0,0 +1,129 @@
+use geoarrow::{GeometryArray, GeometryArrayTrait, WKBArray};
+use geos::{GResult, Geom, Geometry, WKBWriter};
+use polars::export::arrow::array::{BinaryArray, MutableBinaryArray};
+
+/// Buffer each geometry by `width`, approximating quarter circles with `quadsegs` segments.
+/// Returns the buffered geometries as WKB.
+///
+/// Multi-part geometries have no direct conversion to GEOS and are passed to GEOS as WKB.
+pub fn buffer(array: GeometryArray, width: f64, quadsegs: i32) -> GResult<BinaryArray<i64>> {
+    let mut output_array = MutableBinaryArray::<i64>::with_capacity(array.len());
+    let mut writer = WKBWriter::new()?;
+
+    let mut push = |maybe_g: Option<Geometry>| -> GResult<()> {
+        match maybe_g {
+            Some(g) => {
+                let buffered = g.buffer(width, quadsegs)?;
+                output_array.push(Some(writer.write_wkb(&buffered)?.as_ref()));
+            }
+            None => output_array.push_null(),
+        }
+        Ok(())
+    };
+
+    match array {
+        GeometryArray::WKB(arr) => arr.iter_geos().try_for_each(&mut push)?,
+        GeometryArray::Point(arr) => arr.iter_geos().try_for_each(&mut push)?,
+        GeometryArray::LineString(arr) => arr.iter_geos().try_for_each(&mut push)?,
+        GeometryArray::Polygon(arr) => arr.iter_geos().try_for_each(&mut push)?,
+        GeometryArray::MultiPoint(arr) => wkb_array(arr.iter_geo())
+            .iter_geos()
+            .try_for_each(&mut push)?,
+        GeometryArray::MultiLineString(arr) => wkb_array(arr.iter_geo())
+            .iter_geos()
+            .try_for_each(&mut push)?,
+        GeometryArray::MultiPolygon(arr) => wkb_array(arr.iter_geo())
+            .iter_geos()
+            .try_for_each(&mut push)?,
+    }
+
+    Ok(output_array.into())
+}
+
+/// Encode geometries as WKB, keeping null rows
+fn wkb_array<G>(geoms: impl Iterator<Item = Option<G>>) -> WKBArray
+where
+    G: Into<geo::Geometry>,
+{
+    geoms
+        .map(|maybe_g| maybe_g.map(Into::into))
+        .collect::<Vec<Option<geo::Geometry>>>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::buffer;
+    use approx::assert_relative_eq;
+    use geo::{point, polygon, MultiPoint, MultiPolygon, Polygon};
+    use geoarrow::polygon::MutablePolygonArray;
+    use geoarrow::{GeometryArray, MultiPointArray, MultiPolygonArray, PolygonArray};
+    use geos::{Geom, Geometry};
+
+    #[test]
+    fn buffer_polygon() {
+        let polygons: Vec<Polygon> = vec![polygon![
+            (x: 0., y: 0.),
+            (x: 5., y: 0.),
+            (x: 5., y: 6.),
+            (x: 0., y: 6.),
+            (x: 0., y: 0.)
+        ]];
+        let mut_polygon_arr: MutablePolygonArray = polygons.into();
+        let polygon_arr: PolygonArray = mut_polygon_arr.into_arrow().try_into().unwrap();
+
+        let result = buffer(GeometryArray::Polygon(polygon_arr), 1., 16).unwrap();
+        let buffered = Geometry::new_from_wkb(result.value(0)).unwrap();
+
+        // The rectangle, its sides moved out by one and the corners filled with a 64-gon
+        // inscribed in the unit circle
+        assert_relative_eq!(
+            buffered.area().unwrap(),
+            30. + 22. + 3.136548,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn buffer_multi_polygon() {
+        let square = polygon![
+            (x: 0., y: 0.),
+            (x: 1., y: 0.),
+            (x: 1., y: 1.),
+            (x: 0., y: 1.),
+            (x: 0., y: 0.)
+        ];
+        let far_square = polygon![
+            (x: 10., y: 0.),
+            (x: 11., y: 0.),
+            (x: 11., y: 1.),
+            (x: 10., y: 1.),
+            (x: 10., y: 0.)
+        ];
+        let array: MultiPolygonArray = vec![MultiPolygon(vec![square, far_square])].into();
+
+        let result = buffer(GeometryArray::MultiPolygon(array), 1., 16).unwrap();
+        let buffered = Geometry::new_from_wkb(result.value(0)).unwrap();
+
+        // Two disjoint buffered unit squares
+        assert_relative_eq!(
+            buffered.area().unwrap(),
+            2. * (1. + 4. + 3.136548),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn buffer_multi_point() {
+        let array: MultiPointArray = vec![MultiPoint(vec![
+            point!(x: 0., y: 0.),
+            point!(x: 10., y: 0.),
+        ])]
+        .into();
+
+        let result = buffer(GeometryArray::MultiPoint(array), 1., 16).unwrap();
+        let buffered = Geometry::new_from_wkb(result.value(0)).unwrap();
+
+        assert_relative_eq!(buffered.area().unwrap(), 2. * 3.136548, epsilon = 1e-6);
+    }
+}
@@ -1 +1,2 @@
 pub mod area;
+pub mod buffer;
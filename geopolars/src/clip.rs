@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use geo::prelude::BoundingRect;
+use geo::Geometry;
+use polars::prelude::{DataFrame, IdxCa, IdxSize, NewChunkedArray};
+use rstar::AABB;
+
+use crate::error::Result;
+use crate::spatial_index::SpatialIndex;
+use geopolars_geo::geoseries::GeoSeries;
+
+/// Clip the geometries of a DataFrame to a polygonal mask
+///
+/// Rows whose geometry doesn't intersect the mask are dropped, and the geometry column of the
+/// others is replaced by the part of their geometry inside the mask, as in [`GeoSeries::clip`].
+/// Only the rows whose envelope intersects the envelope of the mask in the spatial index of the
+/// geometry column are clipped.
+///
+/// # Arguments
+///
+/// * `df` - The DataFrame to clip, with a `geometry` column.
+/// * `mask` - The Polygon, MultiPolygon or Rect to clip to, in the CRS of the geometry column.
+/// * `index` - The spatial index of the geometry column. If `None`, one is built on the fly.
+pub fn clip(
+    df: &DataFrame,
+    mask: &Geometry<f64>,
+    index: Option<Arc<SpatialIndex>>,
+) -> Result<DataFrame> {
+    let mask_bounds = match mask.bounding_rect() {
+        Some(bounds) => bounds,
+        None => return Ok(df.head(Some(0))),
+    };
+
+    let index = match index {
+        Some(index) => index,
+        None => Arc::new(SpatialIndex::try_from(df.column("geometry")?)?),
+    };
+    let envelope = AABB::from_corners(
+        [mask_bounds.min().x, mask_bounds.min().y],
+        [mask_bounds.max().x, mask_bounds.max().y],
+    );
+    let mut candidates: Vec<IdxSize> = index
+        .r_tree
+        .locate_in_envelope_intersecting(&envelope)
+        .map(|node| node.index as IdxSize)
+        .collect();
+    candidates.sort_unstable();
+
+    let mut output = df.take(&IdxCa::from_vec("index", candidates))?;
+    // Called as functions, as `Series` has inherent methods of the same names
+    let clipped = GeoSeries::clip(output.column("geometry")?, mask)?;
+    let intersects = !GeoSeries::is_empty(&clipped)?.bool()?;
+    output.with_column(clipped)?;
+    Ok(output.filter(&intersects)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::clip;
+    use crate::util::iter_geom;
+    use geo::prelude::Area;
+    use geo::{point, polygon, Geometry};
+    use geopolars_geo::util::from_geom_vec;
+    use polars::prelude::{DataFrame, NamedFrom, Series};
+
+    #[test]
+    fn clip_to_polygon() {
+        let geometry = from_geom_vec(&[
+            Geometry::Polygon(polygon![
+                (x: 1., y: 1.),
+                (x: 3., y: 1.),
+                (x: 3., y: 3.),
+                (x: 1., y: 3.),
+            ]),
+            Geometry::Point(point!(x: 10., y: 10.)),
+            Geometry::Point(point!(x: 0.5, y: 0.5)),
+        ])
+        .unwrap();
+        let values = Series::new("values", [1, 2, 3]);
+        let df = DataFrame::new(vec![geometry, values]).unwrap();
+
+        let mask = Geometry::Polygon(polygon![
+            (x: 0., y: 0.),
+            (x: 2., y: 0.),
+            (x: 2., y: 2.),
+            (x: 0., y: 2.),
+        ]);
+        let result = clip(&df, &mask, None).unwrap();
+
+        let values: Vec<Option<i32>> = result
+            .column("values")
+            .unwrap()
+            .i32()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(values, vec![Some(1), Some(3)]);
+
+        let geoms: Vec<Geometry> = iter_geom(result.column("geometry").unwrap()).collect();
+        assert!((geoms[0].unsigned_area() - 1.).abs() < 1e-12);
+        assert_eq!(geoms[1], Geometry::Point(point!(x: 0.5, y: 0.5)));
+    }
+}
@@ -0,0 +1,367 @@
+//! A `geo` namespace on Polars expressions, so that geometry operations can be used in lazy
+//! queries, e.g. in `select`, `with_columns` or `groupby().agg()`.
+//!
+//! ```ignore
+//! use geopolars::expr::{geom_lit, GeoExpr};
+//! use polars::prelude::*;
+//!
+//! let area_of_interest = geom_lit(polygon)?;
+//! df.lazy()
+//!     .filter(col("geometry").geo().intersects(area_of_interest))
+//!     .select([col("geometry").geo().area().alias("area")])
+//!     .collect()?;
+//! ```
+//!
+//! Each operation maps onto the [`GeoSeries`] method of the same name and is applied to every
+//! geometry of the expression. Aggregations such as [`GeoNameSpace::polygonize`] combine all
+//! geometries of the expression, or of each group in `groupby().agg()`, into one geometry.
+
+use crate::error::{GeopolarsError, Result};
+use geo::Geometry;
+use geopolars_geo::geoseries::GeoSeries;
+use geopolars_geo::ops::length::GeodesicLengthMethod;
+use geopolars_geo::ops::simplify::SimplifyMethod;
+use geopolars_geo::util::from_geom_vec;
+use polars::error::ErrString;
+use polars::prelude::{lit, DataType, Expr, Field, GetOutput, PolarsError, PolarsResult, Series};
+
+/// Geometry operations on an expression, see [`GeoExpr::geo`]
+pub struct GeoNameSpace(Expr);
+
+pub trait GeoExpr {
+    /// Access the geometry operations of an expression of a geometry column
+    fn geo(self) -> GeoNameSpace;
+}
+
+impl GeoExpr for Expr {
+    fn geo(self) -> GeoNameSpace {
+        GeoNameSpace(self)
+    }
+}
+
+/// A literal expression of a single geometry, to compare every geometry of a column against,
+/// e.g. with [`GeoNameSpace::intersects`]
+pub fn geom_lit(geom: Geometry<f64>) -> PolarsResult<Expr> {
+    let series = from_geom_vec(&[geom]).map_err(to_polars_error)?;
+    Ok(lit(series))
+}
+
+pub(crate) fn to_polars_error(err: GeopolarsError) -> PolarsError {
+    match err {
+        GeopolarsError::PolarsError(err) => *err,
+        err => PolarsError::ComputeError(ErrString::from(err.to_string())),
+    }
+}
+
+fn point_dtype() -> DataType {
+    DataType::Struct(vec![
+        Field::new("x", DataType::Float64),
+        Field::new("y", DataType::Float64),
+    ])
+}
+
+fn polygon_dtype() -> DataType {
+    DataType::List(Box::new(DataType::List(Box::new(point_dtype()))))
+}
+
+/// MultiLineStrings are stored like polygons, as lists of lists of points
+fn multi_line_string_dtype() -> DataType {
+    polygon_dtype()
+}
+
+fn multi_polygon_dtype() -> DataType {
+    DataType::List(Box::new(polygon_dtype()))
+}
+
+impl GeoNameSpace {
+    /// Apply a GeoSeries operation to the Series of the expression, keeping its name
+    fn map<F>(self, function: F, output_type: GetOutput) -> Expr
+    where
+        F: Fn(&Series) -> Result<Series> + Send + Sync + 'static,
+    {
+        self.0.map(
+            move |series| {
+                let mut output = function(&series).map_err(to_polars_error)?;
+                output.rename(series.name());
+                Ok(output)
+            },
+            output_type,
+        )
+    }
+
+    /// Apply a GeoSeries aggregation to the Series of the expression, or to each group of it in
+    /// an aggregation context, keeping its name
+    fn aggregate<F>(self, function: F, output_type: GetOutput) -> Expr
+    where
+        F: Fn(&Series) -> Result<Series> + Send + Sync + 'static,
+    {
+        self.0.apply(
+            move |series| {
+                let mut output = function(&series).map_err(to_polars_error)?;
+                output.rename(series.name());
+                Ok(output)
+            },
+            output_type,
+        )
+    }
+
+    /// Apply a GeoSeries operation to the Series of the expression and of `other`, keeping the
+    /// name of the former
+    fn map_binary<F>(self, other: Expr, function: F, output_type: GetOutput) -> Expr
+    where
+        F: Fn(&Series, &Series) -> Result<Series> + Send + Sync + 'static,
+    {
+        self.0.map_many(
+            move |series| {
+                let mut output = function(&series[0], &series[1]).map_err(to_polars_error)?;
+                output.rename(series[0].name());
+                Ok(output)
+            },
+            &[other],
+            output_type,
+        )
+    }
+
+    /// See [`GeoSeries::area`]
+    pub fn area(self) -> Expr {
+        self.map(|s| s.area(), GetOutput::from_type(DataType::Float64))
+    }
+
+    /// Buffer each geometry by `width`, in the units of its CRS, using GEOS. Round joins and caps
+    /// approximate quarter circles with 16 segments. The output is WKB.
+    #[cfg(feature = "geos")]
+    pub fn buffer(self, width: f64) -> Expr {
+        self.map(
+            move |s| buffer(s, width),
+            GetOutput::from_type(DataType::Binary),
+        )
+    }
+
+    /// See [`GeoSeries::bounds`]
+    pub fn bounds(self) -> Expr {
+        let fields = ["minx", "miny", "maxx", "maxy"]
+            .iter()
+            .map(|name| Field::new(name, DataType::Float64))
+            .collect();
+        self.map(
+            |s| s.bounds(),
+            GetOutput::from_type(DataType::Struct(fields)),
+        )
+    }
+
+    /// See [`GeoSeries::centroid`]
+    pub fn centroid(self) -> Expr {
+        self.map(|s| s.centroid(), GetOutput::from_type(point_dtype()))
+    }
+
+    /// See [`GeoSeries::convex_hull`]
+    pub fn convex_hull(self) -> Expr {
+        self.map(|s| s.convex_hull(), GetOutput::from_type(polygon_dtype()))
+    }
+
+    /// See [`GeoSeries::distance`]
+    pub fn distance(self, other: Expr) -> Expr {
+        self.map_binary(
+            other,
+            |s, other| s.distance(other),
+            GetOutput::from_type(DataType::Float64),
+        )
+    }
+
+    /// See [`GeoSeries::envelope`]
+    pub fn envelope(self) -> Expr {
+        self.map(|s| s.envelope(), GetOutput::from_type(polygon_dtype()))
+    }
+
+    /// See [`GeoSeries::euclidean_length`]
+    pub fn euclidean_length(self) -> Expr {
+        self.map(
+            |s| s.euclidean_length(),
+            GetOutput::from_type(DataType::Float64),
+        )
+    }
+
+    /// See [`GeoSeries::frechet_distance`]. `other` may be a geometry column or a single geometry
+    /// created with [`geom_lit`].
+    pub fn frechet_distance(self, other: Expr) -> Expr {
+        self.map_binary(
+            other,
+            |s, other| s.frechet_distance(other),
+            GetOutput::from_type(DataType::Float64),
+        )
+    }
+
+    /// See [`GeoSeries::geodesic_length`]
+    pub fn geodesic_length(self, method: GeodesicLengthMethod) -> Expr {
+        self.map(
+            move |s| s.geodesic_length(method),
+            GetOutput::from_type(DataType::Float64),
+        )
+    }
+
+    /// See [`GeoSeries::geom_type`]
+    pub fn geom_type(self) -> Expr {
+        self.map(|s| s.geom_type(), GetOutput::from_type(DataType::Int8))
+    }
+
+    /// See [`GeoSeries::hausdorff_distance`]. `other` may be a geometry column or a single
+    /// geometry created with [`geom_lit`].
+    pub fn hausdorff_distance(self, other: Expr, densify: Option<f64>) -> Expr {
+        self.map_binary(
+            other,
+            move |s, other| s.hausdorff_distance(other, densify),
+            GetOutput::from_type(DataType::Float64),
+        )
+    }
+
+    /// See [`GeoSeries::intersects`]. `other` may be a geometry column or a single geometry
+    /// created with [`geom_lit`].
+    pub fn intersects(self, other: Expr) -> Expr {
+        self.map_binary(
+            other,
+            |s, other| s.intersects(other),
+            GetOutput::from_type(DataType::Boolean),
+        )
+    }
+
+    /// See [`GeoSeries::is_empty`]
+    pub fn is_empty(self) -> Expr {
+        self.map(|s| s.is_empty(), GetOutput::from_type(DataType::Boolean))
+    }
+
+    /// See [`GeoSeries::is_ring`]
+    pub fn is_ring(self) -> Expr {
+        self.map(|s| s.is_ring(), GetOutput::from_type(DataType::Boolean))
+    }
+
+    /// See [`GeoSeries::line_merge`]
+    pub fn line_merge(self) -> Expr {
+        self.aggregate(
+            |s| s.line_merge(),
+            GetOutput::from_type(multi_line_string_dtype()),
+        )
+    }
+
+    /// See [`GeoSeries::node`]
+    pub fn node(self) -> Expr {
+        self.aggregate(
+            |s| s.node(),
+            GetOutput::from_type(multi_line_string_dtype()),
+        )
+    }
+
+    /// See [`GeoSeries::polygonize`]
+    pub fn polygonize(self) -> Expr {
+        self.aggregate(
+            |s| s.polygonize(),
+            GetOutput::from_type(multi_polygon_dtype()),
+        )
+    }
+
+    /// See [`GeoSeries::simplify`]
+    pub fn simplify(self, tolerance: f64) -> Expr {
+        self.map(move |s| s.simplify(tolerance), GetOutput::same_type())
+    }
+
+    /// See [`GeoSeries::simplify_with`]
+    pub fn simplify_with(self, tolerance: f64, method: SimplifyMethod) -> Expr {
+        self.map(
+            move |s| s.simplify_with(tolerance, method),
+            GetOutput::same_type(),
+        )
+    }
+
+    /// See [`GeoSeries::to_crs`]
+    pub fn to_crs(self, to: &str) -> Expr {
+        let to = to.to_string();
+        self.map(move |s| s.to_crs(&to), GetOutput::same_type())
+    }
+
+    /// See [`GeoSeries::x`]
+    pub fn x(self) -> Expr {
+        self.map(|s| s.x(), GetOutput::from_type(DataType::Float64))
+    }
+
+    /// See [`GeoSeries::y`]
+    pub fn y(self) -> Expr {
+        self.map(|s| s.y(), GetOutput::from_type(DataType::Float64))
+    }
+}
+
+#[cfg(feature = "geos")]
+fn buffer(series: &Series, width: f64) -> Result<Series> {
+    use geopolars_geo::crs::{crs, set_crs};
+    use geopolars_geo::util::geometry_array;
+    use polars::export::arrow::array::Array;
+    use polars::prelude::BinaryChunked;
+    use polars::series::IntoSeries;
+
+    let chunks = series
+        .chunks()
+        .iter()
+        .map(|chunk| {
+            let array = geometry_array(&**chunk)?;
+            geopolars_geos::ops::buffer::buffer(array, width, 16)
+                .map(|arr| arr.boxed())
+                .map_err(|err| {
+                    GeopolarsError::from(PolarsError::ComputeError(ErrString::from(
+                        err.to_string(),
+                    )))
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let output = BinaryChunked::from_chunks(series.name(), chunks).into_series();
+
+    match crs(series)? {
+        Some(crs) => set_crs(&output, Some(&crs)),
+        None => Ok(output),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{geom_lit, GeoExpr};
+    use geo::{point, polygon, Geometry};
+    use geopolars_geo::util::from_geom_vec;
+    use polars::prelude::{col, DataFrame, IntoLazy};
+
+    #[test]
+    fn lazy_select_and_filter() {
+        let geometry = from_geom_vec(&[
+            Geometry::Polygon(polygon![
+                (x: 0., y: 0.),
+                (x: 2., y: 0.),
+                (x: 2., y: 2.),
+                (x: 0., y: 2.),
+            ]),
+            Geometry::Polygon(polygon![
+                (x: 10., y: 10.),
+                (x: 11., y: 10.),
+                (x: 11., y: 11.),
+                (x: 10., y: 11.),
+            ]),
+        ])
+        .unwrap();
+        let df = DataFrame::new(vec![geometry]).unwrap();
+
+        let result = df
+            .lazy()
+            .filter(
+                col("geometry")
+                    .geo()
+                    .intersects(geom_lit(Geometry::Point(point!(x: 1., y: 1.))).unwrap()),
+            )
+            .select([col("geometry").geo().area().alias("area")])
+            .collect()
+            .unwrap();
+
+        let area: Vec<Option<f64>> = result
+            .column("area")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(area, vec![Some(4.)]);
+    }
+}
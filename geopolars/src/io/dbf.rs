@@ -0,0 +1,471 @@
+//! Minimal dBase III reader and writer for Shapefile attribute tables
+
+use crate::error::{GeopolarsError, Result};
+use encoding_rs::{Encoding, WINDOWS_1252};
+use polars::error::ErrString;
+use polars::prelude::{AnyValue, DataFrame, DataType, NamedFrom, PolarsError, Series};
+use std::io::{Read, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const HEADER_LENGTH: usize = 32;
+const FIELD_DESCRIPTOR_LENGTH: usize = 32;
+const HEADER_TERMINATOR: u8 = 0x0D;
+const FILE_TERMINATOR: u8 = 0x1A;
+const MAX_CHARACTER_LENGTH: usize = 254;
+const MAX_FIELD_NAME_LENGTH: usize = 10;
+
+struct FieldDescriptor {
+    name: String,
+    field_type: u8,
+    length: usize,
+    decimal_count: usize,
+}
+
+/// Resolve the text encoding of a DBF file.
+///
+/// The contents of a `.cpg` sidecar file take precedence over the language driver id stored in
+/// the DBF header. Files that specify neither are decoded as Windows-1252, matching GDAL's
+/// default of ISO-8859-1 for all printable characters.
+pub(crate) fn resolve_encoding(cpg: Option<&str>, language_driver_id: u8) -> &'static Encoding {
+    if let Some(label) = cpg.map(str::trim) {
+        if let Some(encoding) = label
+            .parse::<u16>()
+            .ok()
+            .and_then(code_page_encoding)
+            .or_else(|| Encoding::for_label(label.as_bytes()))
+        {
+            return encoding;
+        }
+
+        // Labels such as "ANSI 1251" carry the code page as the last word
+        if let Some(encoding) = label
+            .rsplit(' ')
+            .next()
+            .and_then(|code_page| code_page.parse::<u16>().ok())
+            .and_then(code_page_encoding)
+        {
+            return encoding;
+        }
+    }
+
+    language_driver_code_page(language_driver_id)
+        .and_then(code_page_encoding)
+        .unwrap_or(WINDOWS_1252)
+}
+
+/// Map a Windows or DOS code page number onto an encoding
+fn code_page_encoding(code_page: u16) -> Option<&'static Encoding> {
+    let label = match code_page {
+        866 => "ibm866",
+        874 => "windows-874",
+        932 => "shift_jis",
+        936 => "gbk",
+        949 => "euc-kr",
+        950 => "big5",
+        1250..=1258 => return Encoding::for_label(format!("windows-{code_page}").as_bytes()),
+        65001 => "utf-8",
+        _ => return None,
+    };
+    Encoding::for_label(label.as_bytes())
+}
+
+/// Map the language driver id in byte 29 of the DBF header onto a code page
+fn language_driver_code_page(language_driver_id: u8) -> Option<u16> {
+    let code_page = match language_driver_id {
+        0x03 | 0x57..=0x59 => 1252,
+        0x13 | 0x7B => 932,
+        0x26 | 0x65 => 866,
+        0x4D | 0x7A => 936,
+        0x4E | 0x79 => 949,
+        0x4F | 0x78 => 950,
+        0x7C => 874,
+        0xC8 => 1250,
+        0xC9 => 1251,
+        0xCA => 1254,
+        0xCB => 1253,
+        0xCC => 1257,
+        _ => return None,
+    };
+    Some(code_page)
+}
+
+fn dbf_error(msg: &str) -> GeopolarsError {
+    PolarsError::ComputeError(ErrString::from(format!("Invalid DBF file: {msg}"))).into()
+}
+
+/// Read a DBF table into one typed Series per field
+///
+/// `cpg` holds the contents of the `.cpg` sidecar file, if one exists.
+pub(crate) fn read_dbf<R: Read>(reader: &mut R, cpg: Option<&str>) -> Result<Vec<Series>> {
+    let mut header = [0_u8; HEADER_LENGTH];
+    reader.read_exact(&mut header)?;
+
+    let num_records = u32::from_le_bytes(header[4..8].try_into().unwrap()) as usize;
+    let header_length = u16::from_le_bytes(header[8..10].try_into().unwrap()) as usize;
+    let record_length = u16::from_le_bytes(header[10..12].try_into().unwrap()) as usize;
+    let encoding = resolve_encoding(cpg, header[29]);
+
+    if header_length < HEADER_LENGTH + 1 {
+        return Err(dbf_error("header is too short"));
+    }
+
+    let mut descriptors = vec![0_u8; header_length - HEADER_LENGTH];
+    reader.read_exact(&mut descriptors)?;
+
+    let mut fields = Vec::new();
+    for descriptor in descriptors.chunks_exact(FIELD_DESCRIPTOR_LENGTH) {
+        if descriptor[0] == HEADER_TERMINATOR {
+            break;
+        }
+        let name_length = descriptor[..11].iter().position(|b| *b == 0).unwrap_or(11);
+        let (name, _, _) = encoding.decode(&descriptor[..name_length]);
+        fields.push(FieldDescriptor {
+            name: name.trim().to_string(),
+            field_type: descriptor[11].to_ascii_uppercase(),
+            length: descriptor[16] as usize,
+            decimal_count: descriptor[17] as usize,
+        });
+    }
+
+    if record_length != 1 + fields.iter().map(|field| field.length).sum::<usize>() {
+        return Err(dbf_error("record length does not match field descriptors"));
+    }
+
+    let mut columns: Vec<ColumnBuilder> = fields.iter().map(ColumnBuilder::new).collect();
+    let mut record = vec![0_u8; record_length];
+    for _ in 0..num_records {
+        // Deleted records are kept so that rows stay aligned with the shapes in the .shp file
+        reader.read_exact(&mut record)?;
+
+        let mut offset = 1;
+        for (field, column) in fields.iter().zip(columns.iter_mut()) {
+            column.push(&record[offset..offset + field.length], encoding);
+            offset += field.length;
+        }
+    }
+
+    fields
+        .iter()
+        .zip(columns)
+        .map(|(field, column)| column.into_series(&field.name))
+        .collect()
+}
+
+enum ColumnBuilder {
+    Utf8(Vec<Option<String>>),
+    Int32(Vec<Option<i32>>),
+    Int64(Vec<Option<i64>>),
+    Float64(Vec<Option<f64>>),
+    /// Binary little-endian doubles, stored with field type `O`
+    Double(Vec<Option<f64>>),
+    Boolean(Vec<Option<bool>>),
+    Date(Vec<Option<i32>>),
+}
+
+impl ColumnBuilder {
+    fn new(field: &FieldDescriptor) -> Self {
+        match field.field_type {
+            // Wide enough for any i64; values that still overflow turn the column into floats
+            b'N' if field.decimal_count == 0 && field.length <= 20 => Self::Int64(vec![]),
+            b'N' | b'F' => Self::Float64(vec![]),
+            b'O' => Self::Double(vec![]),
+            b'I' => Self::Int32(vec![]),
+            b'L' => Self::Boolean(vec![]),
+            b'D' => Self::Date(vec![]),
+            _ => Self::Utf8(vec![]),
+        }
+    }
+
+    fn push(&mut self, raw: &[u8], encoding: &'static Encoding) {
+        match self {
+            Self::Utf8(values) => {
+                let (text, _, _) = encoding.decode(raw);
+                let text = text.trim_end_matches([' ', '\0']);
+                values.push((!text.is_empty()).then(|| text.to_string()));
+            }
+            Self::Int32(values) => {
+                values.push(raw.try_into().ok().map(i32::from_le_bytes));
+            }
+            Self::Int64(values) => match (parse_ascii::<i64>(raw), parse_ascii::<f64>(raw)) {
+                (None, Some(value)) => {
+                    let mut floats: Vec<Option<f64>> =
+                        values.iter().map(|v| v.map(|v| v as f64)).collect();
+                    floats.push(Some(value));
+                    *self = Self::Float64(floats);
+                }
+                (value, _) => values.push(value),
+            },
+            Self::Float64(values) => values.push(parse_ascii(raw)),
+            Self::Double(values) => {
+                values.push(raw.try_into().ok().map(f64::from_le_bytes));
+            }
+            Self::Boolean(values) => values.push(match raw.first() {
+                Some(b'T' | b't' | b'Y' | b'y') => Some(true),
+                Some(b'F' | b'f' | b'N' | b'n') => Some(false),
+                _ => None,
+            }),
+            Self::Date(values) => values.push(parse_date(raw)),
+        }
+    }
+
+    fn into_series(self, name: &str) -> Result<Series> {
+        let series = match self {
+            Self::Utf8(values) => Series::new(name, values),
+            Self::Int32(values) => Series::new(name, values),
+            Self::Int64(values) => Series::new(name, values),
+            Self::Float64(values) | Self::Double(values) => Series::new(name, values),
+            Self::Boolean(values) => Series::new(name, values),
+            Self::Date(values) => Series::new(name, values).cast(&DataType::Date)?,
+        };
+        Ok(series)
+    }
+}
+
+fn parse_ascii<T: std::str::FromStr>(raw: &[u8]) -> Option<T> {
+    std::str::from_utf8(raw).ok()?.trim().parse().ok()
+}
+
+/// Parse a `YYYYMMDD` date into days since the unix epoch
+fn parse_date(raw: &[u8]) -> Option<i32> {
+    let text = std::str::from_utf8(raw).ok()?.trim();
+    if text.len() != 8 {
+        return None;
+    }
+    let year: i32 = text[0..4].parse().ok()?;
+    let month: i32 = text[4..6].parse().ok()?;
+    let day: i32 = text[6..8].parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day))
+}
+
+/// Days since the unix epoch for a proleptic Gregorian date
+// From: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i32, month: i32, day: i32) -> i32 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of [`days_from_civil`]
+// From: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i32) -> (i32, i32, i32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i32::from(month <= 2);
+    (year, month, day)
+}
+
+/// Write the non-geometry columns of a DataFrame as a UTF-8 encoded DBF table
+pub(crate) fn write_dbf<W: Write>(df: &DataFrame, writer: &mut W) -> Result<()> {
+    let columns: Vec<&Series> = df
+        .get_columns()
+        .iter()
+        .filter(|series| series.name() != "geometry")
+        .collect();
+
+    let names = field_names(columns.iter().map(|series| series.name()));
+    let fields = columns
+        .iter()
+        .zip(names)
+        .map(|(series, name)| field_descriptor(series, name))
+        .collect::<Result<Vec<_>>>()?;
+
+    let record_length = 1 + fields.iter().map(|field| field.length).sum::<usize>();
+    let header_length = HEADER_LENGTH + FIELD_DESCRIPTOR_LENGTH * fields.len() + 1;
+
+    let mut header = [0_u8; HEADER_LENGTH];
+    // dBase III without memo
+    header[0] = 0x03;
+    // Date of last update, with the year stored as an offset from 1900
+    let today = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| (duration.as_secs() / 86_400) as i32)
+        .unwrap_or_default();
+    let (year, month, day) = civil_from_days(today);
+    header[1..4].copy_from_slice(&[(year - 1900) as u8, month as u8, day as u8]);
+    header[4..8].copy_from_slice(&(df.height() as u32).to_le_bytes());
+    header[8..10].copy_from_slice(&(header_length as u16).to_le_bytes());
+    header[10..12].copy_from_slice(&(record_length as u16).to_le_bytes());
+    writer.write_all(&header)?;
+
+    for field in &fields {
+        let mut descriptor = [0_u8; FIELD_DESCRIPTOR_LENGTH];
+        let name = field.name.as_bytes();
+        descriptor[..name.len()].copy_from_slice(name);
+        descriptor[11] = field.field_type;
+        descriptor[16] = field.length as u8;
+        descriptor[17] = field.decimal_count as u8;
+        writer.write_all(&descriptor)?;
+    }
+    writer.write_all(&[HEADER_TERMINATOR])?;
+
+    let mut record = Vec::with_capacity(record_length);
+    for row_idx in 0..df.height() {
+        record.clear();
+        record.push(b' ');
+        for (series, field) in columns.iter().zip(fields.iter()) {
+            encode_value(&series.get(row_idx)?, field, &mut record)?;
+        }
+        writer.write_all(&record)?;
+    }
+    writer.write_all(&[FILE_TERMINATOR])?;
+
+    Ok(())
+}
+
+/// Truncate column names to the 10 bytes a DBF field name can hold, keeping them unique
+///
+/// Names that clash after truncation, compared case-insensitively, get a numeric suffix.
+fn field_names<'a>(names: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::new();
+    for name in names {
+        let mut candidate = truncate(name, MAX_FIELD_NAME_LENGTH).to_string();
+        let mut suffix = 1;
+        while unique
+            .iter()
+            .any(|other| other.eq_ignore_ascii_case(&candidate))
+        {
+            let tail = format!("_{suffix}");
+            candidate = format!(
+                "{}{tail}",
+                truncate(name, MAX_FIELD_NAME_LENGTH - tail.len())
+            );
+            suffix += 1;
+        }
+        unique.push(candidate);
+    }
+    unique
+}
+
+/// The longest prefix of `text` with at most `length` bytes that doesn't split a character
+fn truncate(text: &str, length: usize) -> &str {
+    if text.len() <= length {
+        return text;
+    }
+    let mut end = length;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+fn field_descriptor(series: &Series, name: String) -> Result<FieldDescriptor> {
+    let (field_type, length, decimal_count) = match series.dtype() {
+        DataType::Boolean => (b'L', 1, 0),
+        DataType::Int8 | DataType::UInt8 | DataType::Int32 | DataType::UInt32 => (b'N', 11, 0),
+        DataType::Int64 | DataType::UInt64 => (b'N', 20, 0),
+        // Written in exponent notation so that every value fits and round-trips exactly
+        DataType::Float32 | DataType::Float64 => (b'F', 24, 15),
+        DataType::Date => (b'D', 8, 0),
+        DataType::Utf8 => {
+            let max_length = series
+                .utf8()?
+                .into_iter()
+                .flatten()
+                .map(str::len)
+                .max()
+                .unwrap_or(1);
+            (b'C', max_length.clamp(1, MAX_CHARACTER_LENGTH), 0)
+        }
+        dt => {
+            return Err(PolarsError::ComputeError(ErrString::from(format!(
+                "Cannot write column of type {dt} to DBF"
+            )))
+            .into())
+        }
+    };
+
+    Ok(FieldDescriptor {
+        name,
+        field_type,
+        length,
+        decimal_count,
+    })
+}
+
+fn encode_value(value: &AnyValue, field: &FieldDescriptor, record: &mut Vec<u8>) -> Result<()> {
+    let text = match value {
+        AnyValue::Boolean(v) => (if *v { "T" } else { "F" }).to_string(),
+        AnyValue::Int8(v) => v.to_string(),
+        AnyValue::UInt8(v) => v.to_string(),
+        AnyValue::Int32(v) => v.to_string(),
+        AnyValue::UInt32(v) => v.to_string(),
+        AnyValue::Int64(v) => v.to_string(),
+        AnyValue::UInt64(v) => v.to_string(),
+        AnyValue::Float32(v) => format!("{v:e}"),
+        AnyValue::Float64(v) => format!("{v:e}"),
+        AnyValue::Date(v) => {
+            let (year, month, day) = civil_from_days(*v);
+            format!("{year:04}{month:02}{day:02}")
+        }
+        AnyValue::Utf8(v) => v.to_string(),
+        _ => String::new(),
+    };
+
+    let numeric = matches!(field.field_type, b'N' | b'F');
+    if numeric && text.len() > field.length {
+        return Err(PolarsError::ComputeError(ErrString::from(format!(
+            "Value {text} of column {} does not fit into {} characters",
+            field.name, field.length
+        )))
+        .into());
+    }
+
+    // Text longer than a character field is cut off without splitting a multi-byte character
+    let bytes = truncate(&text, field.length).as_bytes();
+    let padding = field.length - bytes.len();
+    if numeric {
+        // Numbers are right-aligned
+        record.extend(std::iter::repeat(b' ').take(padding));
+        record.extend(bytes);
+    } else {
+        record.extend(bytes);
+        record.extend(std::iter::repeat(b' ').take(padding));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{civil_from_days, days_from_civil, field_names, resolve_encoding};
+    use encoding_rs::{UTF_8, WINDOWS_1251, WINDOWS_1252};
+
+    #[test]
+    fn encoding_from_cpg() {
+        assert_eq!(resolve_encoding(Some("UTF-8\n"), 0x57), UTF_8);
+        assert_eq!(resolve_encoding(Some("1251"), 0x00), WINDOWS_1251);
+        assert_eq!(resolve_encoding(Some("ANSI 1251"), 0x00), WINDOWS_1251);
+    }
+
+    #[test]
+    fn encoding_from_language_driver() {
+        assert_eq!(resolve_encoding(None, 0xC9), WINDOWS_1251);
+        assert_eq!(resolve_encoding(None, 0x00), WINDOWS_1252);
+    }
+
+    #[test]
+    fn date_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        let days = days_from_civil(2022, 12, 31);
+        assert_eq!(civil_from_days(days), (2022, 12, 31));
+    }
+
+    #[test]
+    fn unique_field_names() {
+        let names =
+            field_names(["population_2020", "population_2021", "POPULATION", "area"].into_iter());
+        assert_eq!(names, ["population", "populati_1", "populati_2", "area"]);
+    }
+}
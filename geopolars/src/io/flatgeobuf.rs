@@ -0,0 +1,523 @@
+use crate::error::{GeopolarsError, Result};
+use crate::util::{geom_at_index, get_geoarrow_type, GeoArrowType};
+use ::flatgeobuf::{
+    ColumnType, Crs, CrsArgs, FallibleStreamingIterator, FgbReader, FgbWriter, GeometryType, Header,
+};
+use geo::Geometry;
+use geopolars_geo::geoseries::GeoSeries;
+use geozero::{ColumnValue, CoordDimensions, PropertyProcessor, ToWkb};
+use polars::error::ErrString;
+use polars::export::arrow::array::{Array, BinaryArray};
+use polars::prelude::{
+    AnyValue, DataFrame, DataType, NamedFrom, PolarsError, PolarsResult, Series,
+};
+use std::io::{Read, Seek, Write};
+
+/// Options for reading a FlatGeobuf file
+#[derive(Default, Clone)]
+pub struct FlatGeobufReadOptions {
+    /// Only read features whose bounding box intersects `[minx, miny, maxx, maxy]`.
+    ///
+    /// The filter is evaluated against the file's packed R-tree, so features outside of the box
+    /// are never decoded. The file must have been written with a spatial index.
+    pub bbox: Option<[f64; 4]>,
+}
+
+/// Options for writing a FlatGeobuf file
+#[derive(Clone)]
+pub struct FlatGeobufWriteOptions<'a> {
+    /// Name of the dataset, stored in the file header
+    pub name: &'a str,
+
+    /// Whether to write a packed R-tree spatial index. The index is required for reading the file
+    /// with a bounding box filter.
+    pub write_index: bool,
+}
+
+impl<'a> Default for FlatGeobufWriteOptions<'a> {
+    fn default() -> Self {
+        Self {
+            name: "geopolars",
+            write_index: true,
+        }
+    }
+}
+
+/// Read a FlatGeobuf file into a DataFrame
+///
+/// Geometries are stored as WKB in a column named `geometry`. Every property column declared in
+/// the file header becomes a typed column in the output DataFrame.
+pub fn read_flatgeobuf<R: Read + Seek>(
+    reader: &mut R,
+    options: FlatGeobufReadOptions,
+) -> Result<DataFrame> {
+    let fgb = FgbReader::open(reader)?;
+    let crs = fgb.header().crs().and_then(|crs| {
+        if crs.code() != 0 {
+            Some(format!("{}:{}", crs.org().unwrap_or("EPSG"), crs.code()))
+        } else {
+            crs.wkt().map(|wkt| wkt.to_string())
+        }
+    });
+
+    let mut properties = PropertyColumns::from_header(fgb.header());
+
+    let mut fgb = match options.bbox {
+        Some([minx, miny, maxx, maxy]) => fgb.select_bbox(minx, miny, maxx, maxy)?,
+        None => fgb.select_all()?,
+    };
+
+    let mut wkb_values: Vec<Option<Vec<u8>>> = Vec::new();
+    while let Some(feature) = fgb.next()? {
+        wkb_values.push(Some(feature.to_wkb(CoordDimensions::xy())?));
+
+        properties.push_null_row();
+        feature.process_properties(&mut properties)?;
+    }
+
+    let wkb_array = BinaryArray::<i32>::from(&wkb_values);
+    let mut geometry = Series::try_from(("geometry", Box::new(wkb_array) as Box<dyn Array>))?;
+    if let Some(crs) = crs {
+        geometry = geometry.set_crs(&crs)?;
+    }
+
+    let mut columns = vec![geometry];
+    columns.extend(properties.finish()?);
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Read the schema of a FlatGeobuf file, with the column types [`read_flatgeobuf`] reads them as,
+/// from the file header
+pub(crate) fn read_flatgeobuf_schema<R: Read + Seek>(
+    reader: &mut R,
+) -> Result<polars::prelude::Schema> {
+    let fgb = FgbReader::open(reader)?;
+    let mut columns = vec![Series::new_empty("geometry", &DataType::Binary)];
+    columns.extend(PropertyColumns::from_header(fgb.header()).finish()?);
+    Ok(DataFrame::new(columns)?.schema())
+}
+
+/// Write a DataFrame to a FlatGeobuf file
+///
+/// The DataFrame must have a column named `geometry` without nulls. All other columns are written
+/// as feature properties; null values are omitted from the feature. The geometry type and CRS of
+/// the geometry column, if any, are stored in the file header.
+///
+/// The geometry type of a WKB column is the type shared by all of its geometries, and `Unknown`
+/// if they have different types.
+pub fn write_flatgeobuf<W: Write>(
+    df: &DataFrame,
+    writer: &mut W,
+    options: FlatGeobufWriteOptions,
+) -> Result<()> {
+    let geometry = df.column("geometry")?;
+    let properties: Vec<&Series> = df
+        .get_columns()
+        .iter()
+        .filter(|series| series.name() != "geometry")
+        .collect();
+
+    let crs = geometry.crs()?;
+
+    let is_null = geometry.is_null();
+    let geoms = (0..df.height())
+        .map(|row_idx| {
+            if is_null.get(row_idx) == Some(true) {
+                return Err(PolarsError::ComputeError(ErrString::from(format!(
+                    "Cannot write the null geometry of row {} to FlatGeobuf",
+                    row_idx
+                ))));
+            }
+            geom_at_index(geometry, row_idx)
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let geometry_type = match get_geoarrow_type(geometry)? {
+        GeoArrowType::Point => GeometryType::Point,
+        GeoArrowType::LineString => GeometryType::LineString,
+        GeoArrowType::Polygon => GeometryType::Polygon,
+        GeoArrowType::MultiPoint => GeometryType::MultiPoint,
+        GeoArrowType::MultiLineString => GeometryType::MultiLineString,
+        GeoArrowType::MultiPolygon => GeometryType::MultiPolygon,
+        GeoArrowType::WKB => common_geometry_type(&geoms),
+    };
+
+    let mut fgb = FgbWriter::create(options.name, geometry_type, |fbb, header| {
+        if !options.write_index {
+            header.index_node_size = 0;
+        }
+        if let Some(crs) = &crs {
+            // Store authority codes like "EPSG:4326" as such, anything else as WKT
+            let authority = crs
+                .split_once(':')
+                .and_then(|(org, code)| Some((org, code.parse::<i32>().ok()?)));
+            let args = match authority {
+                Some((org, code)) => CrsArgs {
+                    org: Some(fbb.create_string(org)),
+                    code,
+                    ..Default::default()
+                },
+                None => CrsArgs {
+                    wkt: Some(fbb.create_string(crs)),
+                    ..Default::default()
+                },
+            };
+            header.crs = Some(Crs::create(fbb, &args));
+        }
+    })?;
+
+    for series in &properties {
+        let column_type = column_type(series.dtype())?;
+        fgb.add_column(series.name(), column_type, |_, column| {
+            column.nullable = true;
+        });
+    }
+
+    for (row_idx, geom) in geoms.into_iter().enumerate() {
+        let values = properties
+            .iter()
+            .map(|series| series.get(row_idx))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let mut property_result = Ok(());
+        fgb.add_feature_geom(geom, |feature| {
+            for (column_idx, (series, value)) in properties.iter().zip(&values).enumerate() {
+                if let Some(value) = column_value(value) {
+                    property_result = property_result.and_then(|_| {
+                        feature.property(column_idx, series.name(), &value)?;
+                        Ok(())
+                    });
+                }
+            }
+        })?;
+        property_result?;
+    }
+
+    fgb.write(writer)?;
+    Ok(())
+}
+
+/// The FlatGeobuf geometry type shared by all geometries, or `Unknown` if they have different
+/// types
+fn common_geometry_type(geoms: &[Geometry]) -> GeometryType {
+    let geometry_type = |geom: &Geometry| match geom {
+        Geometry::Point(_) => GeometryType::Point,
+        Geometry::LineString(_) | Geometry::Line(_) => GeometryType::LineString,
+        Geometry::Polygon(_) | Geometry::Rect(_) | Geometry::Triangle(_) => GeometryType::Polygon,
+        Geometry::MultiPoint(_) => GeometryType::MultiPoint,
+        Geometry::MultiLineString(_) => GeometryType::MultiLineString,
+        Geometry::MultiPolygon(_) => GeometryType::MultiPolygon,
+        Geometry::GeometryCollection(_) => GeometryType::GeometryCollection,
+    };
+    let mut types = geoms.iter().map(geometry_type);
+    match types.next() {
+        Some(first) if types.all(|other| other == first) => first,
+        _ => GeometryType::Unknown,
+    }
+}
+
+/// Map a Polars data type onto the FlatGeobuf column type used to store it
+fn column_type(dtype: &DataType) -> Result<ColumnType> {
+    let column_type = match dtype {
+        DataType::Boolean => ColumnType::Bool,
+        DataType::Int8 => ColumnType::Byte,
+        DataType::UInt8 => ColumnType::UByte,
+        DataType::Int32 => ColumnType::Int,
+        DataType::UInt32 => ColumnType::UInt,
+        DataType::Int64 => ColumnType::Long,
+        DataType::UInt64 => ColumnType::ULong,
+        DataType::Float32 => ColumnType::Float,
+        DataType::Float64 => ColumnType::Double,
+        DataType::Utf8 => ColumnType::String,
+        DataType::Binary => ColumnType::Binary,
+        dt => {
+            return Err(GeopolarsError::from(PolarsError::ComputeError(
+                ErrString::from(format!("Cannot write column of type {dt} to FlatGeobuf")),
+            )))
+        }
+    };
+    Ok(column_type)
+}
+
+fn column_value<'a>(value: &'a AnyValue) -> Option<ColumnValue<'a>> {
+    match value {
+        AnyValue::Boolean(v) => Some(ColumnValue::Bool(*v)),
+        AnyValue::Int8(v) => Some(ColumnValue::Byte(*v)),
+        AnyValue::UInt8(v) => Some(ColumnValue::UByte(*v)),
+        AnyValue::Int32(v) => Some(ColumnValue::Int(*v)),
+        AnyValue::UInt32(v) => Some(ColumnValue::UInt(*v)),
+        AnyValue::Int64(v) => Some(ColumnValue::Long(*v)),
+        AnyValue::UInt64(v) => Some(ColumnValue::ULong(*v)),
+        AnyValue::Float32(v) => Some(ColumnValue::Float(*v)),
+        AnyValue::Float64(v) => Some(ColumnValue::Double(*v)),
+        AnyValue::Utf8(v) => Some(ColumnValue::String(v)),
+        AnyValue::Binary(v) => Some(ColumnValue::Binary(v)),
+        _ => None,
+    }
+}
+
+/// Builder for a single property column. Every feature first pushes a null, which is then
+/// overwritten if the feature has a value for the property.
+enum PropertyColumn {
+    Boolean(Vec<Option<bool>>),
+    Int8(Vec<Option<i8>>),
+    UInt8(Vec<Option<u8>>),
+    Int32(Vec<Option<i32>>),
+    UInt32(Vec<Option<u32>>),
+    Int64(Vec<Option<i64>>),
+    UInt64(Vec<Option<u64>>),
+    Float32(Vec<Option<f32>>),
+    Float64(Vec<Option<f64>>),
+    Utf8(Vec<Option<String>>),
+    Binary(Vec<Option<Vec<u8>>>),
+}
+
+impl PropertyColumn {
+    fn new(column_type: ColumnType) -> Self {
+        match column_type {
+            ColumnType::Bool => Self::Boolean(vec![]),
+            ColumnType::Byte => Self::Int8(vec![]),
+            ColumnType::UByte => Self::UInt8(vec![]),
+            // Polars is compiled without 16-bit integer support
+            ColumnType::Short | ColumnType::Int => Self::Int32(vec![]),
+            ColumnType::UShort | ColumnType::UInt => Self::UInt32(vec![]),
+            ColumnType::Long => Self::Int64(vec![]),
+            ColumnType::ULong => Self::UInt64(vec![]),
+            ColumnType::Float => Self::Float32(vec![]),
+            ColumnType::Double => Self::Float64(vec![]),
+            ColumnType::Binary => Self::Binary(vec![]),
+            // String, Json and DateTime are all stored as strings
+            _ => Self::Utf8(vec![]),
+        }
+    }
+
+    fn push_null(&mut self) {
+        match self {
+            Self::Boolean(values) => values.push(None),
+            Self::Int8(values) => values.push(None),
+            Self::UInt8(values) => values.push(None),
+            Self::Int32(values) => values.push(None),
+            Self::UInt32(values) => values.push(None),
+            Self::Int64(values) => values.push(None),
+            Self::UInt64(values) => values.push(None),
+            Self::Float32(values) => values.push(None),
+            Self::Float64(values) => values.push(None),
+            Self::Utf8(values) => values.push(None),
+            Self::Binary(values) => values.push(None),
+        }
+    }
+
+    fn set_last(&mut self, value: &ColumnValue) {
+        match (self, value) {
+            (Self::Boolean(values), ColumnValue::Bool(v)) => *values.last_mut().unwrap() = Some(*v),
+            (Self::Int8(values), ColumnValue::Byte(v)) => *values.last_mut().unwrap() = Some(*v),
+            (Self::UInt8(values), ColumnValue::UByte(v)) => *values.last_mut().unwrap() = Some(*v),
+            (Self::Int32(values), ColumnValue::Short(v)) => {
+                *values.last_mut().unwrap() = Some(*v as i32)
+            }
+            (Self::Int32(values), ColumnValue::Int(v)) => *values.last_mut().unwrap() = Some(*v),
+            (Self::UInt32(values), ColumnValue::UShort(v)) => {
+                *values.last_mut().unwrap() = Some(*v as u32)
+            }
+            (Self::UInt32(values), ColumnValue::UInt(v)) => *values.last_mut().unwrap() = Some(*v),
+            (Self::Int64(values), ColumnValue::Long(v)) => *values.last_mut().unwrap() = Some(*v),
+            (Self::UInt64(values), ColumnValue::ULong(v)) => *values.last_mut().unwrap() = Some(*v),
+            (Self::Float32(values), ColumnValue::Float(v)) => {
+                *values.last_mut().unwrap() = Some(*v)
+            }
+            (Self::Float64(values), ColumnValue::Double(v)) => {
+                *values.last_mut().unwrap() = Some(*v)
+            }
+            (Self::Utf8(values), ColumnValue::String(v))
+            | (Self::Utf8(values), ColumnValue::Json(v))
+            | (Self::Utf8(values), ColumnValue::DateTime(v)) => {
+                *values.last_mut().unwrap() = Some(v.to_string())
+            }
+            (Self::Binary(values), ColumnValue::Binary(v)) => {
+                *values.last_mut().unwrap() = Some(v.to_vec())
+            }
+            // A value that doesn't match the declared column type is left as null
+            _ => {}
+        }
+    }
+
+    fn into_series(self, name: &str) -> Result<Series> {
+        let series = match self {
+            Self::Boolean(values) => Series::new(name, values),
+            Self::Int8(values) => Series::new(name, values),
+            Self::UInt8(values) => Series::new(name, values),
+            Self::Int32(values) => Series::new(name, values),
+            Self::UInt32(values) => Series::new(name, values),
+            Self::Int64(values) => Series::new(name, values),
+            Self::UInt64(values) => Series::new(name, values),
+            Self::Float32(values) => Series::new(name, values),
+            Self::Float64(values) => Series::new(name, values),
+            Self::Utf8(values) => Series::new(name, values),
+            Self::Binary(values) => {
+                let array = BinaryArray::<i32>::from(&values);
+                Series::try_from((name, Box::new(array) as Box<dyn Array>))?
+            }
+        };
+        Ok(series)
+    }
+}
+
+/// Collects feature properties into one builder per header column
+struct PropertyColumns {
+    names: Vec<String>,
+    columns: Vec<PropertyColumn>,
+}
+
+impl PropertyColumns {
+    fn new(header_columns: Vec<(String, ColumnType)>) -> Self {
+        let (names, column_types): (Vec<_>, Vec<_>) = header_columns.into_iter().unzip();
+        let columns = column_types.into_iter().map(PropertyColumn::new).collect();
+        Self { names, columns }
+    }
+
+    fn from_header(header: Header) -> Self {
+        Self::new(
+            header
+                .columns()
+                .map(|columns| {
+                    columns
+                        .iter()
+                        .map(|column| (column.name().to_string(), column.type_()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        )
+    }
+
+    fn push_null_row(&mut self) {
+        self.columns
+            .iter_mut()
+            .for_each(|column| column.push_null());
+    }
+
+    fn finish(self) -> Result<Vec<Series>> {
+        self.columns
+            .into_iter()
+            .zip(self.names.iter())
+            .map(|(column, name)| column.into_series(name))
+            .collect()
+    }
+}
+
+impl PropertyProcessor for PropertyColumns {
+    fn property(
+        &mut self,
+        idx: usize,
+        _name: &str,
+        value: &ColumnValue,
+    ) -> geozero::error::Result<bool> {
+        if let Some(column) = self.columns.get_mut(idx) {
+            column.set_last(value);
+        }
+        // Returning false continues processing the remaining properties
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_flatgeobuf, write_flatgeobuf, FlatGeobufReadOptions};
+    use crate::util::geom_at_index;
+    use ::flatgeobuf::{FgbReader, GeometryType};
+    use geo::{Geometry, Point};
+    use geopolars_geo::geoseries::GeoSeries;
+    use geopolars_geo::util::from_geom_vec;
+    use geozero::{CoordDimensions, ToWkb};
+    use polars::export::arrow::array::{Array, BinaryArray};
+    use polars::prelude::{DataFrame, NamedFrom, Series};
+    use std::io::Cursor;
+
+    fn cities() -> DataFrame {
+        let geometry = from_geom_vec(&[
+            Geometry::Point(Point::new(-74.006, 40.7128)),
+            Geometry::Point(Point::new(-0.1278, 51.5074)),
+            Geometry::Point(Point::new(135.5244559, 34.687455)),
+        ])
+        .unwrap();
+        let name = Series::new("name", ["New York City", "London", "Osaka"]);
+        let population = Series::new("population", [Some(8_804_190_i64), Some(8_982_000), None]);
+        DataFrame::new(vec![geometry, name, population]).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let df = cities();
+
+        let mut buffer = Cursor::new(Vec::new());
+        write_flatgeobuf(&df, &mut buffer, Default::default()).unwrap();
+
+        buffer.set_position(0);
+        let result = read_flatgeobuf(&mut buffer, Default::default()).unwrap();
+
+        assert_eq!(result.shape(), (3, 3));
+        assert_eq!(
+            result.get_column_names(),
+            vec!["geometry", "name", "population"]
+        );
+        assert_eq!(result.column("population").unwrap().null_count(), 1);
+    }
+
+    #[test]
+    fn geometry_type() {
+        let mut buffer = Cursor::new(Vec::new());
+        write_flatgeobuf(&cities(), &mut buffer, Default::default()).unwrap();
+
+        buffer.set_position(0);
+        let fgb = FgbReader::open(&mut buffer).unwrap();
+        assert_eq!(fgb.header().geometry_type(), GeometryType::Point);
+    }
+
+    #[test]
+    fn write_null_geometry() {
+        let wkb = Geometry::Point(Point::new(0., 0.))
+            .to_wkb(CoordDimensions::xy())
+            .unwrap();
+        let array = BinaryArray::<i64>::from(vec![Some(wkb), None]);
+        let geometry = Series::try_from(("geometry", Box::new(array) as Box<dyn Array>)).unwrap();
+        let df = DataFrame::new(vec![geometry]).unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        let err = write_flatgeobuf(&df, &mut buffer, Default::default()).unwrap_err();
+        assert!(err.to_string().contains("row 1"));
+    }
+
+    #[test]
+    fn crs_round_trip() {
+        let mut df = cities();
+        let geometry = df.column("geometry").unwrap().set_crs("EPSG:4326").unwrap();
+        df.with_column(geometry).unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        write_flatgeobuf(&df, &mut buffer, Default::default()).unwrap();
+
+        buffer.set_position(0);
+        let result = read_flatgeobuf(&mut buffer, Default::default()).unwrap();
+        assert_eq!(
+            result.column("geometry").unwrap().crs().unwrap(),
+            Some("EPSG:4326".to_string())
+        );
+    }
+
+    #[test]
+    fn read_with_bbox() {
+        let df = cities();
+
+        let mut buffer = Cursor::new(Vec::new());
+        write_flatgeobuf(&df, &mut buffer, Default::default()).unwrap();
+
+        buffer.set_position(0);
+        let options = FlatGeobufReadOptions {
+            bbox: Some([-10., 40., 10., 60.]),
+        };
+        let result = read_flatgeobuf(&mut buffer, options).unwrap();
+
+        assert_eq!(result.height(), 1);
+        assert_eq!(
+            geom_at_index(result.column("geometry").unwrap(), 0).unwrap(),
+            Geometry::Point(Point::new(-0.1278, 51.5074))
+        );
+    }
+}
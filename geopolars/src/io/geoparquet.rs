@@ -0,0 +1,480 @@
+//! Readers and writers for [GeoParquet](https://geoparquet.org/releases/v1.0.0/) files
+//!
+//! Geometry columns are written as WKB and described in the `geo` metadata of the file, with their
+//! geometry types, bounding box and CRS.
+
+use crate::error::Result;
+use crate::io::ipc::{bboxes_intersect, bboxes_json, is_geometry_column, parse_bboxes};
+use crate::util::geom_at_index;
+use geopolars_geo::extension::{
+    extension_metadata, geoarrow_type, with_geoarrow_extension, GeoArrowType,
+};
+use geopolars_geo::geoseries::GeoSeries;
+use geozero::{CoordDimensions, ToWkb};
+use polars::export::arrow::array::{new_empty_array, Array, BinaryArray};
+use polars::export::arrow::chunk::Chunk;
+use polars::export::arrow::datatypes::{DataType as ArrowDataType, Field as ArrowField, Schema};
+use polars::export::arrow::io::parquet::read::{
+    infer_schema, read_metadata, FileMetaData, FileReader,
+};
+use polars::export::arrow::io::parquet::write::{
+    transverse, CompressionOptions, Encoding, FileWriter, KeyValue, RowGroupIterator, Version,
+    WriteOptions,
+};
+use polars::prelude::{DataFrame, PolarsError, Series};
+use serde_json::{json, Map, Value};
+use std::io::{Read, Seek, Write};
+
+/// Key of the file metadata entry holding the GeoParquet metadata
+const GEO_KEY: &str = "geo";
+
+/// Key of the file metadata entry holding the bounding box of the `geometry` column in each row
+/// group, in the same format as the per-batch bounding boxes of IPC files
+const ROW_GROUP_BBOX_KEY: &str = "geopolars:row_group_bbox";
+
+/// Version of the GeoParquet specification that written files follow
+const GEOPARQUET_VERSION: &str = "1.0.0";
+
+/// Options for reading a GeoParquet file
+#[derive(Default, Clone)]
+pub struct GeoParquetReadOptions {
+    /// Only read the row groups whose geometries may intersect `[minx, miny, maxx, maxy]`.
+    ///
+    /// Row groups are selected with the per-row-group bounding boxes written by
+    /// [`write_geoparquet`], so row groups outside of the box are never decoded. Rows of the
+    /// selected row groups are not filtered individually. Files without bounding box statistics
+    /// are read in full.
+    pub bbox: Option<[f64; 4]>,
+
+    /// Only read these columns, in the order of the file. Names that are not in the file are
+    /// ignored.
+    pub columns: Option<Vec<String>>,
+}
+
+/// Options for writing a GeoParquet file
+#[derive(Clone)]
+pub struct GeoParquetWriteOptions {
+    /// Maximum number of rows per row group. Smaller row groups let a bounding box filter skip
+    /// more of the file when reading, at the cost of some overhead per row group.
+    pub row_group_size: usize,
+}
+
+impl Default for GeoParquetWriteOptions {
+    fn default() -> Self {
+        Self {
+            row_group_size: 65_536,
+        }
+    }
+}
+
+/// The value of a key of the file metadata, if any
+fn key_value<'a>(metadata: &'a FileMetaData, key: &str) -> Option<&'a str> {
+    metadata
+        .key_value_metadata
+        .as_ref()?
+        .iter()
+        .find(|key_value| key_value.key == key)?
+        .value
+        .as_deref()
+}
+
+/// The GeoParquet metadata of each geometry column of a file, by column name
+fn geometry_columns(metadata: &FileMetaData) -> Result<Map<String, Value>> {
+    let geo = match key_value(metadata, GEO_KEY) {
+        Some(geo) => geo,
+        None => return Ok(Map::new()),
+    };
+    let columns = match serde_json::from_str::<Value>(geo)? {
+        Value::Object(mut geo) => match geo.remove("columns") {
+            Some(Value::Object(columns)) => columns,
+            _ => Map::new(),
+        },
+        _ => Map::new(),
+    };
+    Ok(columns)
+}
+
+/// Read a GeoParquet file into a DataFrame
+///
+/// Geometry columns listed in the GeoParquet metadata are tagged with the GeoArrow extension type
+/// of their encoding and with their CRS. A column without a `crs` entry has the default CRS of
+/// the specification, `OGC:CRS84`, and one with a `null` CRS has no CRS. Parquet files without
+/// GeoParquet metadata are read as usual.
+pub fn read_geoparquet<R: Read + Seek>(
+    reader: &mut R,
+    options: GeoParquetReadOptions,
+) -> Result<DataFrame> {
+    let metadata = read_metadata(reader).map_err(PolarsError::from)?;
+    let schema = infer_schema(&metadata).map_err(PolarsError::from)?;
+    let geometry_columns = geometry_columns(&metadata)?;
+
+    let mut row_groups = metadata.row_groups.clone();
+    let row_group_bboxes = key_value(&metadata, ROW_GROUP_BBOX_KEY).and_then(parse_bboxes);
+    if let (Some(bbox), Some(row_group_bboxes)) = (options.bbox, row_group_bboxes) {
+        if row_group_bboxes.len() == row_groups.len() {
+            row_groups = row_groups
+                .into_iter()
+                .zip(row_group_bboxes)
+                .filter(|(_, row_group_bbox)| {
+                    row_group_bbox.map_or(false, |row_group_bbox| {
+                        bboxes_intersect(&bbox, &row_group_bbox)
+                    })
+                })
+                .map(|(row_group, _)| row_group)
+                .collect();
+        }
+    }
+
+    let fields: Vec<ArrowField> = match &options.columns {
+        Some(columns) => schema
+            .fields
+            .into_iter()
+            .filter(|field| columns.contains(&field.name))
+            .collect(),
+        None => schema.fields,
+    };
+
+    let mut columns: Vec<Vec<Box<dyn Array>>> = vec![vec![]; fields.len()];
+    let file_reader = FileReader::new(
+        reader,
+        row_groups,
+        Schema::from(fields.clone()),
+        None,
+        None,
+        None,
+    );
+    for chunk in file_reader {
+        let chunk = chunk.map_err(PolarsError::from)?;
+        for (column, array) in columns.iter_mut().zip(chunk.into_arrays()) {
+            column.push(array);
+        }
+    }
+
+    let series = fields
+        .iter()
+        .zip(columns)
+        .map(|(field, chunks)| field_series(field, chunks, geometry_columns.get(&field.name)))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(DataFrame::new(series)?)
+}
+
+/// Read the schema of a GeoParquet file, with the column types [`read_geoparquet`] reads them as,
+/// without reading any row group
+pub(crate) fn read_geoparquet_schema<R: Read + Seek>(
+    reader: &mut R,
+) -> Result<polars::prelude::Schema> {
+    let metadata = read_metadata(reader).map_err(PolarsError::from)?;
+    let schema = infer_schema(&metadata).map_err(PolarsError::from)?;
+    let geometry_columns = geometry_columns(&metadata)?;
+    let series = schema
+        .fields
+        .iter()
+        .map(|field| field_series(field, vec![], geometry_columns.get(&field.name)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(DataFrame::new(series)?.schema())
+}
+
+/// Create the Series of a column of a GeoParquet file from its chunks, tagging a geometry column
+/// with the GeoArrow type of its encoding and with its CRS
+fn field_series(
+    field: &ArrowField,
+    mut chunks: Vec<Box<dyn Array>>,
+    column_metadata: Option<&Value>,
+) -> Result<Series> {
+    if chunks.is_empty() {
+        chunks.push(new_empty_array(field.data_type().clone()));
+    }
+    let series = Series::try_from((field.name.as_str(), chunks))?;
+
+    let column_metadata = match column_metadata {
+        Some(column_metadata) => column_metadata,
+        None => return Ok(series),
+    };
+    let geoarrow_type = column_metadata
+        .get("encoding")
+        .and_then(Value::as_str)
+        .and_then(|encoding| {
+            GeoArrowType::from_extension_name(&format!("geoarrow.{}", encoding.to_lowercase()))
+        });
+    let geoarrow_type = match geoarrow_type {
+        Some(geoarrow_type) => geoarrow_type,
+        None => return Ok(series),
+    };
+
+    let crs = match column_metadata.get("crs") {
+        Some(crs) => crs.clone(),
+        None => Value::from("OGC:CRS84"),
+    };
+    let metadata = match crs {
+        Value::Null => None,
+        crs => Some(json!({ "crs": crs }).to_string()),
+    };
+    with_geoarrow_extension(&series, geoarrow_type, metadata)
+}
+
+/// Write a DataFrame to a GeoParquet file
+///
+/// The `geometry` column, and any other column carrying GeoArrow extension information, is
+/// written as WKB and described in the GeoParquet metadata of the file. The `geometry` column is
+/// the primary geometry column. A CRS is written as it is stored in the extension metadata of the
+/// column: GeoParquet readers expect PROJJSON, and may not understand a CRS stored as a string
+/// such as `"EPSG:4326"`.
+///
+/// The DataFrame is written in row groups of `options.row_group_size` rows. The bounding box of
+/// the `geometry` column in each row group is stored in the file metadata, so that
+/// [`read_geoparquet`] can skip row groups outside of a bounding box filter.
+pub fn write_geoparquet<W: Write>(
+    df: &DataFrame,
+    writer: &mut W,
+    options: GeoParquetWriteOptions,
+) -> Result<()> {
+    let mut df = df.clone();
+    df.rechunk();
+
+    let row_group_size = options.row_group_size.max(1);
+    let row_groups: Vec<DataFrame> = (0..df.height())
+        .step_by(row_group_size)
+        .map(|offset| df.slice(offset as i64, row_group_size))
+        .collect();
+
+    let geometry_columns: Vec<&Series> = df
+        .get_columns()
+        .iter()
+        .filter(|series| is_geometry_column(series))
+        .collect();
+
+    let mut key_value_metadata = vec![];
+    if let Some(geo) = geo_metadata(&geometry_columns)? {
+        key_value_metadata.push(KeyValue {
+            key: GEO_KEY.to_string(),
+            value: Some(geo),
+        });
+    }
+    if df.get_column_names().contains(&"geometry") {
+        key_value_metadata.push(KeyValue {
+            key: ROW_GROUP_BBOX_KEY.to_string(),
+            value: Some(bboxes_json(&row_groups)?),
+        });
+    }
+
+    let fields = df
+        .get_columns()
+        .iter()
+        .map(|series| {
+            let data_type = if is_geometry_column(series) {
+                ArrowDataType::LargeBinary
+            } else {
+                series.field().to_arrow().data_type
+            };
+            ArrowField::new(series.name(), data_type, true)
+        })
+        .collect::<Vec<_>>();
+    let schema = Schema::from(fields);
+
+    let chunks = row_groups
+        .iter()
+        .map(|row_group| {
+            let arrays = row_group
+                .get_columns()
+                .iter()
+                .map(|series| {
+                    if is_geometry_column(series) {
+                        wkb_array(series)
+                    } else {
+                        Ok(series.to_arrow(0))
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Chunk::new(arrays))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let write_options = WriteOptions {
+        write_statistics: true,
+        compression: CompressionOptions::Uncompressed,
+        version: Version::V2,
+        data_pagesize_limit: None,
+    };
+    let encodings = schema
+        .fields
+        .iter()
+        .map(|field| transverse(field.data_type(), |_| Encoding::Plain))
+        .collect();
+    let row_group_iter = RowGroupIterator::try_new(
+        chunks.into_iter().map(Ok),
+        &schema,
+        write_options,
+        encodings,
+    )
+    .map_err(PolarsError::from)?;
+
+    let mut file_writer =
+        FileWriter::try_new(writer, schema, write_options).map_err(PolarsError::from)?;
+    for row_group in row_group_iter {
+        file_writer
+            .write(row_group.map_err(PolarsError::from)?)
+            .map_err(PolarsError::from)?;
+    }
+    file_writer
+        .end(Some(key_value_metadata))
+        .map_err(PolarsError::from)?;
+    Ok(())
+}
+
+/// The GeoParquet metadata of the geometry columns of a DataFrame, or `None` without geometry
+/// columns
+fn geo_metadata(geometry_columns: &[&Series]) -> Result<Option<String>> {
+    let primary_column = match geometry_columns
+        .iter()
+        .find(|series| series.name() == "geometry")
+        .or_else(|| geometry_columns.first())
+    {
+        Some(series) => series.name(),
+        None => return Ok(None),
+    };
+
+    let mut columns = Map::new();
+    for series in geometry_columns {
+        let geometry_types = match geoarrow_type(series)? {
+            GeoArrowType::Point => vec!["Point"],
+            GeoArrowType::LineString => vec!["LineString"],
+            GeoArrowType::Polygon => vec!["Polygon"],
+            GeoArrowType::MultiPoint => vec!["MultiPoint"],
+            GeoArrowType::MultiLineString => vec!["MultiLineString"],
+            GeoArrowType::MultiPolygon => vec!["MultiPolygon"],
+            // The geometry types of a WKB column are unknown
+            GeoArrowType::WKB => vec![],
+        };
+        let crs = match extension_metadata(series) {
+            Some(metadata) => serde_json::from_str::<Value>(&metadata)?
+                .get("crs")
+                .cloned()
+                .unwrap_or(Value::Null),
+            None => Value::Null,
+        };
+
+        let mut column = json!({
+            "encoding": "WKB",
+            "geometry_types": geometry_types,
+            "crs": crs,
+        });
+        if let Some(bbox) = series.total_bounds()? {
+            column["bbox"] = Value::from(bbox.to_vec());
+        }
+        columns.insert(series.name().to_string(), column);
+    }
+
+    let geo = json!({
+        "version": GEOPARQUET_VERSION,
+        "primary_column": primary_column,
+        "columns": columns,
+    });
+    Ok(Some(geo.to_string()))
+}
+
+/// Encode the geometries of a geometry column as WKB
+fn wkb_array(series: &Series) -> Result<Box<dyn Array>> {
+    let is_null = series.is_null();
+    let values = (0..series.len())
+        .map(|row_idx| {
+            if is_null.get(row_idx) == Some(true) {
+                return Ok(None);
+            }
+            let geom = geom_at_index(series, row_idx)?;
+            Ok(Some(geom.to_wkb(CoordDimensions::xy())?))
+        })
+        .collect::<Result<Vec<Option<Vec<u8>>>>>()?;
+    Ok(Box::new(BinaryArray::<i64>::from(&values)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_geoparquet, write_geoparquet, GeoParquetReadOptions, GeoParquetWriteOptions};
+    use crate::util::{geom_at_index, get_geoarrow_type, GeoArrowType};
+    use geo::{point, polygon, Geometry, MultiPoint};
+    use geoarrow::{GeometryArray, GeometryArrayTrait, MultiPointArray};
+    use geopolars_geo::extension::with_geoarrow_extension;
+    use geopolars_geo::geoseries::GeoSeries;
+    use geopolars_geo::util::from_geom_vec;
+    use polars::prelude::{DataFrame, NamedFrom, Series};
+    use std::io::Cursor;
+
+    #[test]
+    fn round_trip_multipoint() {
+        let input_geom: MultiPoint = vec![point!(x: 0., y: 0.), point!(x: 1., y: 1.)].into();
+        let input_array: MultiPointArray = vec![input_geom.clone()].into();
+        let arrow_array = GeometryArray::MultiPoint(input_array).into_arrow();
+        let geometry = Series::try_from(("geometry", arrow_array)).unwrap();
+
+        let metadata = r#"{"crs":"EPSG:4326"}"#.to_string();
+        let geometry =
+            with_geoarrow_extension(&geometry, GeoArrowType::MultiPoint, Some(metadata)).unwrap();
+        let df = DataFrame::new(vec![geometry, Series::new("name", ["a"])]).unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        write_geoparquet(&df, &mut buffer, Default::default()).unwrap();
+        buffer.set_position(0);
+        let result = read_geoparquet(&mut buffer, Default::default()).unwrap();
+
+        // Geometries are stored as WKB
+        let geometry = result.column("geometry").unwrap();
+        assert_eq!(get_geoarrow_type(geometry).unwrap(), GeoArrowType::WKB);
+        assert_eq!(geometry.crs().unwrap(), Some("EPSG:4326".to_string()));
+        assert_eq!(
+            geom_at_index(geometry, 0).unwrap(),
+            Geometry::MultiPoint(input_geom)
+        );
+        assert_eq!(result.column("name").unwrap(), &Series::new("name", ["a"]));
+    }
+
+    #[test]
+    fn read_with_bbox() {
+        let geometry = from_geom_vec(&[
+            Geometry::Polygon(polygon![
+                (x: 0., y: 0.),
+                (x: 1., y: 0.),
+                (x: 1., y: 1.),
+            ]),
+            Geometry::Point(point!(x: 10., y: 10.)),
+            Geometry::Point(point!(x: 20., y: 20.)),
+        ])
+        .unwrap();
+        let df = DataFrame::new(vec![geometry, Series::new("name", ["a", "b", "c"])]).unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        write_geoparquet(
+            &df,
+            &mut buffer,
+            GeoParquetWriteOptions { row_group_size: 1 },
+        )
+        .unwrap();
+
+        buffer.set_position(0);
+        let options = GeoParquetReadOptions {
+            bbox: Some([5., 5., 15., 15.]),
+            ..Default::default()
+        };
+        let result = read_geoparquet(&mut buffer, options).unwrap();
+        assert_eq!(result.column("name").unwrap(), &Series::new("name", ["b"]));
+
+        buffer.set_position(0);
+        let options = GeoParquetReadOptions {
+            bbox: Some([100., 100., 101., 101.]),
+            ..Default::default()
+        };
+        let result = read_geoparquet(&mut buffer, options).unwrap();
+        assert_eq!(result.shape(), (0, 2));
+
+        // Without a CRS in the extension metadata, the CRS is written as unknown
+        buffer.set_position(0);
+        let options = GeoParquetReadOptions {
+            columns: Some(vec!["geometry".to_string()]),
+            ..Default::default()
+        };
+        let result = read_geoparquet(&mut buffer, options).unwrap();
+        assert_eq!(result.get_column_names(), ["geometry"]);
+        assert_eq!(result.height(), 3);
+        assert_eq!(result.column("geometry").unwrap().crs().unwrap(), None);
+    }
+}
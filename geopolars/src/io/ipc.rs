@@ -0,0 +1,372 @@
+use crate::error::Result;
+use geopolars_geo::extension::{
+    chunk_extension, extension_array, extension_metadata, geoarrow_type, storage_array,
+    with_geoarrow_extension, GeoArrowType,
+};
+use geopolars_geo::geoseries::GeoSeries;
+use polars::export::arrow::array::{new_empty_array, Array};
+use polars::export::arrow::chunk::Chunk;
+use polars::export::arrow::datatypes::{
+    DataType as ArrowDataType, Field as ArrowField, Metadata, Schema,
+};
+use polars::export::arrow::io::ipc::read::{
+    read_batch, read_file_dictionaries, read_file_metadata,
+};
+use polars::export::arrow::io::ipc::write::{FileWriter, WriteOptions};
+use polars::prelude::{DataFrame, PolarsError, Series};
+use serde_json::Value;
+use std::io::{Read, Seek, Write};
+
+/// Key of the schema metadata entry holding the bounding box of the geometry column in each
+/// record batch, as a JSON array with one `[minx, miny, maxx, maxy]` array, or `null` for a batch
+/// without geometries, per batch
+const BATCH_BBOX_KEY: &str = "geopolars:batch_bbox";
+
+/// Options for reading an Arrow IPC file
+#[derive(Default, Clone)]
+pub struct IpcReadOptions {
+    /// Only read the record batches whose geometries may intersect `[minx, miny, maxx, maxy]`.
+    ///
+    /// Batches are selected with the per-batch bounding boxes written by [`write_ipc`], so
+    /// batches outside of the box are never decoded. Rows of the selected batches are not
+    /// filtered individually. Files without bounding box statistics are read in full.
+    pub bbox: Option<[f64; 4]>,
+
+    /// Only read these columns, in the order of the file. Names that are not in the file are
+    /// ignored.
+    pub columns: Option<Vec<String>>,
+}
+
+/// Options for writing an Arrow IPC file
+#[derive(Clone)]
+pub struct IpcWriteOptions {
+    /// Maximum number of rows per record batch. Smaller batches let a bounding box filter skip
+    /// more of the file when reading, at the cost of some overhead per batch.
+    pub batch_size: usize,
+}
+
+impl Default for IpcWriteOptions {
+    fn default() -> Self {
+        Self { batch_size: 65_536 }
+    }
+}
+
+/// Whether a column should be written as a GeoArrow geometry column
+pub(crate) fn is_geometry_column(series: &Series) -> bool {
+    series.name() == "geometry"
+        || series
+            .chunks()
+            .first()
+            .map_or(false, |chunk| chunk_extension(&**chunk).is_some())
+}
+
+/// The per-batch bounding boxes stored in the schema metadata, if any
+fn batch_bboxes(metadata: &Metadata) -> Option<Vec<Option<[f64; 4]>>> {
+    parse_bboxes(metadata.get(BATCH_BBOX_KEY)?)
+}
+
+/// Parse per-chunk bounding boxes serialized as a JSON array with one `[minx, miny, maxx, maxy]`
+/// array, or `null` for a chunk without geometries, per chunk
+pub(crate) fn parse_bboxes(json: &str) -> Option<Vec<Option<[f64; 4]>>> {
+    let bboxes = match serde_json::from_str::<Value>(json).ok()? {
+        Value::Array(bboxes) => bboxes,
+        _ => return None,
+    };
+    bboxes
+        .iter()
+        .map(|bbox| match bbox {
+            Value::Null => Some(None),
+            Value::Array(values) if values.len() == 4 => {
+                let mut bbox = [0.; 4];
+                for (value, coord) in values.iter().zip(bbox.iter_mut()) {
+                    *coord = value.as_f64()?;
+                }
+                Some(Some(bbox))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Serialize the bounding boxes of the `geometry` column of each chunk, the inverse of
+/// [`parse_bboxes`]
+pub(crate) fn bboxes_json(chunks: &[DataFrame]) -> Result<String> {
+    let bboxes: Vec<Value> = chunks
+        .iter()
+        .map(|chunk| {
+            let bbox = chunk.column("geometry")?.total_bounds()?;
+            Ok(bbox.map_or(Value::Null, |bbox| Value::from(bbox.to_vec())))
+        })
+        .collect::<Result<_>>()?;
+    Ok(Value::Array(bboxes).to_string())
+}
+
+pub(crate) fn bboxes_intersect(a: &[f64; 4], b: &[f64; 4]) -> bool {
+    a[0] <= b[2] && a[2] >= b[0] && a[1] <= b[3] && a[3] >= b[1]
+}
+
+/// Read an Arrow IPC file into a DataFrame
+///
+/// Columns tagged with a `geoarrow.*` extension type keep their logical geometry type and
+/// extension metadata (including the CRS), so that e.g. a MultiPoint column is not mistaken for a
+/// LineString column. Untagged files are read as usual, and their geometry types are inferred
+/// from the physical layout of the geometry column.
+pub fn read_ipc<R: Read + Seek>(reader: &mut R, options: IpcReadOptions) -> Result<DataFrame> {
+    let metadata = read_file_metadata(reader).map_err(PolarsError::from)?;
+    let schema = metadata.schema.clone();
+
+    let mut batches: Vec<usize> = (0..metadata.blocks.len()).collect();
+    if let (Some(bbox), Some(batch_bboxes)) = (options.bbox, batch_bboxes(&schema.metadata)) {
+        if batch_bboxes.len() == batches.len() {
+            batches.retain(|&index| {
+                batch_bboxes[index].map_or(false, |batch_bbox| bboxes_intersect(&bbox, &batch_bbox))
+            });
+        }
+    }
+
+    let projection: Option<Vec<usize>> = options.columns.map(|columns| {
+        (0..schema.fields.len())
+            .filter(|&index| columns.contains(&schema.fields[index].name))
+            .collect()
+    });
+    let fields: Vec<&ArrowField> = match &projection {
+        Some(projection) => projection
+            .iter()
+            .map(|&index| &schema.fields[index])
+            .collect(),
+        None => schema.fields.iter().collect(),
+    };
+
+    let dictionaries = read_file_dictionaries(reader, &metadata, &mut Default::default())
+        .map_err(PolarsError::from)?;
+    let mut message_scratch = Default::default();
+    let mut data_scratch = Default::default();
+
+    let mut columns: Vec<Vec<Box<dyn Array>>> = vec![vec![]; fields.len()];
+    for index in batches {
+        let chunk = read_batch(
+            reader,
+            &dictionaries,
+            &metadata,
+            projection.as_deref(),
+            None,
+            index,
+            &mut message_scratch,
+            &mut data_scratch,
+        )
+        .map_err(PolarsError::from)?;
+        for (column, array) in columns.iter_mut().zip(chunk.into_arrays()) {
+            column.push(array);
+        }
+    }
+
+    let series = fields
+        .into_iter()
+        .zip(columns)
+        .map(|(field, chunks)| field_series(field, chunks))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(DataFrame::new(series)?)
+}
+
+/// Read the schema of an Arrow IPC file, with the column types [`read_ipc`] reads them as,
+/// without reading any record batch
+pub(crate) fn read_ipc_schema<R: Read + Seek>(reader: &mut R) -> Result<polars::prelude::Schema> {
+    let metadata = read_file_metadata(reader).map_err(PolarsError::from)?;
+    let series = metadata
+        .schema
+        .fields
+        .iter()
+        .map(|field| field_series(field, vec![]))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(DataFrame::new(series)?.schema())
+}
+
+/// Create the Series of a field of an IPC file from its chunks, restoring GeoArrow extensions
+fn field_series(field: &ArrowField, mut chunks: Vec<Box<dyn Array>>) -> Result<Series> {
+    if chunks.is_empty() {
+        chunks.push(new_empty_array(field.data_type().clone()));
+    }
+    match field.data_type() {
+        ArrowDataType::Extension(name, _, metadata) => {
+            let storage_chunks = chunks
+                .iter()
+                .map(|chunk| storage_array(&**chunk))
+                .collect::<Result<Vec<_>>>()?;
+            let series = Series::try_from((field.name.as_str(), storage_chunks))?;
+            match GeoArrowType::from_extension_name(name) {
+                Some(geoarrow_type) => {
+                    with_geoarrow_extension(&series, geoarrow_type, metadata.clone())
+                }
+                None => Ok(series),
+            }
+        }
+        _ => Ok(Series::try_from((field.name.as_str(), chunks))?),
+    }
+}
+
+/// Write a DataFrame to an Arrow IPC file
+///
+/// The `geometry` column, and any other column carrying GeoArrow extension information, is
+/// written with its `geoarrow.*` extension type and extension metadata so that other GeoArrow
+/// readers recognize its logical geometry type and CRS.
+///
+/// The DataFrame is written in record batches of `options.batch_size` rows. The bounding box of
+/// the `geometry` column in each batch is stored in the schema metadata, so that [`read_ipc`] can
+/// skip batches outside of a bounding box filter.
+pub fn write_ipc<W: Write>(df: &DataFrame, writer: &mut W, options: IpcWriteOptions) -> Result<()> {
+    let mut df = df.clone();
+    df.rechunk();
+
+    let batch_size = options.batch_size.max(1);
+    let batches: Vec<DataFrame> = (0..df.height())
+        .step_by(batch_size)
+        .map(|offset| df.slice(offset as i64, batch_size))
+        .collect();
+
+    let fields = df
+        .get_columns()
+        .iter()
+        .map(|series| {
+            let data_type = if is_geometry_column(series) {
+                extension_array(
+                    &*series.to_arrow(0),
+                    geoarrow_type(series)?,
+                    extension_metadata(series),
+                )?
+                .data_type()
+                .clone()
+            } else {
+                series.field().to_arrow().data_type
+            };
+            Ok(ArrowField::new(series.name(), data_type, true))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut metadata = Metadata::new();
+    if df.get_column_names().contains(&"geometry") {
+        metadata.insert(BATCH_BBOX_KEY.to_string(), bboxes_json(&batches)?);
+    }
+
+    let schema = Schema::from(fields).with_metadata(metadata);
+    let mut file_writer = FileWriter::try_new(writer, &schema, None, WriteOptions::default())
+        .map_err(PolarsError::from)?;
+    for batch in &batches {
+        let arrays = batch
+            .get_columns()
+            .iter()
+            .map(|series| {
+                if is_geometry_column(series) {
+                    extension_array(
+                        &*series.to_arrow(0),
+                        geoarrow_type(series)?,
+                        extension_metadata(series),
+                    )
+                } else {
+                    Ok(series.to_arrow(0))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        file_writer
+            .write(&Chunk::new(arrays), None)
+            .map_err(PolarsError::from)?;
+    }
+    file_writer.finish().map_err(PolarsError::from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_ipc, write_ipc, IpcReadOptions, IpcWriteOptions};
+    use crate::util::{geom_at_index, get_geoarrow_type, GeoArrowType};
+    use geo::{point, polygon, Geometry, MultiPoint};
+    use geoarrow::{GeometryArray, GeometryArrayTrait, MultiPointArray};
+    use geopolars_geo::extension::{extension_metadata, with_geoarrow_extension};
+    use geopolars_geo::util::from_geom_vec;
+    use polars::prelude::{DataFrame, NamedFrom, Series};
+    use std::fs::File;
+    use std::io::Cursor;
+
+    #[test]
+    fn round_trip_multipoint() {
+        let input_geom: MultiPoint = vec![point!(x: 0., y: 0.), point!(x: 1., y: 1.)].into();
+        let input_array: MultiPointArray = vec![input_geom.clone()].into();
+        let arrow_array = GeometryArray::MultiPoint(input_array).into_arrow();
+        let geometry = Series::try_from(("geometry", arrow_array)).unwrap();
+
+        let metadata = r#"{"crs":"EPSG:4326"}"#.to_string();
+        let geometry =
+            with_geoarrow_extension(&geometry, GeoArrowType::MultiPoint, Some(metadata.clone()))
+                .unwrap();
+        let df = DataFrame::new(vec![geometry, Series::new("name", ["a"])]).unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        write_ipc(&df, &mut buffer, Default::default()).unwrap();
+        buffer.set_position(0);
+        let result = read_ipc(&mut buffer, Default::default()).unwrap();
+
+        let geometry = result.column("geometry").unwrap();
+        assert_eq!(
+            get_geoarrow_type(geometry).unwrap(),
+            GeoArrowType::MultiPoint
+        );
+        assert_eq!(extension_metadata(geometry), Some(metadata));
+        assert_eq!(
+            geom_at_index(geometry, 0).unwrap(),
+            Geometry::MultiPoint(input_geom)
+        );
+        assert_eq!(result.column("name").unwrap(), &Series::new("name", ["a"]));
+    }
+
+    #[test]
+    fn read_untagged() {
+        let mut file = File::open("../data/cities.arrow").unwrap();
+        let df = read_ipc(&mut file, Default::default()).unwrap();
+        let geometry = df.column("geometry").unwrap();
+        assert_eq!(get_geoarrow_type(geometry).unwrap(), GeoArrowType::WKB);
+        assert_eq!(extension_metadata(geometry), None);
+    }
+
+    #[test]
+    fn read_with_bbox() {
+        let geometry = from_geom_vec(&[
+            Geometry::Polygon(polygon![
+                (x: 0., y: 0.),
+                (x: 1., y: 0.),
+                (x: 1., y: 1.),
+            ]),
+            Geometry::Point(point!(x: 10., y: 10.)),
+            Geometry::Point(point!(x: 20., y: 20.)),
+        ])
+        .unwrap();
+        let df = DataFrame::new(vec![geometry, Series::new("name", ["a", "b", "c"])]).unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        write_ipc(&df, &mut buffer, IpcWriteOptions { batch_size: 1 }).unwrap();
+
+        buffer.set_position(0);
+        let options = IpcReadOptions {
+            bbox: Some([5., 5., 15., 15.]),
+            ..Default::default()
+        };
+        let result = read_ipc(&mut buffer, options).unwrap();
+        assert_eq!(result.column("name").unwrap(), &Series::new("name", ["b"]));
+
+        buffer.set_position(0);
+        let options = IpcReadOptions {
+            bbox: Some([100., 100., 101., 101.]),
+            ..Default::default()
+        };
+        let result = read_ipc(&mut buffer, options).unwrap();
+        assert_eq!(result.shape(), (0, 2));
+
+        buffer.set_position(0);
+        let options = IpcReadOptions {
+            columns: Some(vec!["name".to_string()]),
+            ..Default::default()
+        };
+        let result = read_ipc(&mut buffer, options).unwrap();
+        assert_eq!(result.get_column_names(), ["name"]);
+        assert_eq!(result.height(), 3);
+    }
+}
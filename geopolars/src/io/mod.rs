@@ -0,0 +1,13 @@
+//! Readers and writers for geospatial file formats
+
+#[cfg(feature = "shapefile")]
+mod dbf;
+#[cfg(feature = "flatgeobuf")]
+pub mod flatgeobuf;
+#[cfg(feature = "parquet")]
+pub mod geoparquet;
+pub mod ipc;
+#[cfg(feature = "lazy")]
+pub mod scan;
+#[cfg(feature = "shapefile")]
+pub mod shapefile;
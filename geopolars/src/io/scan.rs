@@ -0,0 +1,292 @@
+//! Lazy scans of geometry files with a spatial filter pushed down to the reader
+//!
+//! ```ignore
+//! use geopolars::io::scan::GeoScan;
+//!
+//! let lf = GeoScan::ipc("buildings.arrow")
+//!     .filter_bbox([5.9, 45.8, 10.5, 47.8])
+//!     .finish()?;
+//! ```
+//!
+//! The file is read when the `LazyFrame` is collected, not when the scan is created. The
+//! bounding box of the spatial filters is then passed to the reader, which skips the record
+//! batches (IPC), row groups (GeoParquet) or features (FlatGeobuf) outside of it without decoding
+//! them, and only the columns the query uses are read (IPC and GeoParquet) or kept (FlatGeobuf).
+//! The exact filter is added to the returned `LazyFrame`, so that it composes with further lazy
+//! operations.
+
+use crate::error::Result;
+use crate::expr::{geom_lit, to_polars_error, GeoExpr};
+#[cfg(feature = "flatgeobuf")]
+use crate::io::flatgeobuf::{read_flatgeobuf, read_flatgeobuf_schema, FlatGeobufReadOptions};
+#[cfg(feature = "parquet")]
+use crate::io::geoparquet::{read_geoparquet, read_geoparquet_schema, GeoParquetReadOptions};
+use crate::io::ipc::{read_ipc, read_ipc_schema, IpcReadOptions};
+use geo::algorithm::bounding_rect::BoundingRect;
+use geo::{coord, Geometry, Rect};
+use polars::prelude::{
+    col, AnonymousScan, AnonymousScanOptions, DataFrame, LazyFrame, PolarsResult,
+    ScanArgsAnonymous, Schema,
+};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+enum ScanSource {
+    Ipc(PathBuf),
+    #[cfg(feature = "parquet")]
+    GeoParquet(PathBuf),
+    #[cfg(feature = "flatgeobuf")]
+    FlatGeobuf(PathBuf),
+}
+
+/// A lazy scan of a geometry file, see the [module documentation](self)
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct GeoScan {
+    source: ScanSource,
+    filters: Vec<Geometry<f64>>,
+}
+
+impl GeoScan {
+    /// Scan an Arrow IPC file. Record batches are skipped using the bounding box statistics
+    /// written by [`crate::io::ipc::write_ipc`].
+    pub fn ipc(path: impl AsRef<Path>) -> Self {
+        Self {
+            source: ScanSource::Ipc(path.as_ref().to_path_buf()),
+            filters: vec![],
+        }
+    }
+
+    /// Scan a GeoParquet file. Row groups are skipped using the bounding box statistics written
+    /// by [`crate::io::geoparquet::write_geoparquet`].
+    #[cfg(feature = "parquet")]
+    pub fn geoparquet(path: impl AsRef<Path>) -> Self {
+        Self {
+            source: ScanSource::GeoParquet(path.as_ref().to_path_buf()),
+            filters: vec![],
+        }
+    }
+
+    /// Scan a FlatGeobuf file. Features are skipped using the file's packed R-tree, so the file
+    /// must have been written with a spatial index.
+    #[cfg(feature = "flatgeobuf")]
+    pub fn flatgeobuf(path: impl AsRef<Path>) -> Self {
+        Self {
+            source: ScanSource::FlatGeobuf(path.as_ref().to_path_buf()),
+            filters: vec![],
+        }
+    }
+
+    /// Keep only the rows whose geometry intersects `[minx, miny, maxx, maxy]`
+    pub fn filter_bbox(self, bbox: [f64; 4]) -> Self {
+        let [minx, miny, maxx, maxy] = bbox;
+        let rect = Rect::new(coord! { x: minx, y: miny }, coord! { x: maxx, y: maxy });
+        self.filter_intersects(Geometry::Polygon(rect.to_polygon()))
+    }
+
+    /// Keep only the rows whose geometry intersects `geom`
+    pub fn filter_intersects(mut self, geom: Geometry<f64>) -> Self {
+        self.filters.push(geom);
+        self
+    }
+
+    /// The bounding box that every row passing all filters lies in, if there are filters
+    fn bbox(&self) -> Option<[f64; 4]> {
+        self.filters
+            .iter()
+            .map(|geom| {
+                geom.bounding_rect()
+                    .map(|rect| [rect.min().x, rect.min().y, rect.max().x, rect.max().y])
+                    // An empty geometry intersects nothing
+                    .unwrap_or([
+                        f64::INFINITY,
+                        f64::INFINITY,
+                        f64::NEG_INFINITY,
+                        f64::NEG_INFINITY,
+                    ])
+            })
+            .reduce(|a, b| {
+                [
+                    a[0].max(b[0]),
+                    a[1].max(b[1]),
+                    a[2].min(b[2]),
+                    a[3].min(b[3]),
+                ]
+            })
+    }
+
+    /// Return a `LazyFrame` of the file with the exact spatial filters applied. Only the schema
+    /// is read here; the parts of the file that may pass the filters are read on collect.
+    pub fn finish(self) -> Result<LazyFrame> {
+        let bbox = self.bbox();
+        let scan = BboxScan {
+            source: self.source,
+            bbox,
+        };
+        let args = ScanArgsAnonymous {
+            schema: Some(scan.read_schema()?),
+            ..Default::default()
+        };
+
+        let mut lf = LazyFrame::anonymous_scan(Arc::new(scan), args)?;
+        for geom in self.filters {
+            lf = lf.filter(col("geometry").geo().intersects(geom_lit(geom)?));
+        }
+        Ok(lf)
+    }
+}
+
+/// The scan of a file run by polars when a [`GeoScan`] is collected
+struct BboxScan {
+    source: ScanSource,
+    bbox: Option<[f64; 4]>,
+}
+
+impl BboxScan {
+    fn read_schema(&self) -> Result<Schema> {
+        match &self.source {
+            ScanSource::Ipc(path) => read_ipc_schema(&mut BufReader::new(File::open(path)?)),
+            #[cfg(feature = "parquet")]
+            ScanSource::GeoParquet(path) => {
+                read_geoparquet_schema(&mut BufReader::new(File::open(path)?))
+            }
+            #[cfg(feature = "flatgeobuf")]
+            ScanSource::FlatGeobuf(path) => {
+                read_flatgeobuf_schema(&mut BufReader::new(File::open(path)?))
+            }
+        }
+    }
+
+    fn read(&self, columns: Option<&Vec<String>>) -> Result<DataFrame> {
+        let bbox = self.bbox;
+        let df = match &self.source {
+            ScanSource::Ipc(path) => {
+                let options = IpcReadOptions {
+                    bbox,
+                    columns: columns.cloned(),
+                };
+                read_ipc(&mut BufReader::new(File::open(path)?), options)?
+            }
+            #[cfg(feature = "parquet")]
+            ScanSource::GeoParquet(path) => {
+                let options = GeoParquetReadOptions {
+                    bbox,
+                    columns: columns.cloned(),
+                };
+                read_geoparquet(&mut BufReader::new(File::open(path)?), options)?
+            }
+            #[cfg(feature = "flatgeobuf")]
+            ScanSource::FlatGeobuf(path) => {
+                let options = FlatGeobufReadOptions { bbox };
+                read_flatgeobuf(&mut BufReader::new(File::open(path)?), options)?
+            }
+        };
+        match columns {
+            Some(columns) => Ok(df.select(columns)?),
+            None => Ok(df),
+        }
+    }
+}
+
+impl AnonymousScan for BboxScan {
+    fn scan(&self, scan_opts: AnonymousScanOptions) -> PolarsResult<DataFrame> {
+        self.read(scan_opts.with_columns.as_deref())
+            .map_err(to_polars_error)
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GeoScan;
+    use crate::io::ipc::{write_ipc, IpcWriteOptions};
+    use geo::{point, Geometry};
+    use geopolars_geo::util::from_geom_vec;
+    use polars::prelude::{col, DataFrame, NamedFrom, Series};
+    use std::fs::File;
+
+    #[test]
+    fn scan_ipc_with_bbox() {
+        let geometry = from_geom_vec(&[
+            Geometry::Point(point!(x: 0., y: 0.)),
+            Geometry::Point(point!(x: 1., y: 1.)),
+            Geometry::Point(point!(x: 10., y: 10.)),
+        ])
+        .unwrap();
+        let df = DataFrame::new(vec![geometry, Series::new("name", ["a", "b", "c"])]).unwrap();
+
+        let path = std::env::temp_dir().join("geopolars_scan_ipc_with_bbox.arrow");
+        let mut file = File::create(&path).unwrap();
+        write_ipc(&df, &mut file, IpcWriteOptions { batch_size: 2 }).unwrap();
+
+        // The first batch overlaps the box, but only one of its rows lies inside
+        let result = GeoScan::ipc(&path)
+            .filter_bbox([0.5, 0.5, 2., 2.])
+            .finish()
+            .unwrap()
+            .collect()
+            .unwrap();
+        assert_eq!(result.column("name").unwrap(), &Series::new("name", ["b"]));
+
+        // The file is read on collect, with only the selected columns
+        let lf = GeoScan::ipc(&path)
+            .filter_bbox([0.5, 0.5, 2., 2.])
+            .finish()
+            .unwrap();
+        std::fs::write(&path, b"").unwrap();
+        assert!(lf.clone().collect().is_err());
+
+        write_ipc(
+            &df,
+            &mut File::create(&path).unwrap(),
+            IpcWriteOptions { batch_size: 2 },
+        )
+        .unwrap();
+        let result = lf.select([col("name")]).collect().unwrap();
+        assert_eq!(result.get_column_names(), ["name"]);
+        assert_eq!(result.column("name").unwrap(), &Series::new("name", ["b"]));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn scan_geoparquet_with_bbox() {
+        use crate::io::geoparquet::{write_geoparquet, GeoParquetWriteOptions};
+
+        let geometry = from_geom_vec(&[
+            Geometry::Point(point!(x: 0., y: 0.)),
+            Geometry::Point(point!(x: 1., y: 1.)),
+            Geometry::Point(point!(x: 10., y: 10.)),
+        ])
+        .unwrap();
+        let df = DataFrame::new(vec![geometry, Series::new("name", ["a", "b", "c"])]).unwrap();
+
+        let path = std::env::temp_dir().join("geopolars_scan_geoparquet_with_bbox.parquet");
+        write_geoparquet(
+            &df,
+            &mut File::create(&path).unwrap(),
+            GeoParquetWriteOptions { row_group_size: 2 },
+        )
+        .unwrap();
+
+        // The first row group overlaps the box, but only one of its rows lies inside
+        let result = GeoScan::geoparquet(&path)
+            .filter_bbox([0.5, 0.5, 2., 2.])
+            .finish()
+            .unwrap()
+            .select([col("name")])
+            .collect()
+            .unwrap();
+        assert_eq!(result.get_column_names(), ["name"]);
+        assert_eq!(result.column("name").unwrap(), &Series::new("name", ["b"]));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,310 @@
+use crate::error::{GeopolarsError, Result};
+use crate::io::dbf::{read_dbf, write_dbf};
+use crate::util::geom_at_index;
+use ::shapefile::{Multipoint, Point, Polygon, Polyline, Shape, ShapeReader, ShapeWriter};
+use geo::Geometry;
+use geopolars_geo::geoseries::GeoSeries;
+use geozero::{CoordDimensions, ToWkb};
+use polars::error::ErrString;
+use polars::export::arrow::array::{Array, BinaryArray};
+use polars::prelude::{DataFrame, PolarsError, Series};
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+fn shapefile_error(err: impl Display) -> GeopolarsError {
+    PolarsError::ComputeError(ErrString::from(format!("Shapefile error: {err}"))).into()
+}
+
+/// Read a file if it exists, returning `None` otherwise
+fn read_sidecar(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Read a Shapefile into a DataFrame
+///
+/// `path` points to the `.shp` file; the `.dbf`, `.prj` and `.cpg` sidecar files are expected
+/// next to it with the same file stem. Geometries are stored as WKB in a column named `geometry`,
+/// followed by one typed column per attribute in the `.dbf` file.
+///
+/// ```ignore
+/// let df = ShapefileReader::new("boroughs.shp").finish()?;
+/// ```
+pub struct ShapefileReader {
+    path: PathBuf,
+}
+
+impl ShapefileReader {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// The CRS of the layer, as the WKT stored in the `.prj` sidecar file
+    pub fn crs(&self) -> Result<Option<String>> {
+        Ok(read_sidecar(&self.path.with_extension("prj"))?.map(|wkt| wkt.trim().to_string()))
+    }
+
+    /// Read the layer. The geometry column's CRS is set from the `.prj` sidecar file, if present.
+    pub fn finish(self) -> Result<DataFrame> {
+        let shapes = ShapeReader::from_path(&self.path)
+            .map_err(shapefile_error)?
+            .read()
+            .map_err(shapefile_error)?;
+
+        let wkb_values = shapes
+            .into_iter()
+            .map(|shape| match shape {
+                Shape::NullShape => Ok(None),
+                shape => {
+                    let geom = Geometry::<f64>::try_from(shape).map_err(shapefile_error)?;
+                    Ok(Some(geom.to_wkb(CoordDimensions::xy())?))
+                }
+            })
+            .collect::<Result<Vec<Option<Vec<u8>>>>>()?;
+
+        let wkb_array = BinaryArray::<i32>::from(&wkb_values);
+        let mut geometry = Series::try_from(("geometry", Box::new(wkb_array) as Box<dyn Array>))?;
+        if let Some(crs) = self.crs()? {
+            geometry = geometry.set_crs(&crs)?;
+        }
+        let mut columns = vec![geometry];
+
+        let dbf_path = self.path.with_extension("dbf");
+        if dbf_path.exists() {
+            let cpg = read_sidecar(&self.path.with_extension("cpg"))?;
+            let mut dbf = BufReader::new(File::open(dbf_path)?);
+            let attributes = read_dbf(&mut dbf, cpg.as_deref())?;
+
+            if let Some(column) = attributes.first() {
+                if column.len() != wkb_values.len() {
+                    return Err(shapefile_error(format!(
+                        "found {} shapes but {} attribute records",
+                        wkb_values.len(),
+                        column.len()
+                    )));
+                }
+            }
+            columns.extend(attributes);
+        }
+
+        Ok(DataFrame::new(columns)?)
+    }
+}
+
+/// Write a DataFrame to a Shapefile
+///
+/// `path` points to the `.shp` file to create; the `.shx`, `.dbf` and `.cpg` files, as well as
+/// the `.prj` file if the layer has a CRS, are written next to it. Attributes are always encoded
+/// as UTF-8.
+///
+/// A Shapefile stores a single geometry type, so every geometry in the `geometry` column must be
+/// of the same dimension (points, lines or polygons) and none may be null.
+pub struct ShapefileWriter {
+    path: PathBuf,
+    crs: Option<String>,
+}
+
+impl ShapefileWriter {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            crs: None,
+        }
+    }
+
+    /// Set the CRS of the layer, as WKT to be written to the `.prj` sidecar file. Defaults to the
+    /// CRS of the geometry column, if any.
+    pub fn with_crs(mut self, wkt: impl Into<String>) -> Self {
+        self.crs = Some(wkt.into());
+        self
+    }
+
+    pub fn finish(self, df: &DataFrame) -> Result<()> {
+        let geometry = df.column("geometry")?;
+        let geoms = (0..geometry.len())
+            .map(|idx| geom_at_index(geometry, idx))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let writer = ShapeWriter::from_path(&self.path).map_err(shapefile_error)?;
+        match geoms.first() {
+            Some(Geometry::Point(_)) => {
+                let shapes = geoms
+                    .into_iter()
+                    .map(|geom| match geom {
+                        Geometry::Point(point) => Ok(Point::from(point)),
+                        _ => Err(mixed_geometry_error()),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                writer.write_shapes(&shapes).map_err(shapefile_error)?;
+            }
+            Some(Geometry::MultiPoint(_)) => {
+                let shapes = geoms
+                    .into_iter()
+                    .map(|geom| match geom {
+                        Geometry::MultiPoint(multi_point) => Ok(Multipoint::from(multi_point)),
+                        _ => Err(mixed_geometry_error()),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                writer.write_shapes(&shapes).map_err(shapefile_error)?;
+            }
+            Some(Geometry::LineString(_) | Geometry::MultiLineString(_)) => {
+                let shapes = geoms
+                    .into_iter()
+                    .map(|geom| match geom {
+                        Geometry::LineString(line_string) => Ok(Polyline::from(line_string)),
+                        Geometry::MultiLineString(multi_line_string) => {
+                            Ok(Polyline::from(multi_line_string))
+                        }
+                        _ => Err(mixed_geometry_error()),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                writer.write_shapes(&shapes).map_err(shapefile_error)?;
+            }
+            Some(Geometry::Polygon(_) | Geometry::MultiPolygon(_)) => {
+                let shapes = geoms
+                    .into_iter()
+                    .map(|geom| match geom {
+                        Geometry::Polygon(polygon) => Ok(Polygon::from(polygon)),
+                        Geometry::MultiPolygon(multi_polygon) => Ok(Polygon::from(multi_polygon)),
+                        _ => Err(mixed_geometry_error()),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                writer.write_shapes(&shapes).map_err(shapefile_error)?;
+            }
+            Some(_) => return Err(shapefile_error("unsupported geometry type")),
+            None => return Err(shapefile_error("cannot write an empty DataFrame")),
+        }
+
+        let mut dbf = BufWriter::new(File::create(self.path.with_extension("dbf"))?);
+        write_dbf(df, &mut dbf)?;
+        fs::write(self.path.with_extension("cpg"), "UTF-8")?;
+
+        let crs = match self.crs {
+            Some(crs) => Some(crs),
+            None => geometry.crs()?,
+        };
+        if let Some(crs) = crs {
+            fs::write(self.path.with_extension("prj"), crs)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn mixed_geometry_error() -> GeopolarsError {
+    shapefile_error("all geometries must have the same dimension")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ShapefileReader, ShapefileWriter};
+    use crate::util::geom_at_index;
+    use geo::{point, polygon, Geometry};
+    use geopolars_geo::geoseries::GeoSeries;
+    use geopolars_geo::util::from_geom_vec;
+    use polars::prelude::{DataFrame, DataType, NamedFrom, Series};
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// An empty directory for the files of a test, unique to the test and the test process
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "geopolars_shapefile_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn round_trip() {
+        let geometry = from_geom_vec(&[
+            Geometry::Polygon(polygon![
+                (x: 0., y: 0.),
+                (x: 0., y: 10.),
+                (x: 10., y: 10.),
+                (x: 10., y: 0.),
+                (x: 0., y: 0.),
+            ]),
+            Geometry::Polygon(polygon![
+                (x: 20., y: 0.),
+                (x: 20., y: 10.),
+                (x: 30., y: 10.),
+                (x: 30., y: 0.),
+                (x: 20., y: 0.),
+            ]),
+        ])
+        .unwrap();
+        let name = Series::new("name", ["Zürich", "Москва"]);
+        let code = Series::new("code", [Some(i64::MIN), None]);
+        let share = Series::new("share", [0.1_f64, 123_456_789_012.345]);
+        let df = DataFrame::new(vec![geometry, name, code, share]).unwrap();
+
+        let dir = test_dir("round_trip");
+        let path = dir.join("layer.shp");
+        ShapefileWriter::new(&path)
+            .with_crs("GEOGCS[\"WGS 84\"]")
+            .finish(&df)
+            .unwrap();
+
+        let reader = ShapefileReader::new(&path);
+        assert_eq!(reader.crs().unwrap().unwrap(), "GEOGCS[\"WGS 84\"]");
+
+        let result = reader.finish().unwrap();
+        assert_eq!(
+            result.column("geometry").unwrap().crs().unwrap().unwrap(),
+            "GEOGCS[\"WGS 84\"]"
+        );
+        assert_eq!(result.shape(), (2, 4));
+        assert_eq!(
+            result.column("name").unwrap(),
+            &Series::new("name", ["Zürich", "Москва"])
+        );
+        assert_eq!(result.column("code").unwrap().dtype(), &DataType::Int64);
+        assert_eq!(
+            result.column("code").unwrap(),
+            &Series::new("code", [Some(i64::MIN), None])
+        );
+        assert_eq!(result.column("share").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(
+            result.column("share").unwrap(),
+            &Series::new("share", [0.1_f64, 123_456_789_012.345])
+        );
+        assert_eq!(
+            geom_at_index(result.column("geometry").unwrap(), 0).unwrap(),
+            geom_at_index(df.column("geometry").unwrap(), 0).unwrap()
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn crs_of_geometry_column() {
+        let geometry = from_geom_vec(&[Geometry::Point(point!(x: 1., y: 2.))])
+            .unwrap()
+            .set_crs("GEOGCS[\"WGS 84\"]")
+            .unwrap();
+        let df = DataFrame::new(vec![geometry]).unwrap();
+
+        let dir = test_dir("crs_of_geometry_column");
+        let path = dir.join("layer.shp");
+        ShapefileWriter::new(&path).finish(&df).unwrap();
+
+        let result = ShapefileReader::new(&path).finish().unwrap();
+        assert_eq!(
+            result.column("geometry").unwrap().crs().unwrap().unwrap(),
+            "GEOGCS[\"WGS 84\"]"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
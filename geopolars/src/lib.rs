@@ -1,3 +1,7 @@
+pub mod clip;
+#[cfg(feature = "lazy")]
+pub mod expr;
+pub mod io;
 pub mod spatial_index;
 pub mod util;
 
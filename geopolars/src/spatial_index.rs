@@ -11,6 +11,7 @@ use polars::prelude::{
 use rstar::{RTree, RTreeObject, AABB};
 
 use crate::util::{geom_at_index, iter_geom, Predicate};
+use geopolars_geo::crs::check_crs_match;
 
 pub struct SpatialJoinArgs<'a> {
     pub join_type: JoinType,
@@ -43,6 +44,8 @@ pub fn spatial_join(
 
     let lhs_geometry = lhs.column("geometry")?;
     let rhs_geometry = rhs.column("geometry")?;
+    check_crs_match(lhs_geometry, rhs_geometry)
+        .map_err(|err| PolarsError::ComputeError(ErrString::from(err.to_string())))?;
 
     // If we were not given a left index, generate one on the fly
     let spatial_index_left: Arc<SpatialIndex> = options.l_index.unwrap_or_else(|| {
@@ -349,6 +352,7 @@ impl TryFrom<Series> for SpatialIndex {
 mod tests {
     use crate::spatial_index::{spatial_join, SpatialIndex, SpatialJoinArgs};
     use geo::{polygon, Geometry, Point, Polygon};
+    use geopolars_geo::geoseries::GeoSeries;
     use geopolars_geo::util::from_geom_vec;
     use polars::prelude::{DataFrame, JoinType, NamedFrom, PolarsError, Series};
     use rstar::AABB;
@@ -618,4 +622,32 @@ mod tests {
         println!("inner {}", inner_result);
         println!("left {}", left_result);
     }
+
+    #[test]
+    fn spatial_join_crs_mismatch() {
+        let point_series = from_geom_vec(&[Geometry::Point(Point::new(1.0, 1.0))])
+            .unwrap()
+            .set_crs("EPSG:4326")
+            .unwrap();
+        let point_df: DataFrame = DataFrame::new(vec![point_series]).unwrap();
+
+        let polygon_series = from_geom_vec(&[Geometry::Polygon(polygon![
+            (x:0., y:0.),
+            (x:20., y:0.),
+            (x:20., y:20.),
+            (x:0., y: 20.)
+        ])])
+        .unwrap()
+        .set_crs("EPSG:3857")
+        .unwrap();
+        let polygon_df: DataFrame = DataFrame::new(vec![polygon_series]).unwrap();
+
+        let result = spatial_join(&point_df, &polygon_df, SpatialJoinArgs::default());
+        match result {
+            Err(PolarsError::ComputeError(msg)) => {
+                assert!(msg.to_string().contains("CRS mismatch"))
+            }
+            _ => panic!("expected a CRS mismatch error"),
+        }
+    }
 }
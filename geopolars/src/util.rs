@@ -1,18 +1,14 @@
-use geo::{Coord, Geometry, LineString, Point, Polygon};
+use crate::error::GeopolarsError;
+use geo::{Coord, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
 use geozero::{wkb::Wkb, ToGeo};
-use polars::datatypes::{AnyValue, DataType};
-use polars::export::arrow::array::{ListArray, PrimitiveArray, StructArray};
+use polars::datatypes::AnyValue;
+use polars::error::ErrString;
+use polars::export::arrow::array::{Array, ListArray, PrimitiveArray, StructArray};
 use polars::export::num;
 use polars::prelude::{PolarsError, PolarsResult, Series};
 use std::convert::Into;
 
-// TODO: the rest of the code here can probably be removed
-pub enum GeoArrowType {
-    Point,
-    LineString,
-    Polygon,
-    WKB,
-}
+pub use geopolars_geo::extension::GeoArrowType;
 
 /// Helper function to iterate over geometries from polars Series
 pub(crate) fn iter_geom(series: &Series) -> impl Iterator<Item = Geometry<f64>> + '_ {
@@ -29,16 +25,16 @@ pub(crate) fn iter_geom(series: &Series) -> impl Iterator<Item = Geometry<f64>>
 
 /// Access to a geometry at a specified index
 pub fn geom_at_index(series: &Series, index: usize) -> PolarsResult<Geometry<f64>> {
-    match series.dtype() {
-        DataType::Binary => geom_at_index_wkb(series, index),
-        DataType::Struct(_) => geom_at_index_point(series, index),
-        DataType::List(dt) => match *dt.clone() {
-            DataType::Struct(_) => geom_at_index_linestring(series, index),
-            DataType::List(_) => geom_at_index_polygon(series, index),
-            _ => unimplemented!(),
-        },
-
-        _ => unimplemented!(),
+    let geoarrow_type = get_geoarrow_type(series)
+        .map_err(|err| PolarsError::ComputeError(ErrString::from(err.to_string())))?;
+    match geoarrow_type {
+        GeoArrowType::WKB => geom_at_index_wkb(series, index),
+        GeoArrowType::Point => geom_at_index_point(series, index),
+        GeoArrowType::LineString => geom_at_index_linestring(series, index),
+        GeoArrowType::Polygon => geom_at_index_polygon(series, index),
+        GeoArrowType::MultiPoint => geom_at_index_multipoint(series, index),
+        GeoArrowType::MultiLineString => geom_at_index_multilinestring(series, index),
+        GeoArrowType::MultiPolygon => geom_at_index_multipolygon(series, index),
     }
 }
 
@@ -48,33 +44,23 @@ fn geom_at_index_wkb(series: &Series, index: usize) -> PolarsResult<Geometry<f64
         _ => return Err(PolarsError::SchemaMisMatch("".into())),
     };
 
-    let geom = Wkb(buffer.to_vec())
+    Wkb(buffer.to_vec())
         .to_geo()
-        .expect("unable to convert geo");
-    Ok(geom)
+        .map_err(|err| to_polars_error(err.into()))
 }
 
 /// Access geo point out of geoarrow point column at given index
 fn geom_at_index_point(series: &Series, index: usize) -> PolarsResult<Geometry<f64>> {
     let (chunk_idx, local_idx) = index_to_chunked_index(series, index);
 
-    let struct_array = series.chunks()[chunk_idx]
-        .as_any()
-        .downcast_ref::<StructArray>()
-        .unwrap();
+    let struct_array = downcast::<StructArray>(series.chunks()[chunk_idx].as_ref())?;
 
     let struct_array_values = struct_array.values();
     let x_arrow_array = &struct_array_values[0];
     let y_arrow_array = &struct_array_values[1];
 
-    let x_array_values = x_arrow_array
-        .as_any()
-        .downcast_ref::<PrimitiveArray<f64>>()
-        .unwrap();
-    let y_array_values = y_arrow_array
-        .as_any()
-        .downcast_ref::<PrimitiveArray<f64>>()
-        .unwrap();
+    let x_array_values = downcast::<PrimitiveArray<f64>>(x_arrow_array.as_ref())?;
+    let y_array_values = downcast::<PrimitiveArray<f64>>(y_arrow_array.as_ref())?;
 
     let p = Point::new(
         x_array_values.value(local_idx),
@@ -87,16 +73,10 @@ fn geom_at_index_point(series: &Series, index: usize) -> PolarsResult<Geometry<f
 fn geom_at_index_linestring(series: &Series, index: usize) -> PolarsResult<Geometry<f64>> {
     let (chunk_idx, local_idx) = index_to_chunked_index(series, index);
 
-    let list_array = series.chunks()[chunk_idx]
-        .as_any()
-        .downcast_ref::<ListArray<i64>>()
-        .unwrap();
+    let list_array = downcast::<ListArray<i64>>(series.chunks()[chunk_idx].as_ref())?;
     let inner_dyn_array = list_array.value(local_idx);
 
-    let struct_array = inner_dyn_array
-        .as_any()
-        .downcast_ref::<StructArray>()
-        .unwrap();
+    let struct_array = downcast::<StructArray>(inner_dyn_array.as_ref())?;
 
     let l = parse_linestring(struct_array)?;
     Ok(Geometry::LineString(l))
@@ -109,14 +89,8 @@ fn parse_linestring(linestring: &StructArray) -> PolarsResult<LineString<f64>> {
     let x_arrow_array = &struct_array_values[0];
     let y_arrow_array = &struct_array_values[1];
 
-    let x_array_values = x_arrow_array
-        .as_any()
-        .downcast_ref::<PrimitiveArray<f64>>()
-        .unwrap();
-    let y_array_values = y_arrow_array
-        .as_any()
-        .downcast_ref::<PrimitiveArray<f64>>()
-        .unwrap();
+    let x_array_values = downcast::<PrimitiveArray<f64>>(x_arrow_array.as_ref())?;
+    let y_array_values = downcast::<PrimitiveArray<f64>>(y_arrow_array.as_ref())?;
 
     let mut coords: Vec<Coord> = Vec::with_capacity(x_array_values.len());
     for i in 0..x_array_values.len() {
@@ -133,37 +107,98 @@ fn geom_at_index_polygon(series: &Series, index: usize) -> PolarsResult<Geometry
     let (chunk_idx, local_idx) = index_to_chunked_index(series, index);
     let geometry_dyn_array = &series.chunks()[chunk_idx];
 
-    let geometry_array = geometry_dyn_array
-        .as_any()
-        .downcast_ref::<ListArray<i64>>()
-        .unwrap();
+    let geometry_array = downcast::<ListArray<i64>>(geometry_dyn_array.as_ref())?;
 
     let ring_dyn_array = geometry_array.value(local_idx);
-    let ring_array = ring_dyn_array
-        .as_any()
-        .downcast_ref::<ListArray<i64>>()
-        .unwrap();
+    let ring_array = downcast::<ListArray<i64>>(ring_dyn_array.as_ref())?;
 
+    let p = parse_polygon(ring_array)?;
+    Ok(Geometry::Polygon(p))
+}
+
+/// Parse a slice of a list array into a geo Polygon
+/// The slice is expected to be a ListArray of rings, the first of which is the exterior
+fn parse_polygon(ring_array: &ListArray<i64>) -> PolarsResult<Polygon<f64>> {
     let exterior_ring_dyn = ring_array.value(0);
-    let exterior_ring = exterior_ring_dyn
-        .as_any()
-        .downcast_ref::<StructArray>()
-        .unwrap();
+    let exterior_ring = downcast::<StructArray>(exterior_ring_dyn.as_ref())?;
 
     let exterior_linestring = parse_linestring(exterior_ring)?;
 
     let mut interior_rings: Vec<LineString<f64>> = Vec::with_capacity(ring_array.len() - 1);
     for ring_index in 1..ring_array.len() {
         let interior_ring_dyn = ring_array.value(ring_index);
-        let interior_ring = interior_ring_dyn
-            .as_any()
-            .downcast_ref::<StructArray>()
-            .unwrap();
+        let interior_ring = downcast::<StructArray>(interior_ring_dyn.as_ref())?;
         interior_rings.push(parse_linestring(interior_ring)?);
     }
 
-    let p = Polygon::new(exterior_linestring, interior_rings);
-    Ok(Geometry::Polygon(p))
+    Ok(Polygon::new(exterior_linestring, interior_rings))
+}
+
+/// Access a single MultiPoint out of a GeoArrow MultiPoint column
+fn geom_at_index_multipoint(series: &Series, index: usize) -> PolarsResult<Geometry<f64>> {
+    let (chunk_idx, local_idx) = index_to_chunked_index(series, index);
+
+    let list_array = downcast::<ListArray<i64>>(series.chunks()[chunk_idx].as_ref())?;
+    let inner_dyn_array = list_array.value(local_idx);
+
+    let struct_array = downcast::<StructArray>(inner_dyn_array.as_ref())?;
+
+    let points = parse_linestring(struct_array)?.into_points();
+    Ok(Geometry::MultiPoint(MultiPoint::new(points)))
+}
+
+/// Access a single MultiLineString out of a GeoArrow MultiLineString column
+fn geom_at_index_multilinestring(series: &Series, index: usize) -> PolarsResult<Geometry<f64>> {
+    let (chunk_idx, local_idx) = index_to_chunked_index(series, index);
+
+    let geometry_array = downcast::<ListArray<i64>>(series.chunks()[chunk_idx].as_ref())?;
+
+    let part_dyn_array = geometry_array.value(local_idx);
+    let part_array = downcast::<ListArray<i64>>(part_dyn_array.as_ref())?;
+
+    let mut line_strings: Vec<LineString<f64>> = Vec::with_capacity(part_array.len());
+    for part_index in 0..part_array.len() {
+        let part_dyn = part_array.value(part_index);
+        let part = downcast::<StructArray>(part_dyn.as_ref())?;
+        line_strings.push(parse_linestring(part)?);
+    }
+
+    Ok(Geometry::MultiLineString(MultiLineString::new(
+        line_strings,
+    )))
+}
+
+/// Access a single MultiPolygon out of a GeoArrow MultiPolygon column
+fn geom_at_index_multipolygon(series: &Series, index: usize) -> PolarsResult<Geometry<f64>> {
+    let (chunk_idx, local_idx) = index_to_chunked_index(series, index);
+
+    let geometry_array = downcast::<ListArray<i64>>(series.chunks()[chunk_idx].as_ref())?;
+
+    let polygon_dyn_array = geometry_array.value(local_idx);
+    let polygon_array = downcast::<ListArray<i64>>(polygon_dyn_array.as_ref())?;
+
+    let mut polygons: Vec<Polygon<f64>> = Vec::with_capacity(polygon_array.len());
+    for polygon_index in 0..polygon_array.len() {
+        let ring_dyn_array = polygon_array.value(polygon_index);
+        let ring_array = downcast::<ListArray<i64>>(ring_dyn_array.as_ref())?;
+        polygons.push(parse_polygon(ring_array)?);
+    }
+
+    Ok(Geometry::MultiPolygon(MultiPolygon::new(polygons)))
+}
+
+/// Downcast the array of a geometry column, or return an error for an unexpected Arrow type
+fn downcast<T: 'static>(array: &dyn Array) -> PolarsResult<&T> {
+    array.as_any().downcast_ref::<T>().ok_or_else(|| {
+        to_polars_error(GeopolarsError::UnexpectedArrowType(format!(
+            "{:?}",
+            array.data_type()
+        )))
+    })
+}
+
+fn to_polars_error(err: GeopolarsError) -> PolarsError {
+    PolarsError::ComputeError(ErrString::from(err.to_string()))
 }
 
 pub enum Predicate {
@@ -209,16 +244,10 @@ pub(crate) fn _index_to_chunked_index<
     (current_chunk_idx, index_remainder)
 }
 
-pub fn get_geoarrow_type(series: &Series) -> GeoArrowType {
-    match series.dtype() {
-        DataType::Binary => GeoArrowType::WKB,
-        DataType::Struct(_) => GeoArrowType::Point,
-        DataType::List(dt) => match *dt.clone() {
-            DataType::Struct(_) => GeoArrowType::LineString,
-            DataType::List(_) => GeoArrowType::Polygon,
-            _ => panic!("Unexpected inner list type: {}", dt),
-        },
-
-        dt => panic!("Unexpected geoarrow type: {}", dt),
-    }
+/// The logical geometry type of a geometry Series
+///
+/// Uses the GeoArrow extension type attached to the Series if there is one, and otherwise infers
+/// the type from the physical dtype.
+pub fn get_geoarrow_type(series: &Series) -> crate::error::Result<GeoArrowType> {
+    geopolars_geo::extension::geoarrow_type(series)
 }
@@ -162,12 +162,14 @@ pub(crate) fn to_crs(
 ) -> PyResult<PyObject> {
     let proj_options = ProjOptions {
         search_paths: Some(vec![proj_data_dir]),
+        ..Default::default()
     };
 
     let series = ffi::py_series_to_rust_series(series)?;
 
     let out = series
-        .to_crs_with_options(from, to, proj_options)
+        .set_crs(from)
+        .and_then(|series| series.to_crs_with_options(to, proj_options))
         .map_err(PyGeopolarsError::from)?;
     ffi::rust_series_to_py_geoseries(&out)
 }